@@ -0,0 +1,248 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use chrono::{Datelike, NaiveDate};
+
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::{serialize_integer_to_vec, try_deserialize_integer_from_vec};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalendarEntry {
+    // Inclusive range of dates, a single date starts and ends on the same day
+    Range(NaiveDate, NaiveDate),
+    // Inclusive range of (month, day) pairs repeating every year, wraps around the end of the year
+    // if it ends before it starts
+    YearlyRange((u8, u8), (u8, u8)),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calendar {
+    pub entries: Vec<CalendarEntry>,
+}
+
+impl CalendarEntry {
+    pub fn contains(&self, date: &NaiveDate) -> bool {
+        match self {
+            CalendarEntry::Range(from, to) => date >= from && date <= to,
+            CalendarEntry::YearlyRange(from, to) => {
+                let day = (date.month() as u8, date.day() as u8);
+
+                if from <= to {
+                    day >= *from && day <= *to
+                } else {
+                    day >= *from || day <= *to
+                }
+            }
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match self {
+            CalendarEntry::Range(from, to) => from <= to,
+            CalendarEntry::YearlyRange(from, to) => {
+                is_valid_yearly_day(*from) && is_valid_yearly_day(*to)
+            }
+        }
+    }
+}
+
+// Checked against a leap year, so February 29th is accepted
+fn is_valid_yearly_day((month, day): (u8, u8)) -> bool {
+    NaiveDate::from_ymd_opt(2000, month as u32, day as u32).is_some()
+}
+
+impl Calendar {
+    pub fn contains(&self, date: &NaiveDate) -> bool {
+        self.entries.iter().any(|entry| entry.contains(date))
+    }
+}
+
+impl Serialize for CalendarEntry {
+    fn serialize(&self) -> Vec<u8> {
+        match self {
+            CalendarEntry::Range(from, to) => {
+                let mut data = vec![0x00];
+
+                for date in [from, to].iter() {
+                    serialize_integer_to_vec!(data, date.year(), u16);
+                    data.push(date.month() as u8);
+                    data.push(date.day() as u8);
+                }
+
+                data
+            }
+            CalendarEntry::YearlyRange(from, to) => vec![0x01, from.0, from.1, to.0, to.1],
+        }
+    }
+}
+
+impl TryDeserialize for CalendarEntry {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.is_empty() {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        let entry = match data[0] {
+            0x00 => {
+                if data.len() < 9 {
+                    return Err(ConfigSerializerError::WrongSize);
+                }
+
+                let mut offset = 1;
+                let mut dates = vec![];
+
+                for _ in 0..2 {
+                    let year = try_deserialize_integer_from_vec!(data, offset, u16);
+                    let month = try_deserialize_integer_from_vec!(data, offset, u8);
+                    let day = try_deserialize_integer_from_vec!(data, offset, u8);
+
+                    match NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32) {
+                        Some(date) => dates.push(date),
+                        None => return Err(ConfigSerializerError::InvalidCalendar),
+                    }
+                }
+
+                CalendarEntry::Range(dates[0], dates[1])
+            }
+            0x01 => {
+                if data.len() < 5 {
+                    return Err(ConfigSerializerError::WrongSize);
+                }
+
+                CalendarEntry::YearlyRange((data[1], data[2]), (data[3], data[4]))
+            }
+            _ => return Err(ConfigSerializerError::UnknownEnumVariant),
+        };
+
+        if !entry.is_valid() {
+            return Err(ConfigSerializerError::InvalidCalendar);
+        }
+
+        Ok(Box::new(entry))
+    }
+}
+
+impl Serialize for Calendar {
+    fn serialize(&self) -> Vec<u8> {
+        let mut data = vec![];
+
+        serialize_integer_to_vec!(data, self.entries.len(), u32);
+
+        for entry in self.entries.iter() {
+            let mut entry_data = entry.serialize();
+            serialize_integer_to_vec!(data, entry_data.len(), u8);
+            data.append(&mut entry_data);
+        }
+
+        data
+    }
+}
+
+impl TryDeserialize for Calendar {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        let mut offset = 0;
+
+        let entry_count = try_deserialize_integer_from_vec!(data, offset, u32);
+
+        let mut entries = vec![];
+
+        for _ in 0..entry_count {
+            let entry_len = try_deserialize_integer_from_vec!(data, offset, u8) as usize;
+
+            if data.len() < offset + entry_len {
+                return Err(ConfigSerializerError::WrongSize);
+            }
+
+            entries.push(*CalendarEntry::try_deserialize(
+                &data[offset..offset + entry_len],
+            )?);
+            offset += entry_len;
+        }
+
+        Ok(Box::new(Self { entries }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calendar() -> Calendar {
+        Calendar {
+            entries: vec![
+                CalendarEntry::Range(
+                    NaiveDate::from_ymd(2022, 7, 11),
+                    NaiveDate::from_ymd(2022, 7, 22),
+                ),
+                CalendarEntry::YearlyRange((12, 24), (1, 1)),
+            ],
+        }
+    }
+
+    #[test]
+    fn contains_test() {
+        let calendar = calendar();
+
+        assert!(calendar.contains(&NaiveDate::from_ymd(2022, 7, 11)));
+        assert!(calendar.contains(&NaiveDate::from_ymd(2022, 7, 22)));
+        assert!(!calendar.contains(&NaiveDate::from_ymd(2022, 7, 23)));
+        assert!(!calendar.contains(&NaiveDate::from_ymd(2023, 7, 15)));
+        assert!(calendar.contains(&NaiveDate::from_ymd(2022, 12, 31)));
+        assert!(calendar.contains(&NaiveDate::from_ymd(2031, 1, 1)));
+        assert!(!calendar.contains(&NaiveDate::from_ymd(2031, 1, 2)));
+        assert!(!calendar.contains(&NaiveDate::from_ymd(2031, 12, 23)));
+    }
+
+    #[test]
+    fn serialize_test() {
+        let expected_data = vec![
+            0x00, 0x00, 0x00, 0x02, // entry count
+            0x09, // entry len
+            0x00, 0x07, 0xe6, 0x07, 0x0b, 0x07, 0xe6, 0x07, 0x16, // range
+            0x05, // entry len
+            0x01, 0x0c, 0x18, 0x01, 0x01, // yearly range
+        ];
+
+        assert_eq!(calendar().serialize(), expected_data);
+    }
+
+    #[test]
+    fn deserialize_test() {
+        let data = vec![
+            0x00, 0x00, 0x00, 0x02, // entry count
+            0x09, // entry len
+            0x00, 0x07, 0xe6, 0x07, 0x0b, 0x07, 0xe6, 0x07, 0x16, // range
+            0x05, // entry len
+            0x01, 0x0c, 0x18, 0x01, 0x01, // yearly range
+        ];
+
+        assert_eq!(Calendar::try_deserialize(&data), Ok(Box::new(calendar())));
+    }
+
+    #[test]
+    fn deserialize_invalid_test() {
+        let data = vec![
+            0x00, 0x00, 0x00, 0x01, // entry count
+            0x05, // entry len
+            0x01, 0x02, 0x1e, 0x03, 0x01, // yearly range
+        ];
+
+        assert_eq!(
+            Calendar::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidCalendar)
+        );
+
+        let data = vec![
+            0x00, 0x00, 0x00, 0x01, // entry count
+            0x09, // entry len
+            0x00, 0x07, 0xe6, 0x07, 0x16, 0x07, 0xe6, 0x07, 0x0b, // range
+        ];
+
+        assert_eq!(
+            Calendar::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidCalendar)
+        );
+    }
+}
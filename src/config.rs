@@ -3,8 +3,15 @@ extern crate alloc;
 use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
 
+use crate::calendar::Calendar;
 use crate::event_processor::EventProcessor;
+use crate::gesture::GestureTimings;
 use crate::peripheral::Peripheral;
+use crate::time_zone::TimeZone;
+use crate::validator::{
+    validate_calendar_references, validate_peripheral_references, validate_state_types,
+    validate_value_types, ValidationError,
+};
 use crate::Value;
 
 #[derive(Debug)]
@@ -12,4 +19,22 @@ pub struct Config {
     pub peripherals: BTreeMap<u32, Peripheral>,
     pub initial_state: BTreeMap<u32, Value>,
     pub event_processors: Vec<EventProcessor>,
+    pub time_zone: Option<TimeZone>,
+    pub calendars: BTreeMap<u32, Calendar>,
+    pub gesture_timings: Option<GestureTimings>,
+}
+
+impl Config {
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = validate_state_types(self);
+        errors.append(&mut validate_value_types(self));
+        errors.append(&mut validate_peripheral_references(self));
+        errors.append(&mut validate_calendar_references(self));
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
 }
@@ -0,0 +1,291 @@
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::config::Config;
+use crate::matcher::intersect_event_codes;
+use crate::producer::ProducerTarget;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OutputTarget {
+    Bcm(u16, u8),
+    Relay(u16, u8),
+    Message(u16, u16),
+}
+
+impl From<&ProducerTarget> for OutputTarget {
+    fn from(target: &ProducerTarget) -> Self {
+        match target {
+            ProducerTarget::Bcm {
+                bcm_address, index, ..
+            } => OutputTarget::Bcm(*bcm_address, *index),
+            ProducerTarget::Relay {
+                relay_address,
+                index,
+                ..
+            } => OutputTarget::Relay(*relay_address, *index),
+            ProducerTarget::Message {
+                receiver_address,
+                code,
+                ..
+            } => OutputTarget::Message(*receiver_address, *code),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct OutputConflict {
+    pub target: OutputTarget,
+    pub event_processors: (usize, usize),
+    // The event codes both event processors can fire on, `None` if any event can trigger both
+    pub event_codes: Option<BTreeSet<u16>>,
+}
+
+pub fn find_output_conflicts(config: &Config) -> Vec<OutputConflict> {
+    let mut producers_by_target = BTreeMap::new();
+
+    for (event_processor_index, event_processor) in config.event_processors.iter().enumerate() {
+        let event_processor_event_codes = event_processor.matcher.get_event_codes();
+
+        for creator in event_processor.creators.iter() {
+            let target = match creator.producer.get_target() {
+                Some(target) => target,
+                None => continue,
+            };
+
+            let creator_event_codes = creator
+                .matcher
+                .as_ref()
+                .and_then(|matcher| matcher.get_event_codes());
+
+            let event_codes =
+                intersect_event_codes(event_processor_event_codes.clone(), creator_event_codes);
+
+            producers_by_target
+                .entry(OutputTarget::from(&target))
+                .or_insert_with(Vec::new)
+                .push((event_processor_index, target, event_codes));
+        }
+    }
+
+    let mut conflicts = vec![];
+
+    for (output_target, producers) in producers_by_target.iter() {
+        for (i, (event_processor1, target1, event_codes1)) in producers.iter().enumerate() {
+            for (event_processor2, target2, event_codes2) in producers[i + 1..].iter() {
+                // Producers in the same event processor always run in a fixed order
+                if event_processor1 == event_processor2 || target1 == target2 {
+                    continue;
+                }
+
+                let event_codes = intersect_event_codes(event_codes1.clone(), event_codes2.clone());
+
+                if let Some(event_codes) = &event_codes {
+                    if event_codes.is_empty() {
+                        continue;
+                    }
+                }
+
+                conflicts.push(OutputConflict {
+                    target: *output_target,
+                    event_processors: (*event_processor1, *event_processor2),
+                    event_codes,
+                });
+            }
+        }
+    }
+
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::boxed::Box;
+    use core::str::FromStr;
+
+    use ross_protocol::event::event_code::{BUTTON_PRESSED_EVENT_CODE, BUTTON_RELEASED_EVENT_CODE};
+    use ross_protocol::event::relay::RelayValue;
+
+    use crate::creator::Creator;
+    use crate::cron::CronExpression;
+    use crate::event_processor::EventProcessor;
+    use crate::extractor::{ButtonIndexExtractor, EventCodeExtractor, NoneExtractor};
+    use crate::filter::{CoercionPolicy, TimeMatchesCronExpressionFilter, ValueEqualToConstFilter};
+    use crate::matcher::Matcher;
+    use crate::producer::RelaySetValueProducer;
+    use crate::Value;
+
+    fn event_processor(matcher: Matcher, relay_value: RelayValue) -> EventProcessor {
+        EventProcessor {
+            matcher,
+            creators: vec![Creator {
+                extractor: Box::new(NoneExtractor::new()),
+                producer: Box::new(RelaySetValueProducer::new(0xabab, 0x01, relay_value)),
+                matcher: None,
+            }],
+        }
+    }
+
+    fn event_code_matcher(event_code: u16) -> Matcher {
+        Matcher::Single {
+            extractor: Box::new(EventCodeExtractor::new()),
+            filter: Box::new(ValueEqualToConstFilter::new(
+                Value::U16(event_code),
+                CoercionPolicy::Strict,
+            )),
+        }
+    }
+
+    #[test]
+    fn conflict_test() {
+        let config = Config {
+            peripherals: BTreeMap::new(),
+            initial_state: BTreeMap::new(),
+            event_processors: vec![
+                event_processor(
+                    event_code_matcher(BUTTON_PRESSED_EVENT_CODE),
+                    RelayValue::Single(true),
+                ),
+                event_processor(
+                    Matcher::Single {
+                        extractor: Box::new(ButtonIndexExtractor::new()),
+                        filter: Box::new(ValueEqualToConstFilter::new(
+                            Value::U8(0x00),
+                            CoercionPolicy::Strict,
+                        )),
+                    },
+                    RelayValue::Single(false),
+                ),
+            ],
+            time_zone: None,
+            calendars: BTreeMap::new(),
+            gesture_timings: None,
+        };
+
+        let mut expected_event_codes = BTreeSet::new();
+        expected_event_codes.insert(BUTTON_PRESSED_EVENT_CODE);
+
+        assert_eq!(
+            find_output_conflicts(&config),
+            vec![OutputConflict {
+                target: OutputTarget::Relay(0xabab, 0x01),
+                event_processors: (0, 1),
+                event_codes: Some(expected_event_codes),
+            }]
+        );
+    }
+
+    #[test]
+    fn time_gated_conflict_test() {
+        let config = Config {
+            peripherals: BTreeMap::new(),
+            initial_state: BTreeMap::new(),
+            event_processors: vec![
+                event_processor(
+                    event_code_matcher(BUTTON_PRESSED_EVENT_CODE),
+                    RelayValue::Single(true),
+                ),
+                event_processor(
+                    Matcher::And(
+                        Box::new(event_code_matcher(BUTTON_PRESSED_EVENT_CODE)),
+                        Box::new(Matcher::Single {
+                            extractor: Box::new(NoneExtractor::new()),
+                            filter: Box::new(TimeMatchesCronExpressionFilter::new(
+                                CronExpression::from_str("0 * 22-23 * * *").unwrap(),
+                            )),
+                        }),
+                    ),
+                    RelayValue::Single(false),
+                ),
+            ],
+            time_zone: None,
+            calendars: BTreeMap::new(),
+            gesture_timings: None,
+        };
+
+        let mut expected_event_codes = BTreeSet::new();
+        expected_event_codes.insert(BUTTON_PRESSED_EVENT_CODE);
+
+        assert_eq!(
+            find_output_conflicts(&config),
+            vec![OutputConflict {
+                target: OutputTarget::Relay(0xabab, 0x01),
+                event_processors: (0, 1),
+                event_codes: Some(expected_event_codes),
+            }]
+        );
+    }
+
+    #[test]
+    fn no_conflict_test() {
+        let config = Config {
+            peripherals: BTreeMap::new(),
+            initial_state: BTreeMap::new(),
+            event_processors: vec![
+                event_processor(
+                    event_code_matcher(BUTTON_PRESSED_EVENT_CODE),
+                    RelayValue::Single(true),
+                ),
+                event_processor(
+                    event_code_matcher(BUTTON_RELEASED_EVENT_CODE),
+                    RelayValue::Single(false),
+                ),
+                event_processor(
+                    event_code_matcher(BUTTON_PRESSED_EVENT_CODE),
+                    RelayValue::Single(true),
+                ),
+            ],
+            time_zone: None,
+            calendars: BTreeMap::new(),
+            gesture_timings: None,
+        };
+
+        assert_eq!(find_output_conflicts(&config), vec![]);
+    }
+
+    #[test]
+    fn unrestricted_conflict_test() {
+        let config = Config {
+            peripherals: BTreeMap::new(),
+            initial_state: BTreeMap::new(),
+            event_processors: vec![
+                event_processor(
+                    Matcher::Single {
+                        extractor: Box::new(NoneExtractor::new()),
+                        filter: Box::new(ValueEqualToConstFilter::new(
+                            Value::Bool(true),
+                            CoercionPolicy::Strict,
+                        )),
+                    },
+                    RelayValue::Single(true),
+                ),
+                event_processor(
+                    Matcher::Single {
+                        extractor: Box::new(NoneExtractor::new()),
+                        filter: Box::new(ValueEqualToConstFilter::new(
+                            Value::Bool(false),
+                            CoercionPolicy::Strict,
+                        )),
+                    },
+                    RelayValue::Single(false),
+                ),
+            ],
+            time_zone: None,
+            calendars: BTreeMap::new(),
+            gesture_timings: None,
+        };
+
+        assert_eq!(
+            find_output_conflicts(&config),
+            vec![OutputConflict {
+                target: OutputTarget::Relay(0xabab, 0x01),
+                event_processors: (0, 1),
+                event_codes: None,
+            }]
+        );
+    }
+}
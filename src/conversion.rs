@@ -0,0 +1,262 @@
+use core::convert::TryFrom;
+
+use ross_protocol::event::bcm::BcmValue;
+use ross_protocol::event::message::MessageValue;
+
+use crate::{ExtractorValue, Value};
+
+#[derive(Debug, PartialEq)]
+pub enum ConversionError {
+    IncompatibleType,
+    // Longer than MAX_BYTES_VALUE_LEN
+    TooLong,
+}
+
+impl<'a> TryFrom<&ExtractorValue<'a>> for Value {
+    type Error = ConversionError;
+
+    fn try_from(value: &ExtractorValue<'a>) -> Result<Self, Self::Error> {
+        match *value {
+            ExtractorValue::U8(value) => Ok(Value::U8(value)),
+            ExtractorValue::U16(value) => Ok(Value::U16(value)),
+            ExtractorValue::U32(value) => Ok(Value::U32(value)),
+            ExtractorValue::Bool(value) => Ok(Value::Bool(value)),
+            ExtractorValue::I8(value) => Ok(Value::I8(value)),
+            ExtractorValue::I16(value) => Ok(Value::I16(value)),
+            ExtractorValue::I32(value) => Ok(Value::I32(value)),
+            ExtractorValue::Fixed(value) => Ok(Value::Fixed(value)),
+            ExtractorValue::Bytes(value) => Value::try_from_bytes(value),
+            ExtractorValue::Rgb(r, g, b) => Ok(Value::Rgb(r, g, b)),
+            ExtractorValue::RgbB(r, g, b, brightness) => Ok(Value::RgbB(r, g, b, brightness)),
+            ExtractorValue::Rgbw(r, g, b, w) => Ok(Value::Rgbw(r, g, b, w)),
+            ExtractorValue::RgbwB(r, g, b, w, brightness) => {
+                Ok(Value::RgbwB(r, g, b, w, brightness))
+            }
+            ExtractorValue::None | ExtractorValue::Packet(_) => {
+                Err(ConversionError::IncompatibleType)
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a Value> for ExtractorValue<'a> {
+    fn from(value: &'a Value) -> Self {
+        match *value {
+            Value::U8(value) => ExtractorValue::U8(value),
+            Value::U16(value) => ExtractorValue::U16(value),
+            Value::U32(value) => ExtractorValue::U32(value),
+            Value::Bool(value) => ExtractorValue::Bool(value),
+            Value::Rgb(r, g, b) => ExtractorValue::Rgb(r, g, b),
+            Value::RgbB(r, g, b, brightness) => ExtractorValue::RgbB(r, g, b, brightness),
+            Value::Rgbw(r, g, b, w) => ExtractorValue::Rgbw(r, g, b, w),
+            Value::RgbwB(r, g, b, w, brightness) => ExtractorValue::RgbwB(r, g, b, w, brightness),
+            Value::I8(value) => ExtractorValue::I8(value),
+            Value::I16(value) => ExtractorValue::I16(value),
+            Value::I32(value) => ExtractorValue::I32(value),
+            Value::Fixed(value) => ExtractorValue::Fixed(value),
+            Value::Bytes(ref value) => ExtractorValue::Bytes(value.as_slice()),
+        }
+    }
+}
+
+impl From<MessageValue> for Value {
+    fn from(value: MessageValue) -> Self {
+        match value {
+            MessageValue::U8(value) => Value::U8(value),
+            MessageValue::U16(value) => Value::U16(value),
+            MessageValue::U32(value) => Value::U32(value),
+            MessageValue::Bool(value) => Value::Bool(value),
+        }
+    }
+}
+
+impl TryFrom<&Value> for MessageValue {
+    type Error = ConversionError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match *value {
+            Value::U8(value) => Ok(MessageValue::U8(value)),
+            Value::U16(value) => Ok(MessageValue::U16(value)),
+            Value::U32(value) => Ok(MessageValue::U32(value)),
+            Value::Bool(value) => Ok(MessageValue::Bool(value)),
+            _ => Err(ConversionError::IncompatibleType),
+        }
+    }
+}
+
+impl From<BcmValue> for Value {
+    fn from(value: BcmValue) -> Self {
+        match value {
+            BcmValue::Binary(value) => Value::Bool(value),
+            BcmValue::Single(value) => Value::U8(value),
+            BcmValue::Rgb(r, g, b) => Value::Rgb(r, g, b),
+            BcmValue::RgbB(r, g, b, brightness) => Value::RgbB(r, g, b, brightness),
+            BcmValue::Rgbw(r, g, b, w) => Value::Rgbw(r, g, b, w),
+            BcmValue::RgbwB(r, g, b, w, brightness) => Value::RgbwB(r, g, b, w, brightness),
+        }
+    }
+}
+
+impl TryFrom<&Value> for BcmValue {
+    type Error = ConversionError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match *value {
+            Value::Bool(value) => Ok(BcmValue::Binary(value)),
+            Value::U8(value) => Ok(BcmValue::Single(value)),
+            Value::Rgb(r, g, b) => Ok(BcmValue::Rgb(r, g, b)),
+            Value::RgbB(r, g, b, brightness) => Ok(BcmValue::RgbB(r, g, b, brightness)),
+            Value::Rgbw(r, g, b, w) => Ok(BcmValue::Rgbw(r, g, b, w)),
+            Value::RgbwB(r, g, b, w, brightness) => Ok(BcmValue::RgbwB(r, g, b, w, brightness)),
+            _ => Err(ConversionError::IncompatibleType),
+        }
+    }
+}
+
+impl<'a> From<MessageValue> for ExtractorValue<'a> {
+    fn from(value: MessageValue) -> Self {
+        match value {
+            MessageValue::U8(value) => ExtractorValue::U8(value),
+            MessageValue::U16(value) => ExtractorValue::U16(value),
+            MessageValue::U32(value) => ExtractorValue::U32(value),
+            MessageValue::Bool(value) => ExtractorValue::Bool(value),
+        }
+    }
+}
+
+impl<'a> TryFrom<&ExtractorValue<'a>> for MessageValue {
+    type Error = ConversionError;
+
+    fn try_from(value: &ExtractorValue<'a>) -> Result<Self, Self::Error> {
+        MessageValue::try_from(&Value::try_from(value)?)
+    }
+}
+
+impl<'a> From<BcmValue> for ExtractorValue<'a> {
+    fn from(value: BcmValue) -> Self {
+        match value {
+            BcmValue::Binary(value) => ExtractorValue::Bool(value),
+            BcmValue::Single(value) => ExtractorValue::U8(value),
+            BcmValue::Rgb(r, g, b) => ExtractorValue::Rgb(r, g, b),
+            BcmValue::RgbB(r, g, b, brightness) => ExtractorValue::RgbB(r, g, b, brightness),
+            BcmValue::Rgbw(r, g, b, w) => ExtractorValue::Rgbw(r, g, b, w),
+            BcmValue::RgbwB(r, g, b, w, brightness) => {
+                ExtractorValue::RgbwB(r, g, b, w, brightness)
+            }
+        }
+    }
+}
+
+impl<'a> TryFrom<&ExtractorValue<'a>> for BcmValue {
+    type Error = ConversionError;
+
+    fn try_from(value: &ExtractorValue<'a>) -> Result<Self, Self::Error> {
+        BcmValue::try_from(&Value::try_from(value)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use super::*;
+
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use ross_protocol::packet::Packet;
+
+    use crate::MAX_BYTES_VALUE_LEN;
+
+    #[test]
+    fn value_round_trip_test() {
+        let values = vec![
+            Value::U8(0x01),
+            Value::U16(0x0123),
+            Value::U32(0x0123_4567),
+            Value::Bool(true),
+            Value::Rgb(0x01, 0x23, 0x45),
+            Value::RgbB(0x01, 0x23, 0x45, 0x67),
+            Value::Rgbw(0x01, 0x23, 0x45, 0x67),
+            Value::RgbwB(0x01, 0x23, 0x45, 0x67, 0x89),
+            Value::I8(-1),
+            Value::I16(-1),
+            Value::I32(-1),
+            Value::Fixed(0x0015_8000),
+            Value::try_from_bytes(&[0x61, 0x62]).unwrap(),
+        ];
+
+        for value in values.iter() {
+            let extractor_value = ExtractorValue::from(value);
+
+            assert_eq!(Value::try_from(&extractor_value).as_ref(), Ok(value));
+        }
+    }
+
+    #[test]
+    fn extractor_value_incompatible_test() {
+        let packet = Packet {
+            is_error: false,
+            device_address: 0x0000,
+            data: Vec::new(),
+        };
+
+        assert_eq!(
+            Value::try_from(&ExtractorValue::None),
+            Err(ConversionError::IncompatibleType)
+        );
+        assert_eq!(
+            Value::try_from(&ExtractorValue::Packet(&packet)),
+            Err(ConversionError::IncompatibleType)
+        );
+    }
+
+    #[test]
+    fn extractor_value_too_long_test() {
+        let bytes = [0x61; MAX_BYTES_VALUE_LEN + 1];
+
+        assert_eq!(
+            Value::try_from(&ExtractorValue::Bytes(&bytes)),
+            Err(ConversionError::TooLong)
+        );
+        assert_eq!(
+            Value::try_from(&ExtractorValue::Bytes(&bytes[..MAX_BYTES_VALUE_LEN])),
+            Value::try_from_bytes(&bytes[..MAX_BYTES_VALUE_LEN])
+        );
+    }
+
+    #[test]
+    fn message_value_test() {
+        assert_eq!(Value::from(MessageValue::U16(0x0123)), Value::U16(0x0123));
+        assert_eq!(
+            MessageValue::try_from(&Value::Bool(true)),
+            Ok(MessageValue::Bool(true))
+        );
+        assert_eq!(
+            MessageValue::try_from(&Value::I8(-1)),
+            Err(ConversionError::IncompatibleType)
+        );
+        assert_eq!(
+            MessageValue::try_from(&ExtractorValue::U32(0x0123_4567)),
+            Ok(MessageValue::U32(0x0123_4567))
+        );
+    }
+
+    #[test]
+    fn bcm_value_test() {
+        assert_eq!(Value::from(BcmValue::Single(0x01)), Value::U8(0x01));
+        assert_eq!(Value::from(BcmValue::Binary(true)), Value::Bool(true));
+        assert_eq!(
+            BcmValue::try_from(&Value::RgbB(0x01, 0x23, 0x45, 0x67)),
+            Ok(BcmValue::RgbB(0x01, 0x23, 0x45, 0x67))
+        );
+        assert_eq!(
+            BcmValue::try_from(&Value::U16(0x0123)),
+            Err(ConversionError::IncompatibleType)
+        );
+        assert_eq!(
+            BcmValue::try_from(&ExtractorValue::Rgb(0x01, 0x23, 0x45)),
+            Ok(BcmValue::Rgb(0x01, 0x23, 0x45))
+        );
+    }
+}
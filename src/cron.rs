@@ -2,45 +2,682 @@ extern crate alloc;
 
 use alloc::boxed::Box;
 use alloc::collections::BTreeSet;
+use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
-use chrono::{DateTime, Datelike, Timelike, Utc};
-use core::ops::AddAssign;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Timelike, Utc, Weekday};
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
 
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::time_zone::{LocalDateTime, TimeZone};
 use crate::{serialize_integer_to_vec, try_deserialize_integer_from_vec};
 
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const DAY_WEEK_NAMES: [&str; 7] = ["MON", "TUE", "WED", "THU", "FRI", "SAT", "SUN"];
+
+// Values are limited by the u8 length prefix used when serializing the field
+const MAX_FIELD_VALUE_COUNT: usize = u8::MAX as usize;
+
 #[derive(Debug, PartialEq)]
-pub enum CronField<T: Copy + Ord + AddAssign> {
+pub enum CronField<T: Copy + Ord + Into<u32>> {
     Including(BTreeSet<T>),
     Excluding(BTreeSet<T>),
     EveryFromTo(T, T, T),
     Any,
+    // Matches if any of the items matches
+    List(Vec<CronFieldItem<T>>),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CronFieldItem<T: Copy + Ord + Into<u32>> {
+    EveryFromTo(T, T, T),
+    // Day of month only, the given number of days before the last day of the month
+    LastDay(T),
+    // Day of month only, the weekday closest to the given day within the same month
+    NearestWeekday(T),
+    // Day of month only, the last weekday of the month
+    LastWeekday,
+    // Day of week only, the last occurrence of the given day in the month
+    LastDayWeek(T),
+    // Day of week only, the n-th occurrence of the given day in the month
+    NthDayWeek(T, T),
+}
+
+impl<T: Copy + Ord + Into<u32>> CronFieldItem<T> {
+    fn is_valid(&self, min: T, max: T) -> bool {
+        match self {
+            CronFieldItem::EveryFromTo(every, from, to) => {
+                is_every_from_to_valid(*every, *from, *to, min, max)
+            }
+            _ => false,
+        }
+    }
+
+    // Items that depend on the calendar never match a bare value
+    fn contains_value(&self, value: u32) -> bool {
+        match self {
+            CronFieldItem::EveryFromTo(every, from, to) => {
+                every_from_to_contains(*every, *from, *to, value)
+            }
+            _ => false,
+        }
+    }
+
+    fn matches_day_month(&self, date: &NaiveDate) -> bool {
+        let day = date.day();
+        let last_day = days_in_month(date.year() as u32, date.month());
+
+        match self {
+            CronFieldItem::LastDay(days) => last_day.checked_sub((*days).into()) == Some(day),
+            CronFieldItem::NearestWeekday(target) => {
+                let target = (*target).into();
+
+                match date.with_day(target) {
+                    Some(target_date) => {
+                        let nearest_day = match target_date.weekday() {
+                            Weekday::Sat if target == 1 => 3,
+                            Weekday::Sat => target - 1,
+                            Weekday::Sun if target == last_day => target - 2,
+                            Weekday::Sun => target + 1,
+                            _ => target,
+                        };
+
+                        day == nearest_day
+                    }
+                    None => false,
+                }
+            }
+            CronFieldItem::LastWeekday => {
+                let last_weekday = match date.with_day(last_day).map(|date| date.weekday()) {
+                    Some(Weekday::Sat) => last_day - 1,
+                    Some(Weekday::Sun) => last_day - 2,
+                    _ => last_day,
+                };
+
+                day == last_weekday
+            }
+            _ => self.contains_value(day),
+        }
+    }
+
+    fn matches_day_week(&self, date: &NaiveDate) -> bool {
+        let day = date.day();
+        let day_week = date.weekday().number_from_monday();
+
+        match self {
+            CronFieldItem::LastDayWeek(target) => {
+                day_week == (*target).into()
+                    && day + 7 > days_in_month(date.year() as u32, date.month())
+            }
+            CronFieldItem::NthDayWeek(target, n) => {
+                day_week == (*target).into() && (day - 1) / 7 + 1 == (*n).into()
+            }
+            _ => self.contains_value(day_week),
+        }
+    }
+
+    fn next_value(&self, from: u32) -> Option<u32> {
+        match self {
+            CronFieldItem::EveryFromTo(every, first, last) => {
+                every_from_to_next_value(*every, *first, *last, from)
+            }
+            _ => None,
+        }
+    }
+
+    fn prev_value(&self, to: u32) -> Option<u32> {
+        match self {
+            CronFieldItem::EveryFromTo(every, first, last) => {
+                every_from_to_prev_value(*every, *first, *last, to)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl CronFieldItem<u8> {
+    fn is_valid_day_month(&self) -> bool {
+        match self {
+            CronFieldItem::LastDay(days) => *days <= 30,
+            CronFieldItem::NearestWeekday(day) => (1..=31).contains(day),
+            CronFieldItem::LastWeekday => true,
+            _ => self.is_valid(1, 31),
+        }
+    }
+
+    fn is_valid_day_week(&self) -> bool {
+        match self {
+            CronFieldItem::LastDayWeek(day) => (1..=7).contains(day),
+            CronFieldItem::NthDayWeek(day, n) => (1..=7).contains(day) && (1..=5).contains(n),
+            _ => self.is_valid(1, 7),
+        }
+    }
+}
+
+fn is_every_from_to_valid<T: Copy + Ord + Into<u32>>(
+    every: T,
+    from: T,
+    to: T,
+    min: T,
+    max: T,
+) -> bool {
+    every.into() != 0 && from >= min && from <= to && to <= max
+}
+
+fn every_from_to_contains<T: Copy + Ord + Into<u32>>(every: T, from: T, to: T, value: u32) -> bool {
+    let (every, from, to) = (every.into(), from.into(), to.into());
+
+    // A zero step only contains the first value
+    value >= from && value <= to && (value - from).checked_rem(every).unwrap_or(value - from) == 0
+}
+
+fn every_from_to_next_value<T: Copy + Ord + Into<u32>>(
+    every: T,
+    first: T,
+    last: T,
+    from: u32,
+) -> Option<u32> {
+    let (every, first, last) = (every.into(), first.into(), last.into());
+
+    let value = if from <= first {
+        Some(first)
+    } else {
+        match (from - first).checked_rem(every) {
+            Some(0) => Some(from),
+            Some(remainder) => from.checked_add(every - remainder),
+            None => None,
+        }
+    };
+
+    value.filter(|value| *value <= last)
+}
+
+fn every_from_to_prev_value<T: Copy + Ord + Into<u32>>(
+    every: T,
+    first: T,
+    last: T,
+    to: u32,
+) -> Option<u32> {
+    let (every, first, last) = (every.into(), first.into(), last.into());
+
+    if to < first || last < first {
+        None
+    } else {
+        let offset = to.min(last) - first;
+
+        match offset.checked_div(every) {
+            Some(steps) => Some(first + steps * every),
+            None => Some(first),
+        }
+    }
 }
 
-impl<T: Copy + Ord + AddAssign> CronField<T> {
+impl<T: Copy + Ord + Into<u32>> CronField<T> {
     fn do_match(&self, value: T) -> bool {
+        self.contains_value(value.into())
+    }
+
+    fn is_valid(&self, min: T, max: T) -> bool {
         match self {
-            CronField::Including(values) => values.contains(&value),
-            CronField::Excluding(values) => !values.contains(&value),
+            CronField::Including(values) | CronField::Excluding(values) => {
+                values.iter().all(|value| *value >= min && *value <= max)
+            }
             CronField::EveryFromTo(every, from, to) => {
-                let mut current_value = *from;
+                is_every_from_to_valid(*every, *from, *to, min, max)
+            }
+            CronField::Any => true,
+            CronField::List(items) => Self::is_list_valid(items, |item| item.is_valid(min, max)),
+        }
+    }
 
-                loop {
-                    if current_value == value {
-                        break true;
-                    }
+    fn is_list_valid<F: Fn(&CronFieldItem<T>) -> bool>(
+        items: &[CronFieldItem<T>],
+        is_item_valid: F,
+    ) -> bool {
+        !items.is_empty() && items.len() <= MAX_FIELD_VALUE_COUNT && items.iter().all(is_item_valid)
+    }
+
+    fn contains_value(&self, value: u32) -> bool {
+        let contains = |values: &BTreeSet<T>| values.iter().any(|v| (*v).into() == value);
+
+        match self {
+            CronField::Including(values) => contains(values),
+            CronField::Excluding(values) => !contains(values),
+            CronField::EveryFromTo(every, from, to) => {
+                every_from_to_contains(*every, *from, *to, value)
+            }
+            CronField::Any => true,
+            CronField::List(items) => items.iter().any(|item| item.contains_value(value)),
+        }
+    }
+
+    fn matches_day_month(&self, date: &NaiveDate) -> bool {
+        match self {
+            CronField::List(items) => items.iter().any(|item| item.matches_day_month(date)),
+            _ => self.contains_value(date.day()),
+        }
+    }
+
+    fn matches_day_week(&self, date: &NaiveDate) -> bool {
+        match self {
+            CronField::List(items) => items.iter().any(|item| item.matches_day_week(date)),
+            _ => self.contains_value(date.weekday().number_from_monday()),
+        }
+    }
+
+    // Returns the smallest matching value in `from..=max`
+    fn next_value(&self, from: u32, max: u32) -> Option<u32> {
+        let value = match self {
+            CronField::Including(values) => values
+                .iter()
+                .map(|value| (*value).into())
+                .find(|value| *value >= from),
+            CronField::Excluding(_) => (from..=max).find(|value| self.contains_value(*value)),
+            CronField::EveryFromTo(every, first, last) => {
+                every_from_to_next_value(*every, *first, *last, from)
+            }
+            CronField::Any => Some(from),
+            CronField::List(items) => items.iter().filter_map(|item| item.next_value(from)).min(),
+        };
+
+        value.filter(|value| *value <= max)
+    }
+
+    // Returns the largest matching value in `min..=to`
+    fn prev_value(&self, to: u32, min: u32) -> Option<u32> {
+        let value = match self {
+            CronField::Including(values) => values
+                .iter()
+                .rev()
+                .map(|value| (*value).into())
+                .find(|value| *value <= to),
+            CronField::Excluding(_) => (min..=to).rev().find(|value| self.contains_value(*value)),
+            CronField::EveryFromTo(every, first, last) => {
+                every_from_to_prev_value(*every, *first, *last, to)
+            }
+            CronField::Any => Some(to),
+            CronField::List(items) => items.iter().filter_map(|item| item.prev_value(to)).max(),
+        };
+
+        value.filter(|value| *value >= min)
+    }
+}
+
+impl CronField<u8> {
+    fn is_valid_day_month(&self) -> bool {
+        match self {
+            CronField::List(items) => Self::is_list_valid(items, |item| item.is_valid_day_month()),
+            _ => self.is_valid(1, 31),
+        }
+    }
+
+    fn is_valid_day_week(&self) -> bool {
+        match self {
+            CronField::List(items) => Self::is_list_valid(items, |item| item.is_valid_day_week()),
+            _ => self.is_valid(1, 7),
+        }
+    }
+
+    // Standard cron accepts both 0 and 7 for Sunday, only 7 is kept
+    fn parse_day_week(field: &str) -> Result<Self, CronParseError> {
+        // `SUN` starting a range such as `SUN-SAT` is read as 0
+        let mut sunday_field = String::new();
+
+        for (index, item) in field.split(',').enumerate() {
+            if index > 0 {
+                sunday_field.push(',');
+            }
 
-                    current_value += *every;
+            let item = match item.strip_prefix('!') {
+                Some(item) => {
+                    sunday_field.push('!');
+                    item
+                }
+                None => item,
+            };
+
+            match item.get(..4) {
+                Some(start) if start.eq_ignore_ascii_case("SUN-") => {
+                    sunday_field.push('0');
+                    sunday_field.push_str(&item[3..]);
+                }
+                _ => sunday_field.push_str(item),
+            }
+        }
+
+        let map_sunday = |values: BTreeSet<u8>| -> BTreeSet<u8> {
+            values
+                .into_iter()
+                .map(|value| if value == 0 { 7 } else { value })
+                .collect()
+        };
 
-                    if current_value > *to {
-                        break false;
+        let field = match Self::parse(&sunday_field, 1, 7, &DAY_WEEK_NAMES)? {
+            CronField::Including(values) => CronField::Including(map_sunday(values)),
+            CronField::Excluding(values) => CronField::Excluding(map_sunday(values)),
+            CronField::EveryFromTo(every, 0, to) => CronField::Including(map_sunday(
+                (0..=to).step_by(every.max(1) as usize).collect(),
+            )),
+            CronField::List(items) => {
+                let mut sunday_items = vec![];
+
+                for item in items.into_iter() {
+                    match item {
+                        CronFieldItem::EveryFromTo(every, 0, to) => {
+                            sunday_items.push(CronFieldItem::EveryFromTo(1, 7, 7));
+
+                            if every != 0 && every <= to {
+                                sunday_items.push(CronFieldItem::EveryFromTo(every, every, to));
+                            }
+                        }
+                        CronFieldItem::LastDayWeek(0) => {
+                            sunday_items.push(CronFieldItem::LastDayWeek(7))
+                        }
+                        CronFieldItem::NthDayWeek(0, n) => {
+                            sunday_items.push(CronFieldItem::NthDayWeek(7, n))
+                        }
+                        item => sunday_items.push(item),
                     }
                 }
+
+                CronField::List(sunday_items)
             }
-            CronField::Any => true,
+            field => field,
+        };
+
+        Ok(field)
+    }
+}
+
+impl<T: Copy + Ord + Into<u32> + TryFrom<u32>> CronField<T> {
+    fn parse(field: &str, min: u32, max: u32, names: &[&str]) -> Result<Self, CronParseError> {
+        if field == "*" || field == "?" {
+            return Ok(CronField::Any);
+        }
+
+        if let Some(values) = field.strip_prefix('!') {
+            return Ok(CronField::Excluding(Self::parse_values(
+                values, min, max, names,
+            )?));
         }
+
+        if !field.contains(',') {
+            if let Some(item) = Self::parse_calendar_item(field, names)? {
+                return Ok(CronField::List(vec![item]));
+            }
+
+            let ((from, to), step) = Self::parse_range(field, min, max, names)?;
+
+            if from == to && step.is_none() {
+                let mut values = BTreeSet::new();
+                values.insert(Self::convert_value(from)?);
+
+                return Ok(CronField::Including(values));
+            }
+
+            return Ok(CronField::EveryFromTo(
+                Self::convert_value(step.unwrap_or(1))?,
+                Self::convert_value(from)?,
+                Self::convert_value(to)?,
+            ));
+        }
+
+        let mut items = vec![];
+
+        for item in field.split(',') {
+            match Self::parse_calendar_item(item, names)? {
+                Some(item) => items.push(item),
+                None => {
+                    let ((from, to), step) = Self::parse_range(item, min, max, names)?;
+
+                    items.push(CronFieldItem::EveryFromTo(
+                        Self::convert_value(step.unwrap_or(1))?,
+                        Self::convert_value(from)?,
+                        Self::convert_value(to)?,
+                    ));
+                }
+            }
+
+            if items.len() > MAX_FIELD_VALUE_COUNT {
+                return Err(CronParseError::TooManyValues);
+            }
+        }
+
+        // Plain lists are expanded as long as the values fit, anything else stays a list of items
+        let is_plain = items
+            .iter()
+            .all(|item| matches!(item, CronFieldItem::EveryFromTo(_, _, _)));
+
+        if is_plain {
+            match Self::parse_values(field, min, max, names) {
+                Ok(values) => return Ok(CronField::Including(values)),
+                Err(CronParseError::TooManyValues) => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(CronField::List(items))
+    }
+
+    // Parses the `L`, `L-n`, `LW`, `nW`, `nL` and `n#k` items, `None` for anything else
+    fn parse_calendar_item(
+        item: &str,
+        names: &[&str],
+    ) -> Result<Option<CronFieldItem<T>>, CronParseError> {
+        if item.eq_ignore_ascii_case("LW") {
+            return Ok(Some(CronFieldItem::LastWeekday));
+        }
+
+        if item.eq_ignore_ascii_case("L") {
+            return Ok(Some(CronFieldItem::LastDay(Self::convert_value(0)?)));
+        }
+
+        if let Some(days) = item.strip_prefix("L-").or_else(|| item.strip_prefix("l-")) {
+            let days = days.parse().map_err(|_| CronParseError::InvalidValue)?;
+
+            return Ok(Some(CronFieldItem::LastDay(Self::convert_value(days)?)));
+        }
+
+        if let Some((day, n)) = item.split_once('#') {
+            let day = Self::parse_value(day, names)?;
+            let n = n.parse().map_err(|_| CronParseError::InvalidValue)?;
+
+            return Ok(Some(CronFieldItem::NthDayWeek(
+                Self::convert_value(day)?,
+                Self::convert_value(n)?,
+            )));
+        }
+
+        // Names such as `JUL` end with the same letters, so only numbers and names are accepted
+        // before the suffix
+        if let Some(day) = item.strip_suffix('W').or_else(|| item.strip_suffix('w')) {
+            if let Ok(day) = Self::parse_value(day, names) {
+                return Ok(Some(CronFieldItem::NearestWeekday(Self::convert_value(
+                    day,
+                )?)));
+            }
+        }
+
+        if let Some(day) = item.strip_suffix('L').or_else(|| item.strip_suffix('l')) {
+            if let Ok(day) = Self::parse_value(day, names) {
+                return Ok(Some(CronFieldItem::LastDayWeek(Self::convert_value(day)?)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn parse_values(
+        field: &str,
+        min: u32,
+        max: u32,
+        names: &[&str],
+    ) -> Result<BTreeSet<T>, CronParseError> {
+        let mut values = BTreeSet::new();
+
+        for item in field.split(',') {
+            let ((from, to), step) = Self::parse_range(item, min, max, names)?;
+
+            let mut value = from;
+
+            while value <= to {
+                values.insert(Self::convert_value(value)?);
+
+                if values.len() > MAX_FIELD_VALUE_COUNT {
+                    return Err(CronParseError::TooManyValues);
+                }
+
+                value = match value.checked_add(step.unwrap_or(1)) {
+                    Some(value) => value,
+                    None => break,
+                };
+            }
+        }
+
+        Ok(values)
+    }
+
+    fn parse_range(
+        item: &str,
+        min: u32,
+        max: u32,
+        names: &[&str],
+    ) -> Result<((u32, u32), Option<u32>), CronParseError> {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => match step.parse::<u32>() {
+                Ok(step) if step != 0 => (range, Some(step)),
+                _ => return Err(CronParseError::InvalidStep),
+            },
+            None => (item, None),
+        };
+
+        let (from, to) = if range == "*" {
+            (min, max)
+        } else if let Some((from, to)) = range.split_once('-') {
+            (
+                Self::parse_value(from, names)?,
+                Self::parse_value(to, names)?,
+            )
+        } else {
+            let value = Self::parse_value(range, names)?;
+
+            // A single value with a step repeats until the end of the field
+            if step.is_some() {
+                (value, max)
+            } else {
+                (value, value)
+            }
+        };
+
+        if from > to {
+            return Err(CronParseError::InvalidRange);
+        }
+
+        Ok(((from, to), step))
+    }
+
+    fn parse_value(value: &str, names: &[&str]) -> Result<u32, CronParseError> {
+        if let Some(index) = names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(value))
+        {
+            return Ok(index as u32 + 1);
+        }
+
+        value.parse().map_err(|_| CronParseError::InvalidValue)
+    }
+
+    fn convert_value(value: u32) -> Result<T, CronParseError> {
+        T::try_from(value).map_err(|_| CronParseError::InvalidValue)
+    }
+}
+
+impl FromStr for CronField<u8> {
+    type Err = CronParseError;
+
+    fn from_str(field: &str) -> Result<Self, Self::Err> {
+        Self::parse(field, 0, u8::MAX as u32, &[])
+    }
+}
+
+impl FromStr for CronField<u16> {
+    type Err = CronParseError;
+
+    fn from_str(field: &str) -> Result<Self, Self::Err> {
+        Self::parse(field, 0, u16::MAX as u32, &[])
+    }
+}
+
+impl<T: Copy + Ord + Into<u32> + fmt::Display> fmt::Display for CronField<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CronField::Including(values) => write_values(f, values),
+            CronField::Excluding(values) => {
+                write!(f, "!")?;
+                write_values(f, values)
+            }
+            CronField::EveryFromTo(every, from, to) => {
+                if (*every).into() == 1 {
+                    write!(f, "{}-{}", from, to)
+                } else {
+                    write!(f, "{}-{}/{}", from, to, every)
+                }
+            }
+            CronField::Any => write!(f, "*"),
+            CronField::List(items) => {
+                let mut separator = "";
+
+                for item in items.iter() {
+                    write!(f, "{}{}", separator, item)?;
+                    separator = ",";
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<T: Copy + Ord + Into<u32> + fmt::Display> fmt::Display for CronFieldItem<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CronFieldItem::EveryFromTo(every, from, to) => {
+                if (*every).into() != 1 {
+                    write!(f, "{}-{}/{}", from, to, every)
+                } else if from == to {
+                    write!(f, "{}", from)
+                } else {
+                    write!(f, "{}-{}", from, to)
+                }
+            }
+            CronFieldItem::LastDay(days) => {
+                if (*days).into() == 0 {
+                    write!(f, "L")
+                } else {
+                    write!(f, "L-{}", days)
+                }
+            }
+            CronFieldItem::NearestWeekday(day) => write!(f, "{}W", day),
+            CronFieldItem::LastWeekday => write!(f, "LW"),
+            CronFieldItem::LastDayWeek(day) => write!(f, "{}L", day),
+            CronFieldItem::NthDayWeek(day, n) => write!(f, "{}#{}", day, n),
+        }
+    }
+}
+
+fn write_values<T: fmt::Display>(f: &mut fmt::Formatter, values: &BTreeSet<T>) -> fmt::Result {
+    let mut separator = "";
+
+    for value in values.iter() {
+        write!(f, "{}{}", separator, value)?;
+        separator = ",";
     }
+
+    Ok(())
 }
 
 impl Serialize for CronField<u8> {
@@ -70,6 +707,7 @@ impl Serialize for CronField<u8> {
             }
             CronField::EveryFromTo(every, from, to) => vec![0x02, *every, *from, *to],
             CronField::Any => vec![0x03],
+            CronField::List(items) => serialize_items(items),
         }
     }
 }
@@ -123,32 +761,178 @@ impl TryDeserialize for CronField<u8> {
                 Ok(Box::new(CronField::EveryFromTo(data[1], data[2], data[3])))
             }
             0x03 => Ok(Box::new(CronField::Any)),
+            0x04 => Ok(Box::new(CronField::List(try_deserialize_items(data)?))),
             _ => Err(ConfigSerializerError::UnknownEnumVariant),
         }
     }
 }
 
-impl Serialize for CronField<u16> {
+impl Serialize for CronFieldItem<u8> {
     fn serialize(&self) -> Vec<u8> {
         match self {
-            CronField::Including(values) => {
-                let mut data = vec![0x00];
+            CronFieldItem::EveryFromTo(every, from, to) => vec![0x00, *every, *from, *to],
+            CronFieldItem::LastDay(days) => vec![0x01, *days],
+            CronFieldItem::NearestWeekday(day) => vec![0x02, *day],
+            CronFieldItem::LastWeekday => vec![0x03],
+            CronFieldItem::LastDayWeek(day) => vec![0x04, *day],
+            CronFieldItem::NthDayWeek(day, n) => vec![0x05, *day, *n],
+        }
+    }
+}
 
-                serialize_integer_to_vec!(data, values.len(), u8);
+impl TryDeserialize for CronFieldItem<u8> {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.is_empty() {
+            return Err(ConfigSerializerError::WrongSize);
+        }
 
-                for value in values.iter() {
-                    serialize_integer_to_vec!(data, *value, u16);
-                }
+        let expected_len = match data[0] {
+            0x00 => 4,
+            0x01 | 0x02 | 0x04 => 2,
+            0x03 => 1,
+            0x05 => 3,
+            _ => return Err(ConfigSerializerError::UnknownEnumVariant),
+        };
 
-                data
-            }
-            CronField::Excluding(values) => {
-                let mut data = vec![0x01];
+        if data.len() < expected_len {
+            return Err(ConfigSerializerError::WrongSize);
+        }
 
-                serialize_integer_to_vec!(data, values.len(), u8);
+        let item = match data[0] {
+            0x00 => CronFieldItem::EveryFromTo(data[1], data[2], data[3]),
+            0x01 => CronFieldItem::LastDay(data[1]),
+            0x02 => CronFieldItem::NearestWeekday(data[1]),
+            0x03 => CronFieldItem::LastWeekday,
+            0x04 => CronFieldItem::LastDayWeek(data[1]),
+            _ => CronFieldItem::NthDayWeek(data[1], data[2]),
+        };
 
-                for value in values.iter() {
-                    serialize_integer_to_vec!(data, *value, u16);
+        Ok(Box::new(item))
+    }
+}
+
+impl Serialize for CronFieldItem<u16> {
+    fn serialize(&self) -> Vec<u8> {
+        let (code, values) = match self {
+            CronFieldItem::EveryFromTo(every, from, to) => (0x00, vec![*every, *from, *to]),
+            CronFieldItem::LastDay(days) => (0x01, vec![*days]),
+            CronFieldItem::NearestWeekday(day) => (0x02, vec![*day]),
+            CronFieldItem::LastWeekday => (0x03, vec![]),
+            CronFieldItem::LastDayWeek(day) => (0x04, vec![*day]),
+            CronFieldItem::NthDayWeek(day, n) => (0x05, vec![*day, *n]),
+        };
+
+        let mut data = vec![code];
+
+        for value in values {
+            serialize_integer_to_vec!(data, value, u16);
+        }
+
+        data
+    }
+}
+
+impl TryDeserialize for CronFieldItem<u16> {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.is_empty() {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        let value_count = match data[0] {
+            0x00 => 3,
+            0x01 | 0x02 | 0x04 => 1,
+            0x03 => 0,
+            0x05 => 2,
+            _ => return Err(ConfigSerializerError::UnknownEnumVariant),
+        };
+
+        let mut values = vec![];
+        let mut offset = 1;
+
+        for _ in 0..value_count {
+            values.push(try_deserialize_integer_from_vec!(data, offset, u16));
+        }
+
+        let item = match data[0] {
+            0x00 => CronFieldItem::EveryFromTo(values[0], values[1], values[2]),
+            0x01 => CronFieldItem::LastDay(values[0]),
+            0x02 => CronFieldItem::NearestWeekday(values[0]),
+            0x03 => CronFieldItem::LastWeekday,
+            0x04 => CronFieldItem::LastDayWeek(values[0]),
+            _ => CronFieldItem::NthDayWeek(values[0], values[1]),
+        };
+
+        Ok(Box::new(item))
+    }
+}
+
+// Every item is prefixed with its length so fields can be read without knowing the item layout
+fn serialize_items<T: Copy + Ord + Into<u32>>(items: &[CronFieldItem<T>]) -> Vec<u8>
+where
+    CronFieldItem<T>: Serialize,
+{
+    let mut data = vec![0x04];
+
+    serialize_integer_to_vec!(data, items.len(), u8);
+
+    for item in items.iter() {
+        let mut item_data = item.serialize();
+        serialize_integer_to_vec!(data, item_data.len(), u8);
+        data.append(&mut item_data);
+    }
+
+    data
+}
+
+fn try_deserialize_items<T: Copy + Ord + Into<u32>>(
+    data: &[u8],
+) -> Result<Vec<CronFieldItem<T>>, ConfigSerializerError>
+where
+    CronFieldItem<T>: TryDeserialize,
+{
+    let mut offset = 1;
+
+    let item_count = try_deserialize_integer_from_vec!(data, offset, u8);
+
+    let mut items = vec![];
+
+    for _ in 0..item_count {
+        let item_len = try_deserialize_integer_from_vec!(data, offset, u8) as usize;
+
+        if data.len() < offset + item_len {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        items.push(*CronFieldItem::try_deserialize(
+            &data[offset..offset + item_len],
+        )?);
+        offset += item_len;
+    }
+
+    Ok(items)
+}
+
+impl Serialize for CronField<u16> {
+    fn serialize(&self) -> Vec<u8> {
+        match self {
+            CronField::Including(values) => {
+                let mut data = vec![0x00];
+
+                serialize_integer_to_vec!(data, values.len(), u8);
+
+                for value in values.iter() {
+                    serialize_integer_to_vec!(data, *value, u16);
+                }
+
+                data
+            }
+            CronField::Excluding(values) => {
+                let mut data = vec![0x01];
+
+                serialize_integer_to_vec!(data, values.len(), u8);
+
+                for value in values.iter() {
+                    serialize_integer_to_vec!(data, *value, u16);
                 }
 
                 data
@@ -163,6 +947,7 @@ impl Serialize for CronField<u16> {
                 data
             }
             CronField::Any => vec![0x03],
+            CronField::List(items) => serialize_items(items),
         }
     }
 }
@@ -223,11 +1008,33 @@ impl TryDeserialize for CronField<u16> {
                 Ok(Box::new(CronField::EveryFromTo(every, from, to)))
             }
             0x03 => Ok(Box::new(CronField::Any)),
+            0x04 => Ok(Box::new(CronField::List(try_deserialize_items(data)?))),
             _ => Err(ConfigSerializerError::UnknownEnumVariant),
         }
     }
 }
 
+#[derive(Debug, PartialEq)]
+pub enum CronExpressionError {
+    InvalidSecond,
+    InvalidMinute,
+    InvalidHour,
+    InvalidDayMonth,
+    InvalidMonth,
+    InvalidDayWeek,
+    InvalidYear,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CronParseError {
+    WrongFieldCount,
+    InvalidValue,
+    InvalidRange,
+    InvalidStep,
+    TooManyValues,
+    InvalidExpression(CronExpressionError),
+}
+
 #[derive(Debug, PartialEq)]
 pub struct CronExpression {
     pub second: CronField<u8>,
@@ -240,7 +1047,39 @@ pub struct CronExpression {
 }
 
 impl CronExpression {
-    pub fn do_match(&self, date_time: &DateTime<Utc>) -> bool {
+    pub fn validate(&self) -> Result<(), CronExpressionError> {
+        if !self.second.is_valid(0, 59) {
+            return Err(CronExpressionError::InvalidSecond);
+        }
+
+        if !self.minute.is_valid(0, 59) {
+            return Err(CronExpressionError::InvalidMinute);
+        }
+
+        if !self.hour.is_valid(0, 23) {
+            return Err(CronExpressionError::InvalidHour);
+        }
+
+        if !self.day_month.is_valid_day_month() {
+            return Err(CronExpressionError::InvalidDayMonth);
+        }
+
+        if !self.month.is_valid(1, 12) {
+            return Err(CronExpressionError::InvalidMonth);
+        }
+
+        if !self.day_week.is_valid_day_week() {
+            return Err(CronExpressionError::InvalidDayWeek);
+        }
+
+        if !self.year.is_valid(0, u16::MAX) {
+            return Err(CronExpressionError::InvalidYear);
+        }
+
+        Ok(())
+    }
+
+    pub fn do_match<D: Datelike + Timelike>(&self, date_time: &D) -> bool {
         if !self.second.do_match(date_time.second() as u8) {
             return false;
         }
@@ -253,19 +1092,13 @@ impl CronExpression {
             return false;
         }
 
-        if !self.day_month.do_match(date_time.day() as u8) {
-            return false;
-        }
-
         if !self.month.do_match(date_time.month() as u8) {
             return false;
         }
 
-        if !self
-            .day_week
-            .do_match(date_time.weekday().number_from_monday() as u8)
-        {
-            return false;
+        match NaiveDate::from_ymd_opt(date_time.year(), date_time.month(), date_time.day()) {
+            Some(date) if self.date_matches(&date) => {}
+            _ => return false,
         }
 
         if !self.year.do_match(date_time.year() as u16) {
@@ -274,6 +1107,322 @@ impl CronExpression {
 
         return true;
     }
+
+    pub fn do_match_in_time_zone(&self, date_time: &DateTime<Utc>, time_zone: &TimeZone) -> bool {
+        match time_zone.to_local(date_time) {
+            LocalDateTime::Regular(local_date_time) => self.do_match(&local_date_time),
+            // Local times skipped when clocks jump forward match one DST offset later
+            LocalDateTime::AfterGap(local_date_time, skipped_date_time) => {
+                self.do_match(&local_date_time) || self.do_match(&skipped_date_time)
+            }
+            // Repeated local times only match again if the expression is not tied to an hour
+            LocalDateTime::Repeated(local_date_time) => {
+                self.hour == CronField::Any && self.do_match(&local_date_time)
+            }
+        }
+    }
+
+    // Returns the first matching instant strictly after `date_time`
+    pub fn next_after(&self, date_time: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut cursor = (date_time.naive_utc() + Duration::seconds(1)).with_nanosecond(0)?;
+
+        if cursor.year() < 0 {
+            cursor = to_naive_date_time(0, 1, 1, 0, 0, 0)?;
+        }
+
+        // Every step either returns or moves the cursor forward to the next candidate
+        loop {
+            let (year, month, day) = (cursor.year() as u32, cursor.month(), cursor.day());
+            let (hour, minute, second) = (cursor.hour(), cursor.minute(), cursor.second());
+
+            match self.year.next_value(year, u16::MAX as u32) {
+                Some(next_year) if next_year == year => {}
+                Some(next_year) => {
+                    cursor = to_naive_date_time(next_year, 1, 1, 0, 0, 0)?;
+                    continue;
+                }
+                None => return None,
+            }
+
+            match self.month.next_value(month, 12) {
+                Some(next_month) if next_month == month => {}
+                Some(next_month) => {
+                    cursor = to_naive_date_time(year, next_month, 1, 0, 0, 0)?;
+                    continue;
+                }
+                None => {
+                    cursor = to_naive_date_time(year + 1, 1, 1, 0, 0, 0)?;
+                    continue;
+                }
+            }
+
+            match (day..=days_in_month(year, month)).find(|day| self.day_matches(year, month, *day))
+            {
+                Some(next_day) if next_day == day => {}
+                Some(next_day) => {
+                    cursor = to_naive_date_time(year, month, next_day, 0, 0, 0)?;
+                    continue;
+                }
+                None => {
+                    cursor = to_naive_date_time(year, month, days_in_month(year, month), 0, 0, 0)?
+                        + Duration::days(1);
+                    continue;
+                }
+            }
+
+            match self.hour.next_value(hour, 23) {
+                Some(next_hour) if next_hour == hour => {}
+                Some(next_hour) => {
+                    cursor = to_naive_date_time(year, month, day, next_hour, 0, 0)?;
+                    continue;
+                }
+                None => {
+                    cursor = to_naive_date_time(year, month, day, 0, 0, 0)? + Duration::days(1);
+                    continue;
+                }
+            }
+
+            match self.minute.next_value(minute, 59) {
+                Some(next_minute) if next_minute == minute => {}
+                Some(next_minute) => {
+                    cursor = to_naive_date_time(year, month, day, hour, next_minute, 0)?;
+                    continue;
+                }
+                None => {
+                    cursor = to_naive_date_time(year, month, day, hour, 0, 0)? + Duration::hours(1);
+                    continue;
+                }
+            }
+
+            match self.second.next_value(second, 59) {
+                Some(next_second) => {
+                    let date_time =
+                        to_naive_date_time(year, month, day, hour, minute, next_second)?;
+
+                    return Some(DateTime::from_utc(date_time, Utc));
+                }
+                None => {
+                    cursor = to_naive_date_time(year, month, day, hour, minute, 0)?
+                        + Duration::minutes(1);
+                }
+            }
+        }
+    }
+
+    // Returns the last matching instant strictly before `date_time`
+    pub fn prev_before(&self, date_time: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut cursor = if date_time.nanosecond() == 0 {
+            date_time.naive_utc() - Duration::seconds(1)
+        } else {
+            date_time.naive_utc().with_nanosecond(0)?
+        };
+
+        // Every step either returns or moves the cursor back to the previous candidate
+        loop {
+            if cursor.year() < 0 {
+                return None;
+            }
+
+            let (year, month, day) = (cursor.year() as u32, cursor.month(), cursor.day());
+            let (hour, minute, second) = (cursor.hour(), cursor.minute(), cursor.second());
+
+            match self.year.prev_value(year, 0) {
+                Some(prev_year) if prev_year == year => {}
+                Some(prev_year) => {
+                    cursor = to_naive_date_time(prev_year, 12, 31, 23, 59, 59)?;
+                    continue;
+                }
+                None => return None,
+            }
+
+            match self.month.prev_value(month, 1) {
+                Some(prev_month) if prev_month == month => {}
+                Some(prev_month) => {
+                    let prev_day = days_in_month(year, prev_month);
+                    cursor = to_naive_date_time(year, prev_month, prev_day, 23, 59, 59)?;
+                    continue;
+                }
+                None => {
+                    cursor = to_naive_date_time(year, 1, 1, 0, 0, 0)? - Duration::seconds(1);
+                    continue;
+                }
+            }
+
+            match (1..=day)
+                .rev()
+                .find(|day| self.day_matches(year, month, *day))
+            {
+                Some(prev_day) if prev_day == day => {}
+                Some(prev_day) => {
+                    cursor = to_naive_date_time(year, month, prev_day, 23, 59, 59)?;
+                    continue;
+                }
+                None => {
+                    cursor = to_naive_date_time(year, month, 1, 0, 0, 0)? - Duration::seconds(1);
+                    continue;
+                }
+            }
+
+            match self.hour.prev_value(hour, 0) {
+                Some(prev_hour) if prev_hour == hour => {}
+                Some(prev_hour) => {
+                    cursor = to_naive_date_time(year, month, day, prev_hour, 59, 59)?;
+                    continue;
+                }
+                None => {
+                    cursor = to_naive_date_time(year, month, day, 0, 0, 0)? - Duration::seconds(1);
+                    continue;
+                }
+            }
+
+            match self.minute.prev_value(minute, 0) {
+                Some(prev_minute) if prev_minute == minute => {}
+                Some(prev_minute) => {
+                    cursor = to_naive_date_time(year, month, day, hour, prev_minute, 59)?;
+                    continue;
+                }
+                None => {
+                    cursor =
+                        to_naive_date_time(year, month, day, hour, 0, 0)? - Duration::seconds(1);
+                    continue;
+                }
+            }
+
+            match self.second.prev_value(second, 0) {
+                Some(prev_second) => {
+                    let date_time =
+                        to_naive_date_time(year, month, day, hour, minute, prev_second)?;
+
+                    return Some(DateTime::from_utc(date_time, Utc));
+                }
+                None => {
+                    cursor = to_naive_date_time(year, month, day, hour, minute, 0)?
+                        - Duration::seconds(1);
+                }
+            }
+        }
+    }
+
+    // Returns the last instant strictly before `date_time` the expression matches at in the time
+    // zone, following the same rules for skipped and repeated local times as
+    // `do_match_in_time_zone`
+    pub fn prev_before_in_time_zone(
+        &self,
+        date_time: &DateTime<Utc>,
+        time_zone: &TimeZone,
+    ) -> Option<DateTime<Utc>> {
+        let (min_offset, max_offset) = match &time_zone.dst {
+            Some(dst) => (
+                time_zone.offset.min(dst.offset),
+                time_zone.offset.max(dst.offset),
+            ),
+            None => (time_zone.offset, time_zone.offset),
+        };
+
+        let min_offset = Duration::seconds(min_offset as i64);
+        let max_offset = Duration::seconds(max_offset as i64);
+
+        // Local times up to the largest offset ahead can still belong to an earlier instant
+        let mut bound = *date_time + max_offset;
+
+        loop {
+            let local_date_time = self.prev_before(&bound)?;
+
+            for candidate in [local_date_time - min_offset, local_date_time - max_offset].iter() {
+                if candidate < date_time && self.do_match_in_time_zone(candidate, time_zone) {
+                    return Some(*candidate);
+                }
+            }
+
+            bound = local_date_time.min(*date_time + min_offset);
+        }
+    }
+
+    fn day_matches(&self, year: u32, month: u32, day: u32) -> bool {
+        match NaiveDate::from_ymd_opt(year as i32, month, day) {
+            Some(date) => self.date_matches(&date),
+            None => false,
+        }
+    }
+
+    fn date_matches(&self, date: &NaiveDate) -> bool {
+        self.day_month.matches_day_month(date) && self.day_week.matches_day_week(date)
+    }
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    (28..=31)
+        .rev()
+        .find(|day| NaiveDate::from_ymd_opt(year as i32, month, *day).is_some())
+        .unwrap_or(0)
+}
+
+fn to_naive_date_time(
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+) -> Option<NaiveDateTime> {
+    NaiveDate::from_ymd_opt(year as i32, month, day)?.and_hms_opt(hour, minute, second)
+}
+
+impl FromStr for CronExpression {
+    type Err = CronParseError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+
+        // Five fields start at minutes, six or seven start at seconds with an optional year
+        let (second, fields) = match fields.len() {
+            5 => {
+                let mut values = BTreeSet::new();
+                values.insert(0);
+
+                (CronField::Including(values), &fields[..])
+            }
+            6 | 7 => (CronField::parse(fields[0], 0, 59, &[])?, &fields[1..]),
+            _ => return Err(CronParseError::WrongFieldCount),
+        };
+
+        let year = match fields.get(5) {
+            Some(year) => CronField::parse(year, 0, u16::MAX as u32, &[])?,
+            None => CronField::Any,
+        };
+
+        let expression = CronExpression {
+            second,
+            minute: CronField::parse(fields[0], 0, 59, &[])?,
+            hour: CronField::parse(fields[1], 0, 23, &[])?,
+            day_month: CronField::parse(fields[2], 1, 31, &[])?,
+            month: CronField::parse(fields[3], 1, 12, &MONTH_NAMES)?,
+            day_week: CronField::parse_day_week(fields[4])?,
+            year,
+        };
+
+        expression
+            .validate()
+            .map_err(CronParseError::InvalidExpression)?;
+
+        Ok(expression)
+    }
+}
+
+impl fmt::Display for CronExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {} {}",
+            self.second,
+            self.minute,
+            self.hour,
+            self.day_month,
+            self.month,
+            self.day_week,
+            self.year
+        )
+    }
 }
 
 impl Serialize for CronExpression {
@@ -363,6 +1512,14 @@ impl TryDeserialize for CronExpression {
 mod tests {
     use super::*;
 
+    use alloc::string::ToString;
+
+    use crate::time_zone::{DstRule, DstTransition};
+
+    fn including(values: &[u8]) -> CronField<u8> {
+        CronField::Including(values.iter().copied().collect())
+    }
+
     #[test]
     fn field_serialize_including_u8_test() {
         let mut values = BTreeSet::new();
@@ -627,4 +1784,623 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn field_every_from_to_match_test() {
+        let field: CronField<u8> = CronField::EveryFromTo(5, 250, 255);
+
+        assert!(field.do_match(250));
+        assert!(field.do_match(255));
+        assert!(!field.do_match(251));
+        assert!(!field.do_match(245));
+    }
+
+    #[test]
+    fn field_every_from_to_zero_every_match_test() {
+        let field: CronField<u8> = CronField::EveryFromTo(0, 15, 30);
+
+        assert!(field.do_match(15));
+        assert!(!field.do_match(16));
+    }
+
+    #[test]
+    fn expression_validate_test() {
+        let expression = CronExpression {
+            second: CronField::EveryFromTo(15, 0, 59),
+            minute: CronField::Any,
+            hour: CronField::Any,
+            day_month: CronField::Any,
+            month: CronField::Any,
+            day_week: CronField::EveryFromTo(1, 1, 5),
+            year: CronField::Any,
+        };
+
+        assert_eq!(expression.validate(), Ok(()));
+    }
+
+    #[test]
+    fn expression_validate_out_of_range_test() {
+        let mut included_values = BTreeSet::new();
+        included_values.insert(0);
+
+        let expression = CronExpression {
+            second: CronField::Any,
+            minute: CronField::Any,
+            hour: CronField::Any,
+            day_month: CronField::Any,
+            month: CronField::Including(included_values),
+            day_week: CronField::Any,
+            year: CronField::Any,
+        };
+
+        assert_eq!(
+            expression.validate(),
+            Err(CronExpressionError::InvalidMonth)
+        );
+    }
+
+    #[test]
+    fn expression_validate_zero_every_test() {
+        let expression = CronExpression {
+            second: CronField::Any,
+            minute: CronField::Any,
+            hour: CronField::EveryFromTo(0, 0, 23),
+            day_month: CronField::Any,
+            month: CronField::Any,
+            day_week: CronField::Any,
+            year: CronField::Any,
+        };
+
+        assert_eq!(expression.validate(), Err(CronExpressionError::InvalidHour));
+    }
+
+    #[test]
+    fn expression_validate_from_after_to_test() {
+        let expression = CronExpression {
+            second: CronField::Any,
+            minute: CronField::Any,
+            hour: CronField::Any,
+            day_month: CronField::Any,
+            month: CronField::Any,
+            day_week: CronField::Any,
+            year: CronField::EveryFromTo(1, 2030, 2020),
+        };
+
+        assert_eq!(expression.validate(), Err(CronExpressionError::InvalidYear));
+    }
+
+    #[test]
+    fn field_from_str_test() {
+        assert_eq!(
+            CronField::<u8>::from_str("1,4-6,10-20/10"),
+            Ok(including(&[1, 4, 5, 6, 10, 20]))
+        );
+        assert_eq!(CronField::<u8>::from_str("*"), Ok(CronField::Any));
+        assert_eq!(
+            CronField::<u8>::from_str("0-30/5"),
+            Ok(CronField::EveryFromTo(5, 0, 30))
+        );
+        assert_eq!(
+            CronField::<u16>::from_str("2000/4"),
+            Ok(CronField::EveryFromTo(4, 2000, 0xffff))
+        );
+    }
+
+    #[test]
+    fn field_from_str_excluding_test() {
+        let mut expected_values = BTreeSet::new();
+        expected_values.insert(3);
+        expected_values.insert(7);
+
+        assert_eq!(
+            CronField::<u8>::from_str("!3,7"),
+            Ok(CronField::Excluding(expected_values))
+        );
+    }
+
+    #[test]
+    fn field_from_str_invalid_test() {
+        assert_eq!(
+            CronField::<u8>::from_str("a"),
+            Err(CronParseError::InvalidValue)
+        );
+        assert_eq!(
+            CronField::<u8>::from_str("256"),
+            Err(CronParseError::InvalidValue)
+        );
+        assert_eq!(
+            CronField::<u8>::from_str("*/0"),
+            Err(CronParseError::InvalidStep)
+        );
+        assert_eq!(
+            CronField::<u8>::from_str("5-3"),
+            Err(CronParseError::InvalidRange)
+        );
+        assert_eq!(
+            CronField::<u16>::from_str("!*"),
+            Err(CronParseError::TooManyValues)
+        );
+    }
+
+    #[test]
+    fn field_display_test() {
+        let values: BTreeSet<u8> = [15, 9].iter().copied().collect();
+
+        assert_eq!(CronField::Including(values.clone()).to_string(), "9,15");
+        assert_eq!(CronField::Excluding(values).to_string(), "!9,15");
+        assert_eq!(CronField::<u8>::EveryFromTo(1, 0, 5).to_string(), "0-5");
+        assert_eq!(CronField::<u8>::EveryFromTo(5, 0, 30).to_string(), "0-30/5");
+        assert_eq!(CronField::<u16>::Any.to_string(), "*");
+    }
+
+    #[test]
+    fn expression_from_str_test() {
+        assert_eq!(
+            CronExpression::from_str("*/5 0-30 6 * * MON-FRI *"),
+            Ok(CronExpression {
+                second: CronField::EveryFromTo(5, 0, 59),
+                minute: CronField::EveryFromTo(1, 0, 30),
+                hour: including(&[6]),
+                day_month: CronField::Any,
+                month: CronField::Any,
+                day_week: CronField::EveryFromTo(1, 1, 5),
+                year: CronField::Any,
+            })
+        );
+    }
+
+    #[test]
+    fn expression_from_str_five_fields_test() {
+        assert_eq!(
+            CronExpression::from_str("0 12 1 jan,JUL,12 ?"),
+            Ok(CronExpression {
+                second: including(&[0]),
+                minute: including(&[0]),
+                hour: including(&[12]),
+                day_month: including(&[1]),
+                month: including(&[1, 7, 12]),
+                day_week: CronField::Any,
+                year: CronField::Any,
+            })
+        );
+    }
+
+    #[test]
+    fn expression_from_str_invalid_test() {
+        assert_eq!(
+            CronExpression::from_str("* * * *"),
+            Err(CronParseError::WrongFieldCount)
+        );
+        assert_eq!(
+            CronExpression::from_str("0 75 * * * *"),
+            Err(CronParseError::InvalidExpression(
+                CronExpressionError::InvalidMinute
+            ))
+        );
+        assert_eq!(
+            CronExpression::from_str("0 0 * * * 8"),
+            Err(CronParseError::InvalidExpression(
+                CronExpressionError::InvalidDayWeek
+            ))
+        );
+    }
+
+    #[test]
+    fn expression_from_str_sunday_test() {
+        assert_eq!(
+            CronExpression::from_str("0 0 * * * 0").unwrap().day_week,
+            including(&[7])
+        );
+        assert_eq!(
+            CronExpression::from_str("0 0 * * * 7").unwrap().day_week,
+            including(&[7])
+        );
+        assert_eq!(
+            CronExpression::from_str("0 0 * * * 0-2").unwrap().day_week,
+            including(&[1, 2, 7])
+        );
+        assert_eq!(
+            CronExpression::from_str("0 0 * * * !0,6").unwrap().day_week,
+            CronField::Excluding([6, 7].iter().copied().collect())
+        );
+        assert_eq!(
+            CronExpression::from_str("0 0 * * * 0#2,0L")
+                .unwrap()
+                .day_week,
+            CronField::List(vec![
+                CronFieldItem::NthDayWeek(7, 2),
+                CronFieldItem::LastDayWeek(7),
+            ])
+        );
+        assert_eq!(
+            CronExpression::from_str("0 0 * * * SUN-SAT")
+                .unwrap()
+                .day_week,
+            including(&[1, 2, 3, 4, 5, 6, 7])
+        );
+        assert_eq!(
+            CronExpression::from_str("0 0 * * * sun-thu,SAT")
+                .unwrap()
+                .day_week,
+            including(&[1, 2, 3, 4, 6, 7])
+        );
+        assert_eq!(
+            CronExpression::from_str("0 0 * * * SUN").unwrap().day_week,
+            including(&[7])
+        );
+    }
+
+    #[test]
+    fn expression_display_test() {
+        let expression = CronExpression::from_str("*/5 0-30 6 * * MON-FRI 2020-2030/2").unwrap();
+
+        assert_eq!(expression.to_string(), "0-59/5 0-30 6 * * 1-5 2020-2030/2");
+        assert_eq!(
+            CronExpression::from_str(&expression.to_string()),
+            Ok(expression)
+        );
+    }
+
+    #[test]
+    fn expression_next_after_test() {
+        let expression = CronExpression::from_str("0 30 6 * * MON-FRI *").unwrap();
+
+        assert_eq!(
+            expression.next_after(&DateTime::from_str("2021-12-31T07:00:00Z").unwrap()),
+            Some(DateTime::from_str("2022-01-03T06:30:00Z").unwrap())
+        );
+        assert_eq!(
+            expression.next_after(&DateTime::from_str("2022-01-03T06:29:59.5Z").unwrap()),
+            Some(DateTime::from_str("2022-01-03T06:30:00Z").unwrap())
+        );
+        assert_eq!(
+            expression.next_after(&DateTime::from_str("2022-01-03T06:30:00Z").unwrap()),
+            Some(DateTime::from_str("2022-01-04T06:30:00Z").unwrap())
+        );
+    }
+
+    #[test]
+    fn expression_next_after_leap_day_test() {
+        let expression = CronExpression::from_str("0 0 0 29 FEB *").unwrap();
+
+        assert_eq!(
+            expression.next_after(&DateTime::from_str("2021-03-01T00:00:00Z").unwrap()),
+            Some(DateTime::from_str("2024-02-29T00:00:00Z").unwrap())
+        );
+    }
+
+    #[test]
+    fn expression_next_after_none_test() {
+        let expression = CronExpression::from_str("0 0 0 1 1 * 2000").unwrap();
+
+        assert_eq!(
+            expression.next_after(&DateTime::from_str("2000-01-01T00:00:00Z").unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn expression_prev_before_test() {
+        let expression = CronExpression::from_str("*/15 * 6-7 * * MON-FRI *").unwrap();
+
+        assert_eq!(
+            expression.prev_before(&DateTime::from_str("2022-01-03T06:00:00Z").unwrap()),
+            Some(DateTime::from_str("2021-12-31T07:59:45Z").unwrap())
+        );
+        assert_eq!(
+            expression.prev_before(&DateTime::from_str("2022-01-03T06:00:00.5Z").unwrap()),
+            Some(DateTime::from_str("2022-01-03T06:00:00Z").unwrap())
+        );
+    }
+
+    #[test]
+    fn expression_prev_before_leap_day_test() {
+        let expression = CronExpression::from_str("0 0 12 29 FEB *").unwrap();
+
+        assert_eq!(
+            expression.prev_before(&DateTime::from_str("2024-02-01T00:00:00Z").unwrap()),
+            Some(DateTime::from_str("2020-02-29T12:00:00Z").unwrap())
+        );
+    }
+
+    #[test]
+    fn field_serialize_list_u8_test() {
+        let field = CronField::<u8>::List(vec![
+            CronFieldItem::EveryFromTo(2, 8, 12),
+            CronFieldItem::LastDay(3),
+            CronFieldItem::NearestWeekday(15),
+            CronFieldItem::LastWeekday,
+            CronFieldItem::LastDayWeek(5),
+            CronFieldItem::NthDayWeek(2, 3),
+        ]);
+
+        let expected_data = vec![
+            0x04, 0x06, // list
+            0x04, 0x00, 0x02, 0x08, 0x0c, // every from to
+            0x02, 0x01, 0x03, // last day
+            0x02, 0x02, 0x0f, // nearest weekday
+            0x01, 0x03, // last weekday
+            0x02, 0x04, 0x05, // last day week
+            0x03, 0x05, 0x02, 0x03, // nth day week
+        ];
+
+        assert_eq!(field.serialize(), expected_data)
+    }
+
+    #[test]
+    fn field_try_deserialize_list_u8_test() {
+        let data = vec![
+            0x04, 0x06, // list
+            0x04, 0x00, 0x02, 0x08, 0x0c, // every from to
+            0x02, 0x01, 0x03, // last day
+            0x02, 0x02, 0x0f, // nearest weekday
+            0x01, 0x03, // last weekday
+            0x02, 0x04, 0x05, // last day week
+            0x03, 0x05, 0x02, 0x03, // nth day week
+        ];
+
+        assert_eq!(
+            CronField::<u8>::try_deserialize(&data),
+            Ok(Box::new(CronField::<u8>::List(vec![
+                CronFieldItem::EveryFromTo(2, 8, 12),
+                CronFieldItem::LastDay(3),
+                CronFieldItem::NearestWeekday(15),
+                CronFieldItem::LastWeekday,
+                CronFieldItem::LastDayWeek(5),
+                CronFieldItem::NthDayWeek(2, 3),
+            ]))),
+        )
+    }
+
+    #[test]
+    fn field_serialize_list_u16_test() {
+        let field = CronField::<u16>::List(vec![
+            CronFieldItem::EveryFromTo(0x0001, 0x07d0, 0x07da),
+            CronFieldItem::EveryFromTo(0x0004, 0x0834, 0x0898),
+        ]);
+
+        let expected_data = vec![
+            0x04, 0x02, // list
+            0x07, 0x00, 0x00, 0x01, 0x07, 0xd0, 0x07, 0xda, // every from to
+            0x07, 0x00, 0x00, 0x04, 0x08, 0x34, 0x08, 0x98, // every from to
+        ];
+
+        assert_eq!(field.serialize(), expected_data)
+    }
+
+    #[test]
+    fn field_try_deserialize_list_u16_test() {
+        let data = vec![
+            0x04, 0x02, // list
+            0x07, 0x00, 0x00, 0x01, 0x07, 0xd0, 0x07, 0xda, // every from to
+            0x07, 0x00, 0x00, 0x04, 0x08, 0x34, 0x08, 0x98, // every from to
+        ];
+
+        assert_eq!(
+            CronField::<u16>::try_deserialize(&data),
+            Ok(Box::new(CronField::<u16>::List(vec![
+                CronFieldItem::EveryFromTo(0x0001, 0x07d0, 0x07da),
+                CronFieldItem::EveryFromTo(0x0004, 0x0834, 0x0898),
+            ]))),
+        )
+    }
+
+    #[test]
+    fn field_try_deserialize_list_wrong_size_test() {
+        let data = vec![0x04, 0x01, 0x04, 0x00, 0x02, 0x08];
+
+        assert_eq!(
+            CronField::<u8>::try_deserialize(&data),
+            Err(ConfigSerializerError::WrongSize),
+        )
+    }
+
+    #[test]
+    fn field_from_str_list_test() {
+        assert_eq!(
+            CronField::<u8>::from_str("L"),
+            Ok(CronField::List(vec![CronFieldItem::LastDay(0)]))
+        );
+        assert_eq!(
+            CronField::<u8>::from_str("1,15W,L-2,LW"),
+            Ok(CronField::List(vec![
+                CronFieldItem::EveryFromTo(1, 1, 1),
+                CronFieldItem::NearestWeekday(15),
+                CronFieldItem::LastDay(2),
+                CronFieldItem::LastWeekday,
+            ]))
+        );
+        assert_eq!(
+            CronField::<u8>::parse("FRI#2,1L", 1, 7, &DAY_WEEK_NAMES),
+            Ok(CronField::List(vec![
+                CronFieldItem::NthDayWeek(5, 2),
+                CronFieldItem::LastDayWeek(1),
+            ]))
+        );
+        assert_eq!(
+            CronField::<u8>::parse("JUL", 1, 12, &MONTH_NAMES),
+            Ok(including(&[7]))
+        );
+        assert_eq!(
+            CronField::<u16>::from_str("2000-2300,2400"),
+            Ok(CronField::List(vec![
+                CronFieldItem::EveryFromTo(1, 2000, 2300),
+                CronFieldItem::EveryFromTo(1, 2400, 2400),
+            ]))
+        );
+    }
+
+    #[test]
+    fn field_list_display_test() {
+        let field: CronField<u8> = CronField::List(vec![
+            CronFieldItem::EveryFromTo(1, 1, 1),
+            CronFieldItem::EveryFromTo(1, 8, 12),
+            CronFieldItem::EveryFromTo(15, 0, 45),
+            CronFieldItem::LastDay(0),
+            CronFieldItem::LastDay(2),
+            CronFieldItem::NearestWeekday(15),
+            CronFieldItem::LastWeekday,
+            CronFieldItem::LastDayWeek(5),
+            CronFieldItem::NthDayWeek(2, 3),
+        ]);
+
+        assert_eq!(field.to_string(), "1,8-12,0-45/15,L,L-2,15W,LW,5L,2#3");
+        assert_eq!(CronField::<u8>::from_str(&field.to_string()), Ok(field));
+    }
+
+    #[test]
+    fn expression_validate_list_test() {
+        assert_eq!(
+            CronExpression::from_str("0 0 0 5L * *"),
+            Err(CronParseError::InvalidExpression(
+                CronExpressionError::InvalidDayMonth
+            ))
+        );
+        assert_eq!(
+            CronExpression::from_str("0 0 0 ? * 15W"),
+            Err(CronParseError::InvalidExpression(
+                CronExpressionError::InvalidDayWeek
+            ))
+        );
+        assert_eq!(
+            CronExpression::from_str("0 0 L * * *"),
+            Err(CronParseError::InvalidExpression(
+                CronExpressionError::InvalidHour
+            ))
+        );
+        assert_eq!(
+            CronExpression::from_str("0 0 0 ? * MON#6"),
+            Err(CronParseError::InvalidExpression(
+                CronExpressionError::InvalidDayWeek
+            ))
+        );
+    }
+
+    #[test]
+    fn expression_list_match_test() {
+        let expression = CronExpression::from_str("0 0 8-12,14-18/2 ? * MON-FRI").unwrap();
+
+        assert!(expression.do_match(&NaiveDateTime::from_str("2022-01-03T08:00:00").unwrap()));
+        assert!(expression.do_match(&NaiveDateTime::from_str("2022-01-03T16:00:00").unwrap()));
+        assert!(!expression.do_match(&NaiveDateTime::from_str("2022-01-03T13:00:00").unwrap()));
+        assert!(!expression.do_match(&NaiveDateTime::from_str("2022-01-03T15:00:00").unwrap()));
+    }
+
+    #[test]
+    fn expression_next_after_last_day_test() {
+        let expression = CronExpression::from_str("0 0 0 L * *").unwrap();
+
+        assert_eq!(
+            expression.next_after(&DateTime::from_str("2024-02-01T00:00:00Z").unwrap()),
+            Some(DateTime::from_str("2024-02-29T00:00:00Z").unwrap())
+        );
+        assert_eq!(
+            expression.prev_before(&DateTime::from_str("2024-02-01T00:00:00Z").unwrap()),
+            Some(DateTime::from_str("2024-01-31T00:00:00Z").unwrap())
+        );
+    }
+
+    #[test]
+    fn expression_next_after_nearest_weekday_test() {
+        // 2022-01-15 is a Saturday and 2022-01-01 is a Saturday on the first day of the month
+        let expression = CronExpression::from_str("0 0 0 15W * *").unwrap();
+
+        assert_eq!(
+            expression.next_after(&DateTime::from_str("2022-01-01T00:00:00Z").unwrap()),
+            Some(DateTime::from_str("2022-01-14T00:00:00Z").unwrap())
+        );
+
+        let expression = CronExpression::from_str("0 0 0 1W * *").unwrap();
+
+        assert_eq!(
+            expression.next_after(&DateTime::from_str("2021-12-31T00:00:00Z").unwrap()),
+            Some(DateTime::from_str("2022-01-03T00:00:00Z").unwrap())
+        );
+    }
+
+    #[test]
+    fn expression_next_after_last_weekday_test() {
+        // 2022-07-31 is a Sunday
+        let expression = CronExpression::from_str("0 0 0 LW * *").unwrap();
+
+        assert_eq!(
+            expression.next_after(&DateTime::from_str("2022-07-01T00:00:00Z").unwrap()),
+            Some(DateTime::from_str("2022-07-29T00:00:00Z").unwrap())
+        );
+    }
+
+    #[test]
+    fn expression_next_after_day_week_test() {
+        let expression = CronExpression::from_str("0 0 0 ? * FRI#2").unwrap();
+
+        assert_eq!(
+            expression.next_after(&DateTime::from_str("2022-01-01T00:00:00Z").unwrap()),
+            Some(DateTime::from_str("2022-01-14T00:00:00Z").unwrap())
+        );
+
+        let expression = CronExpression::from_str("0 0 0 ? * 5L").unwrap();
+
+        assert_eq!(
+            expression.next_after(&DateTime::from_str("2022-01-01T00:00:00Z").unwrap()),
+            Some(DateTime::from_str("2022-01-28T00:00:00Z").unwrap())
+        );
+    }
+
+    #[test]
+    fn expression_prev_before_in_time_zone_test() {
+        let time_zone = TimeZone {
+            offset: 3600,
+            dst: Some(DstRule {
+                offset: 7200,
+                start: DstTransition {
+                    month: 3,
+                    week: 5,
+                    weekday: 7,
+                    time: 7200,
+                },
+                end: DstTransition {
+                    month: 10,
+                    week: 5,
+                    weekday: 7,
+                    time: 10800,
+                },
+            }),
+        };
+
+        let expression = CronExpression::from_str("0 30 6 * * *").unwrap();
+
+        assert_eq!(
+            expression.prev_before_in_time_zone(
+                &DateTime::from_str("2022-07-15T04:30:01Z").unwrap(),
+                &time_zone
+            ),
+            Some(DateTime::from_str("2022-07-15T04:30:00Z").unwrap())
+        );
+        assert_eq!(
+            expression.prev_before_in_time_zone(
+                &DateTime::from_str("2022-01-15T05:30:00Z").unwrap(),
+                &time_zone
+            ),
+            Some(DateTime::from_str("2022-01-14T05:30:00Z").unwrap())
+        );
+
+        let expression = CronExpression::from_str("0 30 2 * * *").unwrap();
+
+        // Skipped when clocks jump forward, so it fires one hour later
+        assert_eq!(
+            expression.prev_before_in_time_zone(
+                &DateTime::from_str("2022-03-27T03:00:00Z").unwrap(),
+                &time_zone
+            ),
+            Some(DateTime::from_str("2022-03-27T01:30:00Z").unwrap())
+        );
+        // Repeated when clocks fall back, so only the first occurrence counts
+        assert_eq!(
+            expression.prev_before_in_time_zone(
+                &DateTime::from_str("2022-10-30T02:00:00Z").unwrap(),
+                &time_zone
+            ),
+            Some(DateTime::from_str("2022-10-30T00:30:00Z").unwrap())
+        );
+    }
 }
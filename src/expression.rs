@@ -0,0 +1,1111 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use chrono::{Datelike, Timelike};
+use core::convert::{TryFrom, TryInto};
+use core::str::FromStr;
+
+use crate::filter::{FilterError, MAX_FILTER_LEN};
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::state_manager::StateManager;
+use crate::{ExtractorValue, Value};
+
+pub const MAX_EXPRESSION_LEN: usize = 64;
+pub const MAX_EXPRESSION_STEPS: usize = 256;
+pub const MAX_EXPRESSION_STACK_SIZE: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeField {
+    Second,
+    Minute,
+    Hour,
+    // 1 is Monday, 7 is Sunday
+    DayWeek,
+    DayMonth,
+    Month,
+    Year,
+    // Seconds since the Unix epoch
+    Timestamp,
+}
+
+// Instructions operate on a stack of signed integers, booleans are 0 and 1 and any non-zero
+// integer is true. Fixed-point values are loaded without their fractional part.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Instruction {
+    Push(i32),
+    LoadValue,
+    LoadState(u32),
+    // Converts the integer to the type of the state, wrapping around if it does not fit
+    StoreState(u32),
+    LoadTime(TimeField),
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Negate,
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    MoreThan,
+    MoreThanOrEqual,
+    And,
+    Or,
+    Not,
+    // Targets are instruction indices, the end of the program is a valid target
+    Jump(u16),
+    JumpIfZero(u16),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ExpressionError {
+    TooManyInstructions,
+    // Serialized expressions have to fit into a filter
+    TooLong,
+    InvalidJump,
+    StackUnderflow,
+    StackOverflow,
+    DivisionByZero,
+    Overflow,
+    StepLimitExceeded,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ExpressionParseError {
+    UnexpectedCharacter,
+    InvalidNumber,
+    UnknownIdentifier,
+    UnexpectedToken,
+    UnexpectedEnd,
+    InvalidExpression(ExpressionError),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Expression {
+    pub instructions: Vec<Instruction>,
+}
+
+struct Stack {
+    values: Vec<i64>,
+}
+
+impl Stack {
+    fn push(&mut self, value: i64) -> Result<(), FilterError> {
+        if self.values.len() >= MAX_EXPRESSION_STACK_SIZE {
+            return Err(FilterError::ExpressionError(ExpressionError::StackOverflow));
+        }
+
+        self.values.push(value);
+
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<i64, FilterError> {
+        self.values.pop().ok_or(FilterError::ExpressionError(
+            ExpressionError::StackUnderflow,
+        ))
+    }
+
+    fn apply<F: Fn(i64, i64) -> Result<i64, ExpressionError>>(
+        &mut self,
+        operation: F,
+    ) -> Result<(), FilterError> {
+        let rhs = self.pop()?;
+        let lhs = self.pop()?;
+
+        let result = operation(lhs, rhs).map_err(FilterError::ExpressionError)?;
+
+        self.push(result)
+    }
+}
+
+fn to_integer(value: &Value) -> Option<i64> {
+    match *value {
+        Value::U8(value) => Some(value as i64),
+        Value::U16(value) => Some(value as i64),
+        Value::U32(value) => Some(value as i64),
+        Value::Bool(value) => Some(value as i64),
+        Value::I8(value) => Some(value as i64),
+        Value::I16(value) => Some(value as i64),
+        Value::I32(value) => Some(value as i64),
+        Value::Fixed(value) => Some((value >> 16) as i64),
+        _ => None,
+    }
+}
+
+fn from_integer(state: &Value, value: i64) -> Option<Value> {
+    match state {
+        Value::U8(_) => Some(Value::U8(value as u8)),
+        Value::U16(_) => Some(Value::U16(value as u16)),
+        Value::U32(_) => Some(Value::U32(value as u32)),
+        Value::Bool(_) => Some(Value::Bool(value != 0)),
+        Value::I8(_) => Some(Value::I8(value as i8)),
+        Value::I16(_) => Some(Value::I16(value as i16)),
+        Value::I32(_) => Some(Value::I32(value as i32)),
+        Value::Fixed(_) => Some(Value::Fixed((value as i32).wrapping_shl(16))),
+        _ => None,
+    }
+}
+
+fn checked(value: Option<i64>) -> Result<i64, ExpressionError> {
+    value.ok_or(ExpressionError::Overflow)
+}
+
+impl Expression {
+    pub fn validate(&self) -> Result<(), ExpressionError> {
+        if self.instructions.len() > MAX_EXPRESSION_LEN {
+            return Err(ExpressionError::TooManyInstructions);
+        }
+
+        if self.serialize().len() > MAX_FILTER_LEN {
+            return Err(ExpressionError::TooLong);
+        }
+
+        for instruction in self.instructions.iter() {
+            match *instruction {
+                Instruction::Jump(target) | Instruction::JumpIfZero(target)
+                    if target as usize > self.instructions.len() =>
+                {
+                    return Err(ExpressionError::InvalidJump);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    // Matches if the stack is empty or its top is not zero when the program ends. State is only
+    // written if the whole program runs without errors.
+    pub fn evaluate(
+        &self,
+        value: &ExtractorValue,
+        state_manager: &mut StateManager,
+    ) -> Result<bool, FilterError> {
+        let mut stack = Stack { values: vec![] };
+        let mut writes = BTreeMap::new();
+        let mut position = 0;
+        let mut steps = 0;
+
+        while position < self.instructions.len() {
+            steps += 1;
+
+            if steps > MAX_EXPRESSION_STEPS {
+                return Err(FilterError::ExpressionError(
+                    ExpressionError::StepLimitExceeded,
+                ));
+            }
+
+            let mut next_position = position + 1;
+
+            match self.instructions[position] {
+                Instruction::Push(constant) => stack.push(constant as i64)?,
+                Instruction::LoadValue => {
+                    let value = Value::try_from(value)
+                        .ok()
+                        .and_then(|value| to_integer(&value))
+                        .ok_or(FilterError::WrongValueType)?;
+
+                    stack.push(value)?;
+                }
+                Instruction::LoadState(state_index) => {
+                    let state = writes
+                        .get(&state_index)
+                        .or_else(|| state_manager.get_value(state_index))
+                        .and_then(to_integer)
+                        .ok_or(FilterError::WrongStateType)?;
+
+                    stack.push(state)?;
+                }
+                Instruction::StoreState(state_index) => {
+                    let value = stack.pop()?;
+
+                    let state = state_manager
+                        .get_value(state_index)
+                        .and_then(|state| from_integer(state, value))
+                        .ok_or(FilterError::WrongStateType)?;
+
+                    writes.insert(state_index, state);
+                }
+                Instruction::LoadTime(field) => {
+                    let date_time = state_manager.get_local_date_time();
+
+                    let value = match field {
+                        TimeField::Second => date_time.second() as i64,
+                        TimeField::Minute => date_time.minute() as i64,
+                        TimeField::Hour => date_time.hour() as i64,
+                        TimeField::DayWeek => date_time.weekday().number_from_monday() as i64,
+                        TimeField::DayMonth => date_time.day() as i64,
+                        TimeField::Month => date_time.month() as i64,
+                        TimeField::Year => date_time.year() as i64,
+                        TimeField::Timestamp => state_manager.get_date_time().timestamp(),
+                    };
+
+                    stack.push(value)?;
+                }
+                Instruction::Add => stack.apply(|lhs, rhs| checked(lhs.checked_add(rhs)))?,
+                Instruction::Subtract => stack.apply(|lhs, rhs| checked(lhs.checked_sub(rhs)))?,
+                Instruction::Multiply => stack.apply(|lhs, rhs| checked(lhs.checked_mul(rhs)))?,
+                Instruction::Divide | Instruction::Remainder => {
+                    let is_division = self.instructions[position] == Instruction::Divide;
+
+                    stack.apply(|lhs, rhs| match rhs {
+                        0 => Err(ExpressionError::DivisionByZero),
+                        _ if is_division => checked(lhs.checked_div(rhs)),
+                        _ => checked(lhs.checked_rem(rhs)),
+                    })?
+                }
+                Instruction::Negate => {
+                    let value = stack.pop()?;
+
+                    stack.push(
+                        checked(value.checked_neg()).map_err(FilterError::ExpressionError)?,
+                    )?;
+                }
+                Instruction::Equal => stack.apply(|lhs, rhs| Ok((lhs == rhs) as i64))?,
+                Instruction::NotEqual => stack.apply(|lhs, rhs| Ok((lhs != rhs) as i64))?,
+                Instruction::LessThan => stack.apply(|lhs, rhs| Ok((lhs < rhs) as i64))?,
+                Instruction::LessThanOrEqual => stack.apply(|lhs, rhs| Ok((lhs <= rhs) as i64))?,
+                Instruction::MoreThan => stack.apply(|lhs, rhs| Ok((lhs > rhs) as i64))?,
+                Instruction::MoreThanOrEqual => stack.apply(|lhs, rhs| Ok((lhs >= rhs) as i64))?,
+                Instruction::And => stack.apply(|lhs, rhs| Ok((lhs != 0 && rhs != 0) as i64))?,
+                Instruction::Or => stack.apply(|lhs, rhs| Ok((lhs != 0 || rhs != 0) as i64))?,
+                Instruction::Not => {
+                    let value = stack.pop()?;
+
+                    stack.push((value == 0) as i64)?;
+                }
+                Instruction::Jump(target) => next_position = target as usize,
+                Instruction::JumpIfZero(target) => {
+                    if stack.pop()? == 0 {
+                        next_position = target as usize;
+                    }
+                }
+            }
+
+            position = next_position;
+        }
+
+        for (state_index, state) in writes.into_iter() {
+            state_manager.set_value(state_index, state);
+        }
+
+        match stack.values.last() {
+            Some(value) => Ok(*value != 0),
+            None => Ok(true),
+        }
+    }
+}
+
+impl Serialize for TimeField {
+    fn serialize(&self) -> Vec<u8> {
+        match self {
+            TimeField::Second => vec![0x00],
+            TimeField::Minute => vec![0x01],
+            TimeField::Hour => vec![0x02],
+            TimeField::DayWeek => vec![0x03],
+            TimeField::DayMonth => vec![0x04],
+            TimeField::Month => vec![0x05],
+            TimeField::Year => vec![0x06],
+            TimeField::Timestamp => vec![0x07],
+        }
+    }
+}
+
+impl TryDeserialize for TimeField {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.is_empty() {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        match data[0] {
+            0x00 => Ok(Box::new(TimeField::Second)),
+            0x01 => Ok(Box::new(TimeField::Minute)),
+            0x02 => Ok(Box::new(TimeField::Hour)),
+            0x03 => Ok(Box::new(TimeField::DayWeek)),
+            0x04 => Ok(Box::new(TimeField::DayMonth)),
+            0x05 => Ok(Box::new(TimeField::Month)),
+            0x06 => Ok(Box::new(TimeField::Year)),
+            0x07 => Ok(Box::new(TimeField::Timestamp)),
+            _ => Err(ConfigSerializerError::UnknownEnumVariant),
+        }
+    }
+}
+
+impl Serialize for Instruction {
+    fn serialize(&self) -> Vec<u8> {
+        let (code, mut operand) = match self {
+            Instruction::Push(constant) => (0x00, constant.to_be_bytes().to_vec()),
+            Instruction::LoadValue => (0x01, vec![]),
+            Instruction::LoadState(state_index) => (0x02, state_index.to_be_bytes().to_vec()),
+            Instruction::StoreState(state_index) => (0x03, state_index.to_be_bytes().to_vec()),
+            Instruction::LoadTime(field) => (0x04, field.serialize()),
+            Instruction::Add => (0x05, vec![]),
+            Instruction::Subtract => (0x06, vec![]),
+            Instruction::Multiply => (0x07, vec![]),
+            Instruction::Divide => (0x08, vec![]),
+            Instruction::Remainder => (0x09, vec![]),
+            Instruction::Negate => (0x0a, vec![]),
+            Instruction::Equal => (0x0b, vec![]),
+            Instruction::NotEqual => (0x0c, vec![]),
+            Instruction::LessThan => (0x0d, vec![]),
+            Instruction::LessThanOrEqual => (0x0e, vec![]),
+            Instruction::MoreThan => (0x0f, vec![]),
+            Instruction::MoreThanOrEqual => (0x10, vec![]),
+            Instruction::And => (0x11, vec![]),
+            Instruction::Or => (0x12, vec![]),
+            Instruction::Not => (0x13, vec![]),
+            Instruction::Jump(target) => (0x14, target.to_be_bytes().to_vec()),
+            Instruction::JumpIfZero(target) => (0x15, target.to_be_bytes().to_vec()),
+        };
+
+        let mut data = vec![code];
+        data.append(&mut operand);
+
+        data
+    }
+}
+
+// Operands follow the instruction code
+fn get_operand<const SIZE: usize>(data: &[u8]) -> Result<[u8; SIZE], ConfigSerializerError> {
+    match data.get(1..=SIZE) {
+        Some(operand) => Ok(operand.try_into().unwrap()),
+        None => Err(ConfigSerializerError::WrongSize),
+    }
+}
+
+impl TryDeserialize for Instruction {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.is_empty() {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        let instruction = match data[0] {
+            0x00 => Instruction::Push(i32::from_be_bytes(get_operand(data)?)),
+            0x01 => Instruction::LoadValue,
+            0x02 => Instruction::LoadState(u32::from_be_bytes(get_operand(data)?)),
+            0x03 => Instruction::StoreState(u32::from_be_bytes(get_operand(data)?)),
+            0x04 => Instruction::LoadTime(*TimeField::try_deserialize(&data[1..])?),
+            0x05 => Instruction::Add,
+            0x06 => Instruction::Subtract,
+            0x07 => Instruction::Multiply,
+            0x08 => Instruction::Divide,
+            0x09 => Instruction::Remainder,
+            0x0a => Instruction::Negate,
+            0x0b => Instruction::Equal,
+            0x0c => Instruction::NotEqual,
+            0x0d => Instruction::LessThan,
+            0x0e => Instruction::LessThanOrEqual,
+            0x0f => Instruction::MoreThan,
+            0x10 => Instruction::MoreThanOrEqual,
+            0x11 => Instruction::And,
+            0x12 => Instruction::Or,
+            0x13 => Instruction::Not,
+            0x14 => Instruction::Jump(u16::from_be_bytes(get_operand(data)?)),
+            0x15 => Instruction::JumpIfZero(u16::from_be_bytes(get_operand(data)?)),
+            _ => return Err(ConfigSerializerError::UnknownEnumVariant),
+        };
+
+        Ok(Box::new(instruction))
+    }
+}
+
+impl Serialize for Expression {
+    fn serialize(&self) -> Vec<u8> {
+        let mut data = vec![self.instructions.len() as u8];
+
+        for instruction in self.instructions.iter() {
+            data.append(&mut instruction.serialize());
+        }
+
+        data
+    }
+}
+
+impl TryDeserialize for Expression {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.is_empty() {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        let instruction_count = data[0] as usize;
+        let mut offset = 1;
+        let mut instructions = vec![];
+
+        for _ in 0..instruction_count {
+            let instruction = *Instruction::try_deserialize(&data[offset..])?;
+            offset += instruction.serialize().len();
+
+            instructions.push(instruction);
+        }
+
+        let expression = Expression { instructions };
+
+        if let Err(err) = expression.validate() {
+            return Err(ConfigSerializerError::InvalidExpression(err));
+        }
+
+        Ok(Box::new(expression))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    Number(i64),
+    Identifier(&'a str),
+    Symbol(&'a str),
+}
+
+// Longer symbols go first so that they are not split up
+const SYMBOLS: [&str; 22] = [
+    "==", "!=", "<=", ">=", "&&", "||", "+", "-", "*", "/", "%", "!", "<", ">", "(", ")", "[", "]",
+    "=", ";", "?", ":",
+];
+
+fn tokenize(expression: &str) -> Result<Vec<Token<'_>>, ExpressionParseError> {
+    let mut tokens = vec![];
+    let mut rest = expression.trim_start();
+
+    while !rest.is_empty() {
+        let length = match rest.as_bytes()[0] {
+            b'0'..=b'9' => {
+                let length = rest
+                    .find(|c: char| !c.is_ascii_alphanumeric())
+                    .unwrap_or(rest.len());
+
+                let number = match rest[..length].strip_prefix("0x") {
+                    Some(digits) => i64::from_str_radix(digits, 16),
+                    None => i64::from_str(&rest[..length]),
+                };
+
+                tokens.push(Token::Number(
+                    number.map_err(|_| ExpressionParseError::InvalidNumber)?,
+                ));
+
+                length
+            }
+            b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
+                let length = rest
+                    .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+
+                tokens.push(Token::Identifier(&rest[..length]));
+
+                length
+            }
+            _ => match SYMBOLS.iter().find(|symbol| rest.starts_with(*symbol)) {
+                Some(symbol) => {
+                    tokens.push(Token::Symbol(symbol));
+
+                    symbol.len()
+                }
+                None => return Err(ExpressionParseError::UnexpectedCharacter),
+            },
+        };
+
+        rest = rest[length..].trim_start();
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    position: usize,
+    instructions: Vec<Instruction>,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self, offset: usize) -> Option<Token<'a>> {
+        self.tokens.get(self.position + offset).copied()
+    }
+
+    fn next(&mut self) -> Result<Token<'a>, ExpressionParseError> {
+        let token = self.peek(0).ok_or(ExpressionParseError::UnexpectedEnd)?;
+        self.position += 1;
+
+        Ok(token)
+    }
+
+    fn accept(&mut self, symbol: &str) -> bool {
+        if self.peek(0) == Some(Token::Symbol(symbol)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), ExpressionParseError> {
+        match self.next()? {
+            Token::Symbol(next_symbol) if next_symbol == symbol => Ok(()),
+            _ => Err(ExpressionParseError::UnexpectedToken),
+        }
+    }
+
+    // Jump targets have to fit into an instruction
+    fn get_jump_target(&self) -> Result<u16, ExpressionParseError> {
+        if self.instructions.len() > MAX_EXPRESSION_LEN {
+            return Err(ExpressionParseError::InvalidExpression(
+                ExpressionError::TooManyInstructions,
+            ));
+        }
+
+        Ok(self.instructions.len() as u16)
+    }
+
+    fn parse_state_index(&mut self) -> Result<u32, ExpressionParseError> {
+        self.expect("[")?;
+
+        let state_index = match self.next()? {
+            Token::Number(number) => {
+                u32::try_from(number).map_err(|_| ExpressionParseError::InvalidNumber)?
+            }
+            _ => return Err(ExpressionParseError::UnexpectedToken),
+        };
+
+        self.expect("]")?;
+
+        Ok(state_index)
+    }
+
+    fn parse_statement(&mut self) -> Result<(), ExpressionParseError> {
+        let is_assignment = self.peek(0) == Some(Token::Identifier("state"))
+            && self.peek(4) == Some(Token::Symbol("="));
+
+        if is_assignment {
+            self.position += 1;
+            let state_index = self.parse_state_index()?;
+            self.expect("=")?;
+
+            self.parse_conditional()?;
+            self.instructions.push(Instruction::StoreState(state_index));
+        } else {
+            self.parse_conditional()?;
+        }
+
+        Ok(())
+    }
+
+    fn parse_conditional(&mut self) -> Result<(), ExpressionParseError> {
+        self.parse_binary(0)?;
+
+        if self.accept("?") {
+            let condition_jump = self.instructions.len();
+            self.instructions.push(Instruction::JumpIfZero(0));
+
+            self.parse_conditional()?;
+            self.expect(":")?;
+
+            let end_jump = self.instructions.len();
+            self.instructions.push(Instruction::Jump(0));
+
+            self.instructions[condition_jump] = Instruction::JumpIfZero(self.get_jump_target()?);
+            self.parse_conditional()?;
+            self.instructions[end_jump] = Instruction::Jump(self.get_jump_target()?);
+        }
+
+        Ok(())
+    }
+
+    // Operators ordered from the lowest to the highest precedence
+    fn parse_binary(&mut self, precedence: usize) -> Result<(), ExpressionParseError> {
+        const OPERATORS: [&[(&str, Instruction)]; 6] = [
+            &[("||", Instruction::Or)],
+            &[("&&", Instruction::And)],
+            &[("==", Instruction::Equal), ("!=", Instruction::NotEqual)],
+            &[
+                ("<=", Instruction::LessThanOrEqual),
+                (">=", Instruction::MoreThanOrEqual),
+                ("<", Instruction::LessThan),
+                (">", Instruction::MoreThan),
+            ],
+            &[("+", Instruction::Add), ("-", Instruction::Subtract)],
+            &[
+                ("*", Instruction::Multiply),
+                ("/", Instruction::Divide),
+                ("%", Instruction::Remainder),
+            ],
+        ];
+
+        if precedence == OPERATORS.len() {
+            return self.parse_unary();
+        }
+
+        self.parse_binary(precedence + 1)?;
+
+        'operators: loop {
+            for (symbol, instruction) in OPERATORS[precedence].iter() {
+                if self.accept(symbol) {
+                    self.parse_binary(precedence + 1)?;
+                    self.instructions.push(instruction.clone());
+
+                    continue 'operators;
+                }
+            }
+
+            return Ok(());
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<(), ExpressionParseError> {
+        if self.accept("-") {
+            self.parse_unary()?;
+            self.instructions.push(Instruction::Negate);
+        } else if self.accept("!") {
+            self.parse_unary()?;
+            self.instructions.push(Instruction::Not);
+        } else {
+            self.parse_primary()?;
+        }
+
+        Ok(())
+    }
+
+    fn parse_primary(&mut self) -> Result<(), ExpressionParseError> {
+        let instruction = match self.next()? {
+            Token::Number(number) => Instruction::Push(
+                i32::try_from(number).map_err(|_| ExpressionParseError::InvalidNumber)?,
+            ),
+            Token::Identifier("true") => Instruction::Push(1),
+            Token::Identifier("false") => Instruction::Push(0),
+            Token::Identifier("value") => Instruction::LoadValue,
+            Token::Identifier("state") => Instruction::LoadState(self.parse_state_index()?),
+            Token::Identifier("second") => Instruction::LoadTime(TimeField::Second),
+            Token::Identifier("minute") => Instruction::LoadTime(TimeField::Minute),
+            Token::Identifier("hour") => Instruction::LoadTime(TimeField::Hour),
+            Token::Identifier("day_week") => Instruction::LoadTime(TimeField::DayWeek),
+            Token::Identifier("day_month") => Instruction::LoadTime(TimeField::DayMonth),
+            Token::Identifier("month") => Instruction::LoadTime(TimeField::Month),
+            Token::Identifier("year") => Instruction::LoadTime(TimeField::Year),
+            Token::Identifier("timestamp") => Instruction::LoadTime(TimeField::Timestamp),
+            Token::Identifier(_) => return Err(ExpressionParseError::UnknownIdentifier),
+            Token::Symbol("(") => {
+                self.parse_conditional()?;
+                self.expect(")")?;
+
+                return Ok(());
+            }
+            Token::Symbol(_) => return Err(ExpressionParseError::UnexpectedToken),
+        };
+
+        self.instructions.push(instruction);
+
+        Ok(())
+    }
+}
+
+// Statements are separated by semicolons and are either `state[index] = expression` or an
+// expression, whose result decides whether the program matches if it is the last statement
+impl FromStr for Expression {
+    type Err = ExpressionParseError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(expression)?,
+            position: 0,
+            instructions: vec![],
+        };
+
+        loop {
+            parser.parse_statement()?;
+
+            if !parser.accept(";") {
+                break;
+            }
+
+            if parser.peek(0).is_none() {
+                break;
+            }
+        }
+
+        if parser.peek(0).is_some() {
+            return Err(ExpressionParseError::UnexpectedToken);
+        }
+
+        let expression = Expression {
+            instructions: parser.instructions,
+        };
+
+        if let Err(err) = expression.validate() {
+            return Err(ExpressionParseError::InvalidExpression(err));
+        }
+
+        Ok(expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::String;
+    use chrono::{DateTime, NaiveDate, Utc};
+
+    fn evaluate(
+        expression: &str,
+        value: &ExtractorValue,
+        state_manager: &mut StateManager,
+    ) -> Result<bool, FilterError> {
+        Expression::from_str(expression)
+            .unwrap()
+            .evaluate(value, state_manager)
+    }
+
+    #[test]
+    fn parse_precedence_test() {
+        assert_eq!(
+            Expression::from_str("state[3] = (state[3] + value * 10) % 100"),
+            Ok(Expression {
+                instructions: vec![
+                    Instruction::LoadState(3),
+                    Instruction::LoadValue,
+                    Instruction::Push(10),
+                    Instruction::Multiply,
+                    Instruction::Add,
+                    Instruction::Push(100),
+                    Instruction::Remainder,
+                    Instruction::StoreState(3),
+                ]
+            })
+        );
+        assert_eq!(
+            Expression::from_str("1 - 2 - 3 || !0 && -hour"),
+            Ok(Expression {
+                instructions: vec![
+                    Instruction::Push(1),
+                    Instruction::Push(2),
+                    Instruction::Subtract,
+                    Instruction::Push(3),
+                    Instruction::Subtract,
+                    Instruction::Push(0),
+                    Instruction::Not,
+                    Instruction::LoadTime(TimeField::Hour),
+                    Instruction::Negate,
+                    Instruction::And,
+                    Instruction::Or,
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn parse_conditional_test() {
+        assert_eq!(
+            Expression::from_str("value > 0x10 ? 1 : 2"),
+            Ok(Expression {
+                instructions: vec![
+                    Instruction::LoadValue,
+                    Instruction::Push(0x10),
+                    Instruction::MoreThan,
+                    Instruction::JumpIfZero(6),
+                    Instruction::Push(1),
+                    Instruction::Jump(7),
+                    Instruction::Push(2),
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            Expression::from_str("value $ 1"),
+            Err(ExpressionParseError::UnexpectedCharacter)
+        );
+        assert_eq!(
+            Expression::from_str("state[1] +"),
+            Err(ExpressionParseError::UnexpectedEnd)
+        );
+        assert_eq!(
+            Expression::from_str("values == 1"),
+            Err(ExpressionParseError::UnknownIdentifier)
+        );
+        assert_eq!(
+            Expression::from_str("(1 + 2"),
+            Err(ExpressionParseError::UnexpectedEnd)
+        );
+        assert_eq!(
+            Expression::from_str("1 2"),
+            Err(ExpressionParseError::UnexpectedToken)
+        );
+        assert_eq!(
+            Expression::from_str("4294967296"),
+            Err(ExpressionParseError::InvalidNumber)
+        );
+        assert_eq!(
+            Expression::from_str("state[-1] = 1"),
+            Err(ExpressionParseError::UnexpectedToken)
+        );
+    }
+
+    #[test]
+    fn parse_too_many_instructions_test() {
+        let mut expression = String::from("1");
+
+        for _ in 0..MAX_EXPRESSION_LEN {
+            expression.push_str(" + 1");
+        }
+
+        assert_eq!(
+            Expression::from_str(&expression),
+            Err(ExpressionParseError::InvalidExpression(
+                ExpressionError::TooManyInstructions
+            ))
+        );
+    }
+
+    #[test]
+    fn evaluate_assignment_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(3, Value::U8(95));
+
+        assert_eq!(
+            evaluate(
+                "state[3] = (state[3] + value * 10) % 100",
+                &ExtractorValue::U16(0x0001),
+                &mut state_manager
+            ),
+            Ok(true)
+        );
+        assert_eq!(*state_manager.get_value(3).unwrap(), Value::U8(5));
+    }
+
+    #[test]
+    fn evaluate_condition_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(1, Value::Bool(true));
+        state_manager.set_value(2, Value::Bool(false));
+
+        let expression = "state[1] && (value > 20 || state[2])";
+
+        assert_eq!(
+            evaluate(expression, &ExtractorValue::U8(21), &mut state_manager),
+            Ok(true)
+        );
+        assert_eq!(
+            evaluate(expression, &ExtractorValue::U8(20), &mut state_manager),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn evaluate_statements_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::I16(-1));
+        state_manager.set_value(1, Value::Fixed(0x0002_8000));
+
+        assert_eq!(
+            evaluate(
+                "state[0] = state[0] * 3; state[1] = state[0] < 0 ? 7 : 8; state[1] == 7",
+                &ExtractorValue::None,
+                &mut state_manager
+            ),
+            Ok(true)
+        );
+        assert_eq!(*state_manager.get_value(0).unwrap(), Value::I16(-3));
+        assert_eq!(
+            *state_manager.get_value(1).unwrap(),
+            Value::Fixed(0x0007_0000)
+        );
+    }
+
+    #[test]
+    fn evaluate_time_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_date_time(DateTime::from_utc(
+            NaiveDate::from_ymd(2021, 11, 6).and_hms(12, 30, 15),
+            Utc,
+        ));
+
+        assert_eq!(
+            evaluate(
+                "hour == 12 && minute == 30 && second == 15 && day_week == 6",
+                &ExtractorValue::None,
+                &mut state_manager
+            ),
+            Ok(true)
+        );
+        assert_eq!(
+            evaluate(
+                "year == 2021 && month == 11 && day_month == 6 && timestamp == 1636201815",
+                &ExtractorValue::None,
+                &mut state_manager
+            ),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn evaluate_error_leaves_state_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U32(0x0000_0001));
+
+        assert_eq!(
+            evaluate(
+                "state[0] = 5; state[0] / (value - 1)",
+                &ExtractorValue::U8(0x01),
+                &mut state_manager
+            ),
+            Err(FilterError::ExpressionError(
+                ExpressionError::DivisionByZero
+            ))
+        );
+        assert_eq!(
+            *state_manager.get_value(0).unwrap(),
+            Value::U32(0x0000_0001)
+        );
+    }
+
+    #[test]
+    fn evaluate_wrong_type_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::Rgb(0x00, 0x00, 0x00));
+
+        assert_eq!(
+            evaluate("state[0] == 1", &ExtractorValue::None, &mut state_manager),
+            Err(FilterError::WrongStateType)
+        );
+        assert_eq!(
+            evaluate("state[1] = 1", &ExtractorValue::None, &mut state_manager),
+            Err(FilterError::WrongStateType)
+        );
+        assert_eq!(
+            evaluate("value == 1", &ExtractorValue::None, &mut state_manager),
+            Err(FilterError::WrongValueType)
+        );
+    }
+
+    #[test]
+    fn evaluate_limits_test() {
+        let mut state_manager = StateManager::new();
+
+        let expression = Expression {
+            instructions: vec![Instruction::Jump(0)],
+        };
+
+        assert_eq!(
+            expression.evaluate(&ExtractorValue::None, &mut state_manager),
+            Err(FilterError::ExpressionError(
+                ExpressionError::StepLimitExceeded
+            ))
+        );
+
+        let expression = Expression {
+            instructions: vec![Instruction::Push(1), Instruction::Jump(0)],
+        };
+
+        assert_eq!(
+            expression.evaluate(&ExtractorValue::None, &mut state_manager),
+            Err(FilterError::ExpressionError(ExpressionError::StackOverflow))
+        );
+
+        let expression = Expression {
+            instructions: vec![Instruction::Add],
+        };
+
+        assert_eq!(
+            expression.evaluate(&ExtractorValue::None, &mut state_manager),
+            Err(FilterError::ExpressionError(
+                ExpressionError::StackUnderflow
+            ))
+        );
+    }
+
+    #[test]
+    fn serialize_test() {
+        let expression = Expression {
+            instructions: vec![
+                Instruction::LoadState(0xabab_abab),
+                Instruction::Push(-2),
+                Instruction::LoadTime(TimeField::Hour),
+                Instruction::JumpIfZero(0x0004),
+                Instruction::Add,
+            ],
+        };
+
+        let expected_data = vec![
+            0x05, // instruction count
+            0x02, 0xab, 0xab, 0xab, 0xab, // load state
+            0x00, 0xff, 0xff, 0xff, 0xfe, // push
+            0x04, 0x02, // load time
+            0x15, 0x00, 0x04, // jump if zero
+            0x05, // add
+        ];
+
+        assert_eq!(expression.serialize(), expected_data);
+    }
+
+    #[test]
+    fn deserialize_test() {
+        let data = vec![
+            0x03, // instruction count
+            0x01, // load value
+            0x03, 0x00, 0x00, 0x00, 0x01, // store state
+            0x14, 0x00, 0x03, // jump
+        ];
+
+        let expression = Box::new(Expression {
+            instructions: vec![
+                Instruction::LoadValue,
+                Instruction::StoreState(0x0000_0001),
+                Instruction::Jump(0x0003),
+            ],
+        });
+
+        assert_eq!(Expression::try_deserialize(&data), Ok(expression));
+    }
+
+    #[test]
+    fn deserialize_invalid_test() {
+        assert_eq!(
+            Expression::try_deserialize(&[0x01, 0x14, 0x00, 0x02]),
+            Err(ConfigSerializerError::InvalidExpression(
+                ExpressionError::InvalidJump
+            ))
+        );
+        assert_eq!(
+            Expression::try_deserialize(&[0x02, 0x00, 0x00, 0x00]),
+            Err(ConfigSerializerError::WrongSize)
+        );
+        assert_eq!(
+            Expression::try_deserialize(&[0x01, 0x16]),
+            Err(ConfigSerializerError::UnknownEnumVariant)
+        );
+    }
+
+    #[test]
+    fn deserialize_too_long_test() {
+        // 50 pushes and 4 additions take up exactly 255 bytes
+        let mut data = vec![54];
+
+        for _ in 0..50 {
+            data.append(&mut vec![0x00, 0x00, 0x00, 0x00, 0x01]);
+        }
+
+        data.append(&mut vec![0x05; 4]);
+
+        assert_eq!(data.len(), MAX_FILTER_LEN);
+        assert!(Expression::try_deserialize(&data).is_ok());
+
+        data[0] = 55;
+        data.push(0x05);
+
+        assert_eq!(
+            Expression::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidExpression(
+                ExpressionError::TooLong
+            ))
+        );
+
+        let expression = Expression {
+            instructions: vec![Instruction::Push(1); 52],
+        };
+
+        assert_eq!(expression.validate(), Err(ExpressionError::TooLong));
+    }
+}
@@ -8,9 +8,13 @@ use ross_protocol::convert_packet::ConvertPacket;
 use ross_protocol::event::button::{ButtonPressedEvent, ButtonReleasedEvent};
 use ross_protocol::packet::Packet;
 
-use crate::extractor::{Extractor, ExtractorError, BUTTON_INDEX_EXTRACTOR_CODE};
+use crate::extractor::{
+    Extractor, ExtractorError, BUTTON_GESTURE_EXTRACTOR_CODE, BUTTON_GESTURE_INDEX_EXTRACTOR_CODE,
+    BUTTON_INDEX_EXTRACTOR_CODE,
+};
+use crate::gesture::ButtonGestureEvent;
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
-use crate::ExtractorValue;
+use crate::{ExtractorValue, ExtractorValueType};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -37,6 +41,10 @@ impl Extractor for ButtonIndexExtractor {
     fn get_code(&self) -> u16 {
         BUTTON_INDEX_EXTRACTOR_CODE
     }
+
+    fn get_value_types(&self) -> Vec<ExtractorValueType> {
+        vec![ExtractorValueType::U8]
+    }
 }
 
 impl Serialize for ButtonIndexExtractor {
@@ -51,6 +59,84 @@ impl TryDeserialize for ButtonIndexExtractor {
     }
 }
 
+#[repr(C)]
+#[derive(Debug, Default, PartialEq)]
+pub struct ButtonGestureExtractor {}
+
+impl ButtonGestureExtractor {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Extractor for ButtonGestureExtractor {
+    fn extract<'a>(&self, packet: &'a Packet) -> Result<ExtractorValue<'a>, ExtractorError> {
+        match ButtonGestureEvent::try_from_packet(packet) {
+            Ok(event) => Ok(ExtractorValue::U8(event.gesture.get_code())),
+            Err(err) => Err(ExtractorError::ConvertPacketError(err)),
+        }
+    }
+
+    fn get_code(&self) -> u16 {
+        BUTTON_GESTURE_EXTRACTOR_CODE
+    }
+
+    fn get_value_types(&self) -> Vec<ExtractorValueType> {
+        vec![ExtractorValueType::U8]
+    }
+}
+
+impl Serialize for ButtonGestureExtractor {
+    fn serialize(&self) -> Vec<u8> {
+        vec![]
+    }
+}
+
+impl TryDeserialize for ButtonGestureExtractor {
+    fn try_deserialize(_data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        Ok(Box::new(Self {}))
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Default, PartialEq)]
+pub struct ButtonGestureIndexExtractor {}
+
+impl ButtonGestureIndexExtractor {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Extractor for ButtonGestureIndexExtractor {
+    fn extract<'a>(&self, packet: &'a Packet) -> Result<ExtractorValue<'a>, ExtractorError> {
+        match ButtonGestureEvent::try_from_packet(packet) {
+            Ok(event) => Ok(ExtractorValue::U8(event.index)),
+            Err(err) => Err(ExtractorError::ConvertPacketError(err)),
+        }
+    }
+
+    fn get_code(&self) -> u16 {
+        BUTTON_GESTURE_INDEX_EXTRACTOR_CODE
+    }
+
+    fn get_value_types(&self) -> Vec<ExtractorValueType> {
+        vec![ExtractorValueType::U8]
+    }
+}
+
+impl Serialize for ButtonGestureIndexExtractor {
+    fn serialize(&self) -> Vec<u8> {
+        vec![]
+    }
+}
+
+impl TryDeserialize for ButtonGestureIndexExtractor {
+    fn try_deserialize(_data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        Ok(Box::new(Self {}))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     extern crate alloc;
@@ -61,6 +147,8 @@ mod tests {
 
     use ross_protocol::event::event_code::{BUTTON_PRESSED_EVENT_CODE, BUTTON_RELEASED_EVENT_CODE};
 
+    use crate::gesture::BUTTON_GESTURE_EVENT_CODE;
+
     const PACKET: Packet = Packet {
         is_error: false,
         device_address: 0xabab,
@@ -137,6 +225,61 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn gesture_correct_format_test() {
+        let mut packet = PACKET;
+        packet.data = vec![
+            ((BUTTON_GESTURE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BUTTON_GESTURE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                            // button address
+            0x23,                                            // button address
+            0x45,                                            // index
+            0x01,                                            // gesture
+        ];
+
+        let extractor = ButtonGestureIndexExtractor::new();
+
+        assert_eq!(extractor.extract(&packet), Ok(ExtractorValue::U8(0x45)),);
+
+        let extractor = ButtonGestureExtractor::new();
+
+        assert_eq!(extractor.extract(&packet), Ok(ExtractorValue::U8(0x01)),);
+
+        // Gestures are not reported as presses or releases to existing configs
+        let extractor = ButtonIndexExtractor::new();
+
+        assert!(matches!(
+            extractor.extract(&packet),
+            Err(ExtractorError::ConvertPacketError(_))
+        ));
+    }
+
+    #[test]
+    fn gesture_wrong_event_test() {
+        let mut packet = PACKET;
+        packet.data = vec![
+            ((BUTTON_PRESSED_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BUTTON_PRESSED_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                            // button address
+            0x23,                                            // button address
+            0x45,                                            // index
+        ];
+
+        let extractor = ButtonGestureExtractor::new();
+
+        assert!(matches!(
+            extractor.extract(&packet),
+            Err(ExtractorError::ConvertPacketError(_))
+        ));
+
+        let extractor = ButtonGestureIndexExtractor::new();
+
+        assert!(matches!(
+            extractor.extract(&packet),
+            Err(ExtractorError::ConvertPacketError(_))
+        ));
+    }
+
     #[test]
     fn serialize_test() {
         let extractor = ButtonIndexExtractor::new();
@@ -11,7 +11,7 @@ use crate::extractor::{
     Extractor, ExtractorError, EVENT_CODE_EXTRACTOR_CODE, EVENT_PRODUCER_ADDRESS_EXTRACTOR_CODE,
 };
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
-use crate::ExtractorValue;
+use crate::{ExtractorValue, ExtractorValueType};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -37,6 +37,10 @@ impl Extractor for EventCodeExtractor {
     fn get_code(&self) -> u16 {
         EVENT_CODE_EXTRACTOR_CODE
     }
+
+    fn get_value_types(&self) -> Vec<ExtractorValueType> {
+        vec![ExtractorValueType::U16]
+    }
 }
 
 impl Serialize for EventCodeExtractor {
@@ -75,6 +79,10 @@ impl Extractor for EventProducerAddressExtractor {
     fn get_code(&self) -> u16 {
         EVENT_PRODUCER_ADDRESS_EXTRACTOR_CODE
     }
+
+    fn get_value_types(&self) -> Vec<ExtractorValueType> {
+        vec![ExtractorValueType::U16]
+    }
 }
 
 impl Serialize for EventProducerAddressExtractor {
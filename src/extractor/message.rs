@@ -11,7 +11,7 @@ use crate::extractor::{
     Extractor, ExtractorError, MESSAGE_CODE_EXTRACTOR_CODE, MESSAGE_VALUE_EXTRACTOR_CODE,
 };
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
-use crate::ExtractorValue;
+use crate::{ExtractorValue, ExtractorValueType};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -34,6 +34,10 @@ impl Extractor for MessageCodeExtractor {
     fn get_code(&self) -> u16 {
         MESSAGE_CODE_EXTRACTOR_CODE
     }
+
+    fn get_value_types(&self) -> Vec<ExtractorValueType> {
+        vec![ExtractorValueType::U16]
+    }
 }
 
 impl Serialize for MessageCodeExtractor {
@@ -80,6 +84,15 @@ impl Extractor for MessageValueExtractor {
     fn get_code(&self) -> u16 {
         MESSAGE_VALUE_EXTRACTOR_CODE
     }
+
+    fn get_value_types(&self) -> Vec<ExtractorValueType> {
+        vec![
+            ExtractorValueType::U8,
+            ExtractorValueType::U16,
+            ExtractorValueType::U32,
+            ExtractorValueType::Bool,
+        ]
+    }
 }
 
 impl Serialize for MessageValueExtractor {
@@ -1,3 +1,6 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
 use core::fmt::Debug;
 use downcast_rs::{impl_downcast, Downcast};
 
@@ -5,7 +8,7 @@ use ross_protocol::convert_packet::ConvertPacketError;
 use ross_protocol::packet::Packet;
 
 use crate::serializer::Serialize;
-use crate::ExtractorValue;
+use crate::{ExtractorValue, ExtractorValueType};
 
 mod none;
 pub use none::*;
@@ -29,6 +32,9 @@ pub const EVENT_PRODUCER_ADDRESS_EXTRACTOR_CODE: u16 = 0x0003;
 pub const MESSAGE_CODE_EXTRACTOR_CODE: u16 = 0x0004;
 pub const MESSAGE_VALUE_EXTRACTOR_CODE: u16 = 0x0005;
 pub const BUTTON_INDEX_EXTRACTOR_CODE: u16 = 0x0006;
+pub const PACKET_BYTES_EXTRACTOR_CODE: u16 = 0x0007;
+pub const BUTTON_GESTURE_EXTRACTOR_CODE: u16 = 0x0008;
+pub const BUTTON_GESTURE_INDEX_EXTRACTOR_CODE: u16 = 0x0009;
 
 #[derive(Debug, PartialEq)]
 pub enum ExtractorError {
@@ -40,6 +46,7 @@ pub enum ExtractorError {
 pub trait Extractor: Downcast + Debug + Serialize {
     fn extract<'a>(&self, packet: &'a Packet) -> Result<ExtractorValue<'a>, ExtractorError>;
     fn get_code(&self) -> u16;
+    fn get_value_types(&self) -> Vec<ExtractorValueType>;
 }
 
 impl_downcast!(Extractor);
@@ -7,7 +7,7 @@ use ross_protocol::packet::Packet;
 
 use crate::extractor::{Extractor, ExtractorError, NONE_EXTRACTOR_CODE};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
-use crate::ExtractorValue;
+use crate::{ExtractorValue, ExtractorValueType};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -27,6 +27,10 @@ impl Extractor for NoneExtractor {
     fn get_code(&self) -> u16 {
         NONE_EXTRACTOR_CODE
     }
+
+    fn get_value_types(&self) -> Vec<ExtractorValueType> {
+        vec![ExtractorValueType::None]
+    }
 }
 
 impl Serialize for NoneExtractor {
@@ -3,11 +3,14 @@ extern crate alloc;
 use alloc::boxed::Box;
 use alloc::vec;
 use alloc::vec::Vec;
+use core::convert::TryInto;
 use ross_protocol::packet::Packet;
 
-use crate::extractor::{Extractor, ExtractorError, PACKET_EXTRACTOR_CODE};
+use crate::extractor::{
+    Extractor, ExtractorError, PACKET_BYTES_EXTRACTOR_CODE, PACKET_EXTRACTOR_CODE,
+};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
-use crate::ExtractorValue;
+use crate::{ExtractorValue, ExtractorValueType, MAX_BYTES_VALUE_LEN};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -27,6 +30,10 @@ impl Extractor for PacketExtractor {
     fn get_code(&self) -> u16 {
         PACKET_EXTRACTOR_CODE
     }
+
+    fn get_value_types(&self) -> Vec<ExtractorValueType> {
+        vec![ExtractorValueType::Packet]
+    }
 }
 
 impl Serialize for PacketExtractor {
@@ -41,6 +48,65 @@ impl TryDeserialize for PacketExtractor {
     }
 }
 
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub struct PacketBytesExtractor {
+    offset: u16,
+    len: u8,
+}
+
+impl PacketBytesExtractor {
+    pub fn new(offset: u16, len: u8) -> Result<Self, ConfigSerializerError> {
+        if len as usize > MAX_BYTES_VALUE_LEN {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        Ok(Self { offset, len })
+    }
+}
+
+impl Extractor for PacketBytesExtractor {
+    fn extract<'a>(&self, packet: &'a Packet) -> Result<ExtractorValue<'a>, ExtractorError> {
+        let start = self.offset as usize;
+        let end = start + self.len as usize;
+
+        if packet.data.len() < end {
+            Err(ExtractorError::PacketTooShort)
+        } else {
+            Ok(ExtractorValue::Bytes(&packet.data[start..end]))
+        }
+    }
+
+    fn get_code(&self) -> u16 {
+        PACKET_BYTES_EXTRACTOR_CODE
+    }
+
+    fn get_value_types(&self) -> Vec<ExtractorValueType> {
+        vec![ExtractorValueType::Bytes]
+    }
+}
+
+impl Serialize for PacketBytesExtractor {
+    fn serialize(&self) -> Vec<u8> {
+        let offset = self.offset.to_be_bytes();
+
+        vec![offset[0], offset[1], self.len]
+    }
+}
+
+impl TryDeserialize for PacketBytesExtractor {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.len() < 3 {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        let offset = u16::from_be_bytes(data[0..=1].try_into().unwrap());
+        let len = data[2];
+
+        Ok(Box::new(Self::new(offset, len)?))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     extern crate alloc;
@@ -91,4 +157,78 @@ mod tests {
 
         assert_eq!(PacketExtractor::try_deserialize(&data), Ok(extractor));
     }
+
+    #[test]
+    fn bytes_test() {
+        let mut packet = PACKET;
+        packet.data = vec![
+            0x00, // event code
+            0x00, // event code
+            0x61, // payload
+            0x62, // payload
+        ];
+
+        let extractor = PacketBytesExtractor::new(2, 2).unwrap();
+
+        assert_eq!(
+            extractor.extract(&packet),
+            Ok(ExtractorValue::Bytes(&[0x61, 0x62]))
+        );
+    }
+
+    #[test]
+    fn bytes_packet_too_short_test() {
+        let mut packet = PACKET;
+        packet.data = vec![
+            0x00, // event code
+            0x00, // event code
+            0x61, // payload
+        ];
+
+        let extractor = PacketBytesExtractor::new(2, 2).unwrap();
+
+        assert_eq!(
+            extractor.extract(&packet),
+            Err(ExtractorError::PacketTooShort)
+        );
+    }
+
+    #[test]
+    fn bytes_serialize_test() {
+        let extractor = PacketBytesExtractor::new(0x0102, 0x10).unwrap();
+
+        let expected_data = vec![0x01, 0x02, 0x10];
+
+        assert_eq!(extractor.serialize(), expected_data);
+    }
+
+    #[test]
+    fn bytes_deserialize_test() {
+        let data = vec![0x01, 0x02, 0x10];
+
+        let extractor = Box::new(PacketBytesExtractor::new(0x0102, 0x10).unwrap());
+
+        assert_eq!(PacketBytesExtractor::try_deserialize(&data), Ok(extractor));
+    }
+
+    #[test]
+    fn bytes_deserialize_wrong_size_test() {
+        let data = vec![0x01, 0x02];
+
+        assert_eq!(
+            PacketBytesExtractor::try_deserialize(&data),
+            Err(ConfigSerializerError::WrongSize)
+        );
+
+        let data = vec![0x01, 0x02, MAX_BYTES_VALUE_LEN as u8 + 1];
+
+        assert_eq!(
+            PacketBytesExtractor::try_deserialize(&data),
+            Err(ConfigSerializerError::WrongSize)
+        );
+        assert_eq!(
+            PacketBytesExtractor::new(0x0102, MAX_BYTES_VALUE_LEN as u8 + 1),
+            Err(ConfigSerializerError::WrongSize)
+        );
+    }
 }
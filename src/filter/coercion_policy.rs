@@ -0,0 +1,396 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::filter::{FilterError, OverflowMode};
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::{ExtractorValueType, Value, ValueType};
+
+// Strict requires both sides of a comparison or an operation to be of the same type.
+//
+// Widen allows integers and fixed-point numbers of different types to be mixed. Both sides are
+// widened to a signed Q16.16 number before they are compared or combined, so U8(5) equals
+// U16(5) and I8(-1) is less than U32(0). Results of arithmetic are converted back to the type of
+// the state using the filter's overflow mode, integer states drop the fractional part of a
+// fixed-point result by rounding towards negative infinity. Other values are never coerced.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoercionPolicy {
+    Strict,
+    Widen,
+}
+
+pub(crate) const NUMERIC_VALUE_TYPES: [ValueType; 7] = [
+    ValueType::U8,
+    ValueType::U16,
+    ValueType::U32,
+    ValueType::I8,
+    ValueType::I16,
+    ValueType::I32,
+    ValueType::Fixed,
+];
+
+pub(crate) const NUMERIC_EXTRACTOR_VALUE_TYPES: [ExtractorValueType; 7] = [
+    ExtractorValueType::U8,
+    ExtractorValueType::U16,
+    ExtractorValueType::U32,
+    ExtractorValueType::I8,
+    ExtractorValueType::I16,
+    ExtractorValueType::I32,
+    ExtractorValueType::Fixed,
+];
+
+fn to_integer(value: &Value) -> Option<i64> {
+    match *value {
+        Value::U8(value) => Some(value as i64),
+        Value::U16(value) => Some(value as i64),
+        Value::U32(value) => Some(value as i64),
+        Value::I8(value) => Some(value as i64),
+        Value::I16(value) => Some(value as i64),
+        Value::I32(value) => Some(value as i64),
+        Value::Fixed(value) => Some(value as i64),
+        _ => None,
+    }
+}
+
+fn widen(value: &Value) -> Option<i64> {
+    match value {
+        Value::Fixed(_) => to_integer(value),
+        _ => to_integer(value).map(|value| value << 16),
+    }
+}
+
+fn get_integer_range(value_type: ValueType) -> Option<(i64, i64)> {
+    match value_type {
+        ValueType::U8 => Some((u8::MIN as i64, u8::MAX as i64)),
+        ValueType::U16 => Some((u16::MIN as i64, u16::MAX as i64)),
+        ValueType::U32 => Some((u32::MIN as i64, u32::MAX as i64)),
+        ValueType::I8 => Some((i8::MIN as i64, i8::MAX as i64)),
+        ValueType::I16 => Some((i16::MIN as i64, i16::MAX as i64)),
+        ValueType::I32 | ValueType::Fixed => Some((i32::MIN as i64, i32::MAX as i64)),
+        _ => None,
+    }
+}
+
+// Truncates values that are out of range
+fn from_integer(value_type: ValueType, value: i64) -> Value {
+    match value_type {
+        ValueType::U8 => Value::U8(value as u8),
+        ValueType::U16 => Value::U16(value as u16),
+        ValueType::U32 => Value::U32(value as u32),
+        ValueType::I8 => Value::I8(value as i8),
+        ValueType::I16 => Value::I16(value as i16),
+        ValueType::I32 => Value::I32(value as i32),
+        _ => Value::Fixed(value as i32),
+    }
+}
+
+fn narrow(
+    overflow_mode: &OverflowMode,
+    value: i64,
+    value_type: ValueType,
+) -> Result<Option<Value>, FilterError> {
+    let (min, max) = match get_integer_range(value_type) {
+        Some(range) => range,
+        None => return Err(FilterError::WrongStateType),
+    };
+
+    let value = match value_type {
+        ValueType::Fixed => value,
+        _ => value >> 16,
+    };
+
+    let value = match overflow_mode {
+        OverflowMode::Wrap => value,
+        OverflowMode::Saturate => value.max(min).min(max),
+        OverflowMode::Clamp(lower, upper) => {
+            if lower.get_type() != value_type || upper.get_type() != value_type {
+                return Err(FilterError::WrongStateType);
+            }
+
+            match (to_integer(lower), to_integer(upper)) {
+                (Some(lower), Some(upper)) => value.max(min).min(max).max(lower).min(upper),
+                _ => return Err(FilterError::WrongStateType),
+            }
+        }
+        OverflowMode::Fail => {
+            if value < min || value > max {
+                return Ok(None);
+            }
+
+            value
+        }
+    };
+
+    Ok(Some(from_integer(value_type, value)))
+}
+
+impl CoercionPolicy {
+    pub fn compare(&self, lhs: &Value, rhs: &Value) -> Option<Ordering> {
+        if lhs.get_type() == rhs.get_type() {
+            return lhs.partial_cmp(rhs);
+        }
+
+        match self {
+            CoercionPolicy::Strict => None,
+            CoercionPolicy::Widen => match (widen(lhs), widen(rhs)) {
+                (Some(lhs), Some(rhs)) => Some(lhs.cmp(&rhs)),
+                _ => None,
+            },
+        }
+    }
+
+    // Returns None unless both sides are numbers
+    pub fn compare_numbers(&self, lhs: &Value, rhs: &Value) -> Option<Ordering> {
+        match (to_integer(lhs), to_integer(rhs)) {
+            (Some(_), Some(_)) => self.compare(lhs, rhs),
+            _ => None,
+        }
+    }
+
+    pub fn equals(&self, lhs: &Value, rhs: &Value) -> bool {
+        self.compare(lhs, rhs) == Some(Ordering::Equal)
+    }
+
+    // Returns None if the operation overflowed in fail mode
+    pub fn add(
+        &self,
+        overflow_mode: &OverflowMode,
+        lhs: &Value,
+        rhs: &Value,
+    ) -> Result<Option<Value>, FilterError> {
+        match (self, widen(lhs), widen(rhs)) {
+            (CoercionPolicy::Widen, Some(wide_lhs), Some(wide_rhs))
+                if lhs.get_type() != rhs.get_type() =>
+            {
+                narrow(overflow_mode, wide_lhs + wide_rhs, lhs.get_type())
+            }
+            _ => overflow_mode.add(lhs, rhs),
+        }
+    }
+
+    // Returns None if the operation overflowed in fail mode
+    pub fn sub(
+        &self,
+        overflow_mode: &OverflowMode,
+        lhs: &Value,
+        rhs: &Value,
+    ) -> Result<Option<Value>, FilterError> {
+        match (self, widen(lhs), widen(rhs)) {
+            (CoercionPolicy::Widen, Some(wide_lhs), Some(wide_rhs))
+                if lhs.get_type() != rhs.get_type() =>
+            {
+                narrow(overflow_mode, wide_lhs - wide_rhs, lhs.get_type())
+            }
+            _ => overflow_mode.sub(lhs, rhs),
+        }
+    }
+
+    // Types of state that can be compared with or combined with a value of the given type
+    pub fn get_accepted_value_types(&self, value_type: ValueType) -> Vec<ValueType> {
+        match self {
+            CoercionPolicy::Widen if NUMERIC_VALUE_TYPES.contains(&value_type) => {
+                NUMERIC_VALUE_TYPES.to_vec()
+            }
+            _ => vec![value_type],
+        }
+    }
+
+    pub fn get_accepted_extractor_value_types(
+        &self,
+        value_type: ExtractorValueType,
+    ) -> Vec<ExtractorValueType> {
+        match self {
+            CoercionPolicy::Widen if NUMERIC_EXTRACTOR_VALUE_TYPES.contains(&value_type) => {
+                NUMERIC_EXTRACTOR_VALUE_TYPES.to_vec()
+            }
+            _ => vec![value_type],
+        }
+    }
+}
+
+// Filters serialized without a coercion policy are strict
+pub fn try_deserialize_coercion_policy(
+    data: &[u8],
+    offset: usize,
+) -> Result<CoercionPolicy, ConfigSerializerError> {
+    if data.len() <= offset {
+        Ok(CoercionPolicy::Strict)
+    } else {
+        Ok(*CoercionPolicy::try_deserialize(&data[offset..])?)
+    }
+}
+
+impl Serialize for CoercionPolicy {
+    fn serialize(&self) -> Vec<u8> {
+        match self {
+            CoercionPolicy::Strict => vec![0x00],
+            CoercionPolicy::Widen => vec![0x01],
+        }
+    }
+}
+
+impl TryDeserialize for CoercionPolicy {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.is_empty() {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        match data[0] {
+            0x00 => Ok(Box::new(CoercionPolicy::Strict)),
+            0x01 => Ok(Box::new(CoercionPolicy::Widen)),
+            _ => Err(ConfigSerializerError::UnknownEnumVariant),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_strict_test() {
+        assert_eq!(
+            CoercionPolicy::Strict.compare(&Value::U8(0x05), &Value::U8(0x06)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            CoercionPolicy::Strict.compare(&Value::U8(0x05), &Value::U16(0x0005)),
+            None
+        );
+    }
+
+    #[test]
+    fn compare_widen_test() {
+        assert_eq!(
+            CoercionPolicy::Widen.compare(&Value::U8(0x05), &Value::U16(0x0005)),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            CoercionPolicy::Widen.compare(&Value::I8(-1), &Value::U32(0x0000_0000)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            CoercionPolicy::Widen.compare(&Value::U8(21), &Value::Fixed(0x0015_8000)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            CoercionPolicy::Widen.compare(&Value::U8(0x01), &Value::Bool(true)),
+            None
+        );
+    }
+
+    #[test]
+    fn compare_numbers_test() {
+        assert_eq!(
+            CoercionPolicy::Widen.compare_numbers(&Value::U8(0x05), &Value::I16(0x0006)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            CoercionPolicy::Widen.compare_numbers(&Value::Bool(false), &Value::Bool(true)),
+            None
+        );
+    }
+
+    #[test]
+    fn add_widen_test() {
+        assert_eq!(
+            CoercionPolicy::Widen.add(&OverflowMode::Wrap, &Value::U8(0x10), &Value::U16(0x0110)),
+            Ok(Some(Value::U8(0x20)))
+        );
+        assert_eq!(
+            CoercionPolicy::Widen.add(
+                &OverflowMode::Saturate,
+                &Value::U8(0x10),
+                &Value::U16(0x0110)
+            ),
+            Ok(Some(Value::U8(0xff)))
+        );
+        assert_eq!(
+            CoercionPolicy::Widen.add(&OverflowMode::Fail, &Value::I8(-100), &Value::U16(200)),
+            Ok(Some(Value::I8(100)))
+        );
+        assert_eq!(
+            CoercionPolicy::Widen.add(
+                &OverflowMode::Wrap,
+                &Value::Fixed(0x0001_8000),
+                &Value::U8(1)
+            ),
+            Ok(Some(Value::Fixed(0x0002_8000)))
+        );
+    }
+
+    #[test]
+    fn sub_widen_test() {
+        assert_eq!(
+            CoercionPolicy::Widen.sub(
+                &OverflowMode::Saturate,
+                &Value::U8(0x10),
+                &Value::U32(0x0000_0100)
+            ),
+            Ok(Some(Value::U8(0x00)))
+        );
+        assert_eq!(
+            CoercionPolicy::Widen.sub(&OverflowMode::Fail, &Value::U8(0x10), &Value::I8(0x11)),
+            Ok(None)
+        );
+        assert_eq!(
+            CoercionPolicy::Widen.sub(
+                &OverflowMode::Clamp(Value::U8(0x05), Value::U8(0xfa)),
+                &Value::U8(0x10),
+                &Value::U16(0x0010)
+            ),
+            Ok(Some(Value::U8(0x05)))
+        );
+        assert_eq!(
+            CoercionPolicy::Widen.sub(
+                &OverflowMode::Wrap,
+                &Value::U8(0x02),
+                &Value::Fixed(0x0000_8000)
+            ),
+            Ok(Some(Value::U8(0x01)))
+        );
+    }
+
+    #[test]
+    fn arithmetic_strict_test() {
+        assert_eq!(
+            CoercionPolicy::Strict.add(&OverflowMode::Wrap, &Value::U8(0x10), &Value::U16(0x0010)),
+            Err(FilterError::WrongStateType)
+        );
+    }
+
+    #[test]
+    fn accepted_value_types_test() {
+        assert_eq!(
+            CoercionPolicy::Strict.get_accepted_value_types(ValueType::U8),
+            vec![ValueType::U8]
+        );
+        assert_eq!(
+            CoercionPolicy::Widen.get_accepted_value_types(ValueType::U8),
+            NUMERIC_VALUE_TYPES.to_vec()
+        );
+        assert_eq!(
+            CoercionPolicy::Widen.get_accepted_value_types(ValueType::Bool),
+            vec![ValueType::Bool]
+        );
+    }
+
+    #[test]
+    fn deserialize_test() {
+        assert_eq!(
+            try_deserialize_coercion_policy(&[0xab], 1),
+            Ok(CoercionPolicy::Strict)
+        );
+        assert_eq!(
+            try_deserialize_coercion_policy(&[0xab, 0x01], 1),
+            Ok(CoercionPolicy::Widen)
+        );
+        assert_eq!(
+            try_deserialize_coercion_policy(&[0xab, 0x02], 1),
+            Err(ConfigSerializerError::UnknownEnumVariant)
+        );
+    }
+}
@@ -0,0 +1,678 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::{TryFrom, TryInto};
+
+use crate::filter::{
+    CoercionPolicy, Filter, FilterError, COMPARISON_FILTER_CODE, MAX_FILTER_LEN,
+    NUMERIC_EXTRACTOR_VALUE_TYPES, NUMERIC_VALUE_TYPES,
+};
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::state_manager::StateManager;
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value, ValueType};
+
+pub const MAX_COMPARISON_SET_LEN: usize = 32;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComparisonOperand {
+    Const(Value),
+    // The extracted value
+    Value,
+    State(u32),
+}
+
+// Right operands are part of the operator
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComparisonOperator {
+    Equal(ComparisonOperand),
+    NotEqual(ComparisonOperand),
+    LessThan(ComparisonOperand),
+    LessThanOrEqual(ComparisonOperand),
+    MoreThan(ComparisonOperand),
+    MoreThanOrEqual(ComparisonOperand),
+    // Inclusive bounds
+    InRange(ComparisonOperand, ComparisonOperand),
+    InSet(Vec<ComparisonOperand>),
+}
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub struct ComparisonFilter {
+    left: ComparisonOperand,
+    operator: ComparisonOperator,
+    coercion_policy: CoercionPolicy,
+}
+
+impl ComparisonOperand {
+    fn resolve(
+        &self,
+        value: &ExtractorValue,
+        state_manager: &StateManager,
+    ) -> Result<Value, FilterError> {
+        match self {
+            ComparisonOperand::Const(value) => Ok(value.clone()),
+            ComparisonOperand::Value => {
+                Value::try_from(value).map_err(|_| FilterError::WrongValueType)
+            }
+            ComparisonOperand::State(state_index) => match state_manager.get_value(*state_index) {
+                Some(value) => Ok(value.clone()),
+                None => Err(FilterError::WrongStateType),
+            },
+        }
+    }
+}
+
+impl ComparisonOperator {
+    fn get_right_operands(&self) -> Vec<&ComparisonOperand> {
+        match self {
+            ComparisonOperator::Equal(right)
+            | ComparisonOperator::NotEqual(right)
+            | ComparisonOperator::LessThan(right)
+            | ComparisonOperator::LessThanOrEqual(right)
+            | ComparisonOperator::MoreThan(right)
+            | ComparisonOperator::MoreThanOrEqual(right) => vec![right],
+            ComparisonOperator::InRange(min, max) => vec![min, max],
+            ComparisonOperator::InSet(members) => members.iter().collect(),
+        }
+    }
+
+    fn is_ordering(&self) -> bool {
+        !matches!(
+            self,
+            ComparisonOperator::Equal(_)
+                | ComparisonOperator::NotEqual(_)
+                | ComparisonOperator::InSet(_)
+        )
+    }
+}
+
+impl ComparisonFilter {
+    pub fn new(
+        left: ComparisonOperand,
+        operator: ComparisonOperator,
+        coercion_policy: CoercionPolicy,
+    ) -> Result<Self, ConfigSerializerError> {
+        if let ComparisonOperator::InSet(members) = &operator {
+            if members.len() > MAX_COMPARISON_SET_LEN {
+                return Err(ConfigSerializerError::InvalidComparisonSet);
+            }
+        }
+
+        let filter = Self {
+            left,
+            operator,
+            coercion_policy,
+        };
+
+        if filter.serialize().len() > MAX_FILTER_LEN {
+            return Err(ConfigSerializerError::FilterTooLong);
+        }
+
+        Ok(filter)
+    }
+
+    fn get_operands(&self) -> Vec<&ComparisonOperand> {
+        let mut operands = vec![&self.left];
+        operands.append(&mut self.operator.get_right_operands());
+
+        operands
+    }
+
+    fn get_const_operand(&self) -> Option<&Value> {
+        self.get_operands()
+            .into_iter()
+            .find_map(|operand| match operand {
+                ComparisonOperand::Const(value) => Some(value),
+                _ => None,
+            })
+    }
+
+    // Operands that can not be ordered never match
+    fn is_ordered(&self, lhs: &Value, rhs: &Value, orderings: &[Ordering]) -> bool {
+        match self.coercion_policy.compare_numbers(lhs, rhs) {
+            Some(ordering) => orderings.contains(&ordering),
+            None => false,
+        }
+    }
+}
+
+impl Filter for ComparisonFilter {
+    fn filter(
+        &mut self,
+        value: &ExtractorValue,
+        state_manager: &mut StateManager,
+    ) -> Result<bool, FilterError> {
+        let left = self.left.resolve(value, state_manager)?;
+
+        let mut right = vec![];
+
+        for operand in self.operator.get_right_operands() {
+            right.push(operand.resolve(value, state_manager)?);
+        }
+
+        let result = match self.operator {
+            ComparisonOperator::Equal(_) => self.coercion_policy.equals(&left, &right[0]),
+            ComparisonOperator::NotEqual(_) => !self.coercion_policy.equals(&left, &right[0]),
+            ComparisonOperator::LessThan(_) => self.is_ordered(&left, &right[0], &[Ordering::Less]),
+            ComparisonOperator::LessThanOrEqual(_) => {
+                self.is_ordered(&left, &right[0], &[Ordering::Less, Ordering::Equal])
+            }
+            ComparisonOperator::MoreThan(_) => {
+                self.is_ordered(&left, &right[0], &[Ordering::Greater])
+            }
+            ComparisonOperator::MoreThanOrEqual(_) => {
+                self.is_ordered(&left, &right[0], &[Ordering::Greater, Ordering::Equal])
+            }
+            ComparisonOperator::InRange(_, _) => {
+                self.is_ordered(&left, &right[0], &[Ordering::Greater, Ordering::Equal])
+                    && self.is_ordered(&left, &right[1], &[Ordering::Less, Ordering::Equal])
+            }
+            ComparisonOperator::InSet(_) => right
+                .iter()
+                .any(|member| self.coercion_policy.equals(&left, member)),
+        };
+
+        Ok(result)
+    }
+
+    fn get_code(&self) -> u16 {
+        COMPARISON_FILTER_CODE
+    }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        if !self.get_operands().contains(&&ComparisonOperand::Value) {
+            return ExtractorValueType::all();
+        }
+
+        match self.get_const_operand() {
+            Some(value)
+                if self.operator.is_ordering()
+                    && !NUMERIC_VALUE_TYPES.contains(&value.get_type()) =>
+            {
+                vec![]
+            }
+            Some(value) => self
+                .coercion_policy
+                .get_accepted_extractor_value_types(ExtractorValue::from(value).get_type()),
+            None if self.operator.is_ordering() => NUMERIC_EXTRACTOR_VALUE_TYPES.to_vec(),
+            None => ExtractorValueType::all()
+                .into_iter()
+                .filter(|value_type| {
+                    *value_type != ExtractorValueType::None
+                        && *value_type != ExtractorValueType::Packet
+                })
+                .collect(),
+        }
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        let accepted_types = match self.get_const_operand() {
+            Some(value)
+                if self.operator.is_ordering()
+                    && !NUMERIC_VALUE_TYPES.contains(&value.get_type()) =>
+            {
+                vec![]
+            }
+            Some(value) => self
+                .coercion_policy
+                .get_accepted_value_types(value.get_type()),
+            None if self.operator.is_ordering() => NUMERIC_VALUE_TYPES.to_vec(),
+            None => ValueType::all(),
+        };
+
+        self.get_operands()
+            .into_iter()
+            .filter_map(|operand| match operand {
+                ComparisonOperand::State(state_index) => {
+                    Some(StateAccess::Read(*state_index, accepted_types.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Serialize for ComparisonOperand {
+    fn serialize(&self) -> Vec<u8> {
+        match self {
+            ComparisonOperand::Const(value) => {
+                let mut data = vec![0x00];
+                data.append(&mut value.serialize());
+
+                data
+            }
+            ComparisonOperand::Value => vec![0x01],
+            ComparisonOperand::State(state_index) => {
+                let state_index_bytes = state_index.to_be_bytes();
+
+                vec![
+                    0x02,
+                    state_index_bytes[0],
+                    state_index_bytes[1],
+                    state_index_bytes[2],
+                    state_index_bytes[3],
+                ]
+            }
+        }
+    }
+}
+
+impl TryDeserialize for ComparisonOperand {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.is_empty() {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        match data[0] {
+            0x00 => Ok(Box::new(ComparisonOperand::Const(*Value::try_deserialize(
+                &data[1..],
+            )?))),
+            0x01 => Ok(Box::new(ComparisonOperand::Value)),
+            0x02 => {
+                if data.len() < 5 {
+                    return Err(ConfigSerializerError::WrongSize);
+                }
+
+                let state_index = u32::from_be_bytes(data[1..=4].try_into().unwrap());
+
+                Ok(Box::new(ComparisonOperand::State(state_index)))
+            }
+            _ => Err(ConfigSerializerError::UnknownEnumVariant),
+        }
+    }
+}
+
+impl Serialize for ComparisonOperator {
+    fn serialize(&self) -> Vec<u8> {
+        let mut data = match self {
+            ComparisonOperator::Equal(_) => vec![0x00],
+            ComparisonOperator::NotEqual(_) => vec![0x01],
+            ComparisonOperator::LessThan(_) => vec![0x02],
+            ComparisonOperator::LessThanOrEqual(_) => vec![0x03],
+            ComparisonOperator::MoreThan(_) => vec![0x04],
+            ComparisonOperator::MoreThanOrEqual(_) => vec![0x05],
+            ComparisonOperator::InRange(_, _) => vec![0x06],
+            ComparisonOperator::InSet(members) => vec![0x07, members.len() as u8],
+        };
+
+        for operand in self.get_right_operands() {
+            data.append(&mut operand.serialize());
+        }
+
+        data
+    }
+}
+
+impl TryDeserialize for ComparisonOperator {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.is_empty() {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        let operand_count = match data[0] {
+            0x00..=0x05 => 1,
+            0x06 => 2,
+            0x07 => {
+                if data.len() < 2 {
+                    return Err(ConfigSerializerError::WrongSize);
+                }
+
+                if data[1] as usize > MAX_COMPARISON_SET_LEN {
+                    return Err(ConfigSerializerError::InvalidComparisonSet);
+                }
+
+                data[1] as usize
+            }
+            _ => return Err(ConfigSerializerError::UnknownEnumVariant),
+        };
+
+        let mut offset = if data[0] == 0x07 { 2 } else { 1 };
+        let mut operands = vec![];
+
+        for _ in 0..operand_count {
+            let operand = *ComparisonOperand::try_deserialize(&data[offset..])?;
+            offset += operand.serialize().len();
+
+            operands.push(operand);
+        }
+
+        let operator = match data[0] {
+            0x00 => ComparisonOperator::Equal(operands.remove(0)),
+            0x01 => ComparisonOperator::NotEqual(operands.remove(0)),
+            0x02 => ComparisonOperator::LessThan(operands.remove(0)),
+            0x03 => ComparisonOperator::LessThanOrEqual(operands.remove(0)),
+            0x04 => ComparisonOperator::MoreThan(operands.remove(0)),
+            0x05 => ComparisonOperator::MoreThanOrEqual(operands.remove(0)),
+            0x06 => {
+                let min = operands.remove(0);
+                let max = operands.remove(0);
+
+                ComparisonOperator::InRange(min, max)
+            }
+            _ => ComparisonOperator::InSet(operands),
+        };
+
+        Ok(Box::new(operator))
+    }
+}
+
+impl Serialize for ComparisonFilter {
+    fn serialize(&self) -> Vec<u8> {
+        let mut data = self.left.serialize();
+
+        data.append(&mut self.operator.serialize());
+        data.append(&mut self.coercion_policy.serialize());
+
+        data
+    }
+}
+
+impl TryDeserialize for ComparisonFilter {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        let left = *ComparisonOperand::try_deserialize(data)?;
+        let mut offset = left.serialize().len();
+
+        let operator = *ComparisonOperator::try_deserialize(&data[offset..])?;
+        offset += operator.serialize().len();
+
+        let coercion_policy = *CoercionPolicy::try_deserialize(&data[offset..])?;
+
+        Ok(Box::new(Self::new(left, operator, coercion_policy)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_not_equal_to_const_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U8(0x01));
+
+        let mut filter = ComparisonFilter::new(
+            ComparisonOperand::State(0),
+            ComparisonOperator::NotEqual(ComparisonOperand::Const(Value::U8(0x02))),
+            CoercionPolicy::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+
+        state_manager.set_value(0, Value::U8(0x02));
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn value_more_than_or_equal_to_state_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U16(0x0100));
+
+        let mut filter = ComparisonFilter::new(
+            ComparisonOperand::Value,
+            ComparisonOperator::MoreThanOrEqual(ComparisonOperand::State(0)),
+            CoercionPolicy::Widen,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::U32(0x0000_0100), &mut state_manager),
+            Ok(true)
+        );
+        assert_eq!(
+            filter.filter(&ExtractorValue::U8(0xff), &mut state_manager),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn state_less_than_state_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::I16(-5));
+        state_manager.set_value(1, Value::I16(5));
+
+        let mut filter = ComparisonFilter::new(
+            ComparisonOperand::State(0),
+            ComparisonOperator::LessThan(ComparisonOperand::State(1)),
+            CoercionPolicy::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn in_range_test() {
+        let mut state_manager = StateManager::new();
+
+        let mut filter = ComparisonFilter::new(
+            ComparisonOperand::Value,
+            ComparisonOperator::InRange(
+                ComparisonOperand::Const(Value::U8(0x10)),
+                ComparisonOperand::Const(Value::U8(0x20)),
+            ),
+            CoercionPolicy::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::U8(0x10), &mut state_manager),
+            Ok(true)
+        );
+        assert_eq!(
+            filter.filter(&ExtractorValue::U8(0x20), &mut state_manager),
+            Ok(true)
+        );
+        assert_eq!(
+            filter.filter(&ExtractorValue::U8(0x21), &mut state_manager),
+            Ok(false)
+        );
+        assert_eq!(
+            filter.filter(&ExtractorValue::U16(0x0015), &mut state_manager),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn in_set_test() {
+        let mut state_manager = StateManager::new();
+
+        let mut filter = ComparisonFilter::new(
+            ComparisonOperand::Value,
+            ComparisonOperator::InSet(vec![
+                ComparisonOperand::Const(Value::U8(0x01)),
+                ComparisonOperand::Const(Value::U8(0x03)),
+            ]),
+            CoercionPolicy::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::U8(0x03), &mut state_manager),
+            Ok(true)
+        );
+        assert_eq!(
+            filter.filter(&ExtractorValue::U8(0x02), &mut state_manager),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn wrong_state_type_test() {
+        let mut state_manager = StateManager::new();
+
+        let mut filter = ComparisonFilter::new(
+            ComparisonOperand::State(0),
+            ComparisonOperator::Equal(ComparisonOperand::Value),
+            CoercionPolicy::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::U8(0x00), &mut state_manager),
+            Err(FilterError::WrongStateType)
+        );
+    }
+
+    #[test]
+    fn wrong_value_type_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U8(0x00));
+
+        let mut filter = ComparisonFilter::new(
+            ComparisonOperand::State(0),
+            ComparisonOperator::Equal(ComparisonOperand::Value),
+            CoercionPolicy::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Err(FilterError::WrongValueType)
+        );
+    }
+
+    #[test]
+    fn state_accesses_test() {
+        let filter = ComparisonFilter::new(
+            ComparisonOperand::State(0),
+            ComparisonOperator::InRange(
+                ComparisonOperand::Const(Value::U8(0x10)),
+                ComparisonOperand::State(1),
+            ),
+            CoercionPolicy::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter.get_state_accesses(),
+            vec![
+                StateAccess::Read(0, vec![ValueType::U8]),
+                StateAccess::Read(1, vec![ValueType::U8]),
+            ]
+        );
+        assert_eq!(filter.get_accepted_value_types(), ExtractorValueType::all());
+    }
+
+    #[test]
+    fn serialize_test() {
+        let filter = ComparisonFilter::new(
+            ComparisonOperand::State(0xabab_abab),
+            ComparisonOperator::InSet(vec![
+                ComparisonOperand::Const(Value::U8(0x01)),
+                ComparisonOperand::Value,
+            ]),
+            CoercionPolicy::Widen,
+        )
+        .unwrap();
+
+        let expected_data = vec![
+            0x02, 0xab, 0xab, 0xab, 0xab, // left
+            0x07, 0x02, // operator
+            0x00, 0x00, 0x01, // first member
+            0x01, // second member
+            0x01, // coercion policy
+        ];
+
+        assert_eq!(filter.serialize(), expected_data);
+    }
+
+    #[test]
+    fn deserialize_test() {
+        let data = vec![
+            0x01, // left
+            0x06, // operator
+            0x00, 0x00, 0x10, // min
+            0x02, 0x00, 0x00, 0x00, 0x01, // max
+            0x00, // coercion policy
+        ];
+
+        let filter = Box::new(
+            ComparisonFilter::new(
+                ComparisonOperand::Value,
+                ComparisonOperator::InRange(
+                    ComparisonOperand::Const(Value::U8(0x10)),
+                    ComparisonOperand::State(1),
+                ),
+                CoercionPolicy::Strict,
+            )
+            .unwrap(),
+        );
+
+        assert_eq!(ComparisonFilter::try_deserialize(&data), Ok(filter));
+    }
+
+    #[test]
+    fn deserialize_wrong_size_test() {
+        let data = vec![0x02, 0xab, 0xab, 0xab, 0xab, 0x00, 0x01];
+
+        assert_eq!(
+            ComparisonFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::WrongSize)
+        );
+    }
+
+    #[test]
+    fn deserialize_too_many_set_members_test() {
+        let mut data = vec![0x01, 0x07, MAX_COMPARISON_SET_LEN as u8 + 1];
+        data.resize(MAX_COMPARISON_SET_LEN + 4, 0x01);
+        data.push(0x00);
+
+        assert_eq!(
+            ComparisonFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidComparisonSet)
+        );
+    }
+
+    #[test]
+    fn new_too_many_set_members_test() {
+        let members = vec![ComparisonOperand::Value; MAX_COMPARISON_SET_LEN + 1];
+
+        assert_eq!(
+            ComparisonFilter::new(
+                ComparisonOperand::Value,
+                ComparisonOperator::InSet(members),
+                CoercionPolicy::Strict,
+            ),
+            Err(ConfigSerializerError::InvalidComparisonSet)
+        );
+    }
+
+    #[test]
+    fn new_too_long_test() {
+        let member = ComparisonOperand::Const(Value::try_from_bytes(&[0x61; 8]).unwrap());
+
+        assert_eq!(
+            ComparisonFilter::new(
+                ComparisonOperand::Value,
+                ComparisonOperator::InSet(vec![member; MAX_COMPARISON_SET_LEN]),
+                CoercionPolicy::Strict,
+            ),
+            Err(ConfigSerializerError::FilterTooLong)
+        );
+    }
+
+    #[test]
+    fn deserialize_unknown_operator_test() {
+        let data = vec![0x01, 0x08, 0x01, 0x00];
+
+        assert_eq!(
+            ComparisonFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::UnknownEnumVariant)
+        );
+    }
+}
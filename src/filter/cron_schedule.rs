@@ -0,0 +1,342 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use chrono::{Duration, Timelike};
+
+use crate::cron::CronExpression;
+use crate::filter::{
+    serialize_timestamp, try_deserialize_timestamp, Filter, FilterError, CRON_SCHEDULE_FILTER_CODE,
+};
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::state_manager::StateManager;
+use crate::validator::StateAccess;
+use crate::{serialize_integer_to_vec, try_deserialize_integer_from_vec};
+use crate::{ExtractorValue, ExtractorValueType};
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub struct CronScheduleFilter {
+    expression: CronExpression,
+    // Seconds after an occurrence during which it still fires if it was not evaluated in time
+    catch_up: u32,
+    // Milliseconds since the Unix epoch
+    last_occurrence: Option<i64>,
+}
+
+impl CronScheduleFilter {
+    pub fn new(expression: CronExpression, catch_up: u32) -> Self {
+        Self {
+            expression,
+            catch_up,
+            last_occurrence: None,
+        }
+    }
+}
+
+impl Filter for CronScheduleFilter {
+    fn filter(
+        &mut self,
+        _value: &ExtractorValue,
+        state_manager: &mut StateManager,
+    ) -> Result<bool, FilterError> {
+        let date_time = *state_manager.get_date_time();
+
+        // Includes an occurrence in the current second
+        let bound = match date_time.with_nanosecond(0) {
+            Some(date_time) => date_time + Duration::seconds(1),
+            None => return Ok(false),
+        };
+
+        let occurrence = match state_manager.get_time_zone() {
+            Some(time_zone) => self.expression.prev_before_in_time_zone(&bound, time_zone),
+            None => self.expression.prev_before(&bound),
+        };
+
+        let occurrence = match occurrence {
+            Some(occurrence) => occurrence,
+            None => return Ok(false),
+        };
+
+        if let Some(last_occurrence) = self.last_occurrence {
+            if occurrence.timestamp_millis() <= last_occurrence {
+                return Ok(false);
+            }
+        }
+
+        // Several missed occurrences only fire once, for the latest one
+        self.last_occurrence = Some(occurrence.timestamp_millis());
+
+        Ok(date_time < occurrence + Duration::seconds(self.catch_up as i64 + 1))
+    }
+
+    fn get_code(&self) -> u16 {
+        CRON_SCHEDULE_FILTER_CODE
+    }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![]
+    }
+}
+
+impl Serialize for CronScheduleFilter {
+    fn serialize(&self) -> Vec<u8> {
+        let mut data = vec![];
+
+        serialize_integer_to_vec!(data, self.catch_up, u32);
+        data.append(&mut self.expression.serialize());
+        serialize_timestamp(&mut data, self.last_occurrence);
+
+        data
+    }
+}
+
+impl TryDeserialize for CronScheduleFilter {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        let mut offset = 0;
+
+        let catch_up = try_deserialize_integer_from_vec!(data, offset, u32);
+        let expression = *CronExpression::try_deserialize(&data[offset..])?;
+
+        if let Err(err) = expression.validate() {
+            return Err(ConfigSerializerError::InvalidCronExpression(err));
+        }
+
+        offset += expression.serialize().len();
+
+        let last_occurrence = try_deserialize_timestamp(&data[offset..])?;
+
+        Ok(Box::new(Self {
+            expression,
+            catch_up,
+            last_occurrence,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::DateTime;
+    use core::str::FromStr;
+
+    use crate::cron::CronExpressionError;
+    use crate::time_zone::TimeZone;
+
+    fn filter_at(
+        filter: &mut CronScheduleFilter,
+        state_manager: &mut StateManager,
+        date_time: &str,
+    ) -> Result<bool, FilterError> {
+        state_manager.set_date_time(DateTime::from_str(date_time).unwrap());
+        filter.filter(&ExtractorValue::None, state_manager)
+    }
+
+    #[test]
+    fn fires_once_test() {
+        let mut state_manager = StateManager::new();
+
+        let mut filter =
+            CronScheduleFilter::new(CronExpression::from_str("0 30 6 * * *").unwrap(), 0);
+
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:29:59Z"),
+            Ok(false)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00Z"),
+            Ok(true)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00.5Z"),
+            Ok(false)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-04T06:30:00.2Z"),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn missed_without_catch_up_test() {
+        let mut state_manager = StateManager::new();
+
+        let mut filter =
+            CronScheduleFilter::new(CronExpression::from_str("0 30 6 * * *").unwrap(), 0);
+
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:29:59Z"),
+            Ok(false)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:01Z"),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn catch_up_test() {
+        let mut state_manager = StateManager::new();
+
+        let mut filter =
+            CronScheduleFilter::new(CronExpression::from_str("0 */5 * * * *").unwrap(), 60);
+
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:29:59Z"),
+            Ok(false)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:45Z"),
+            Ok(true)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:50Z"),
+            Ok(false)
+        );
+        // Both 06:35 and 06:40 were missed, only the latest fires
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:40:30Z"),
+            Ok(true)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:41:00Z"),
+            Ok(false)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:46:01Z"),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn time_zone_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_time_zone(Some(TimeZone {
+            offset: 7200,
+            dst: None,
+        }));
+
+        let mut filter =
+            CronScheduleFilter::new(CronExpression::from_str("0 30 6 * * *").unwrap(), 0);
+
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00Z"),
+            Ok(false)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-04T04:30:00Z"),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn serialize_test() {
+        let mut filter =
+            CronScheduleFilter::new(CronExpression::from_str("0 30 6 * * *").unwrap(), 300);
+        filter.last_occurrence = Some(0x0000_017e_1d9f_2e40);
+
+        let expected_data = vec![
+            0x00, 0x00, 0x01, 0x2c, // catch up
+            3, 0, 1, 0, // second
+            3, 0, 1, 30, // minute
+            3, 0, 1, 6, // hour
+            1, 3, // day (month)
+            1, 3, // month
+            1, 3, // day (week)
+            1, 3, // year
+            0x01, 0x00, 0x00, 0x01, 0x7e, 0x1d, 0x9f, 0x2e, 0x40, // last occurrence
+        ];
+
+        assert_eq!(filter.serialize(), expected_data);
+    }
+
+    #[test]
+    fn deserialize_test() {
+        let data = vec![
+            0x00, 0x00, 0x01, 0x2c, // catch up
+            3, 0, 1, 0, // second
+            3, 0, 1, 30, // minute
+            3, 0, 1, 6, // hour
+            1, 3, // day (month)
+            1, 3, // month
+            1, 3, // day (week)
+            1, 3,    // year
+            0x00, // last occurrence
+        ];
+
+        assert_eq!(
+            CronScheduleFilter::try_deserialize(&data),
+            Ok(Box::new(CronScheduleFilter::new(
+                CronExpression::from_str("0 30 6 * * *").unwrap(),
+                300
+            )))
+        );
+    }
+
+    #[test]
+    fn deserialize_wrong_size_test() {
+        let data = vec![
+            0x00, 0x00, 0x01, 0x2c, // catch up
+            3, 0, 1, 0, // second
+            3, 0, 1, 30, // minute
+            3, 0, 1, 6, // hour
+            1, 3, // day (month)
+            1, 3, // month
+            1, 3, // day (week)
+            1, 3, // year
+        ];
+
+        assert_eq!(
+            CronScheduleFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::WrongSize)
+        );
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let mut state_manager = StateManager::new();
+
+        let mut filter =
+            CronScheduleFilter::new(CronExpression::from_str("0 30 6 * * *").unwrap(), 60);
+
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:10Z"),
+            Ok(true)
+        );
+
+        let mut filter = CronScheduleFilter::try_deserialize(&filter.serialize()).unwrap();
+
+        // Still in the catch up window, but the occurrence already fired
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:20Z"),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_expression_test() {
+        let data = vec![
+            0x00, 0x00, 0x01, 0x2c, // catch up
+            3, 0, 1, 0, // second
+            3, 0, 1, 30, // minute
+            3, 0, 1, 24, // hour
+            1, 3, // day (month)
+            1, 3, // month
+            1, 3, // day (week)
+            1, 3, // year
+        ];
+
+        assert_eq!(
+            CronScheduleFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidCronExpression(
+                CronExpressionError::InvalidHour
+            ))
+        );
+    }
+}
@@ -0,0 +1,474 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::filter::{Filter, FilterError, CYCLE_STATE_FILTER_CODE, MAX_FILTER_LEN};
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::state_manager::StateManager;
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value};
+
+pub const MAX_CYCLE_VALUES_LEN: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CycleMode {
+    // Goes back to the first value after the last one
+    Wrap,
+    // Reverses direction at the first and the last value
+    PingPong,
+}
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub struct CycleStateFilter {
+    state_index: u32,
+    values: Vec<Value>,
+    mode: CycleMode,
+    // Serialized so that ping-pong cycles keep their direction across restarts
+    is_reversed: bool,
+}
+
+impl CycleStateFilter {
+    pub fn new(
+        state_index: u32,
+        values: Vec<Value>,
+        mode: CycleMode,
+    ) -> Result<Self, ConfigSerializerError> {
+        if values.len() > MAX_CYCLE_VALUES_LEN {
+            return Err(ConfigSerializerError::InvalidCycleValues);
+        }
+
+        // Values replace each other in the same state
+        match values.first() {
+            Some(first_value)
+                if values
+                    .iter()
+                    .all(|value| value.get_type() == first_value.get_type()) => {}
+            _ => return Err(ConfigSerializerError::InvalidCycleValues),
+        }
+
+        let filter = Self {
+            state_index,
+            values,
+            mode,
+            is_reversed: false,
+        };
+
+        if filter.serialize().len() > MAX_FILTER_LEN {
+            return Err(ConfigSerializerError::FilterTooLong);
+        }
+
+        Ok(filter)
+    }
+
+    fn get_next_position(&mut self, position: usize) -> usize {
+        let last_position = self.values.len() - 1;
+
+        match self.mode {
+            CycleMode::Wrap if position == last_position => 0,
+            CycleMode::Wrap => position + 1,
+            CycleMode::PingPong => {
+                if last_position == 0 {
+                    return 0;
+                }
+
+                if position == last_position {
+                    self.is_reversed = true;
+                } else if position == 0 {
+                    self.is_reversed = false;
+                }
+
+                if self.is_reversed {
+                    position - 1
+                } else {
+                    position + 1
+                }
+            }
+        }
+    }
+}
+
+impl Filter for CycleStateFilter {
+    fn filter(
+        &mut self,
+        _value: &ExtractorValue,
+        state_manager: &mut StateManager,
+    ) -> Result<bool, FilterError> {
+        let value_type = match self.values.first() {
+            Some(value) => value.get_type(),
+            None => return Err(FilterError::WrongStateType),
+        };
+
+        let current_state = match state_manager.get_value(self.state_index) {
+            Some(current_state) if current_state.get_type() == value_type => current_state,
+            _ => return Err(FilterError::WrongStateType),
+        };
+
+        // A state that is not in the list starts the cycle from the first value
+        let next_position = match self.values.iter().position(|value| value == current_state) {
+            Some(position) => self.get_next_position(position),
+            None => {
+                self.is_reversed = false;
+                0
+            }
+        };
+
+        state_manager.set_value(self.state_index, self.values[next_position].clone());
+
+        Ok(true)
+    }
+
+    fn get_code(&self) -> u16 {
+        CYCLE_STATE_FILTER_CODE
+    }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        let value_type = match self.values.first() {
+            Some(value) => value.get_type(),
+            None => return vec![],
+        };
+
+        vec![
+            StateAccess::Read(self.state_index, vec![value_type]),
+            StateAccess::Write(self.state_index, Some(value_type)),
+        ]
+    }
+}
+
+impl Serialize for CycleMode {
+    fn serialize(&self) -> Vec<u8> {
+        match self {
+            CycleMode::Wrap => vec![0x00],
+            CycleMode::PingPong => vec![0x01],
+        }
+    }
+}
+
+impl TryDeserialize for CycleMode {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.is_empty() {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        match data[0] {
+            0x00 => Ok(Box::new(CycleMode::Wrap)),
+            0x01 => Ok(Box::new(CycleMode::PingPong)),
+            _ => Err(ConfigSerializerError::UnknownEnumVariant),
+        }
+    }
+}
+
+impl Serialize for CycleStateFilter {
+    fn serialize(&self) -> Vec<u8> {
+        let state_index_bytes = self.state_index.to_be_bytes();
+
+        let mut data = vec![
+            state_index_bytes[0],
+            state_index_bytes[1],
+            state_index_bytes[2],
+            state_index_bytes[3],
+        ];
+
+        data.append(&mut self.mode.serialize());
+        data.push(self.values.len() as u8);
+
+        for value in self.values.iter() {
+            data.append(&mut value.serialize());
+        }
+
+        data.push(self.is_reversed as u8);
+
+        data
+    }
+}
+
+impl TryDeserialize for CycleStateFilter {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.len() < 6 {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        let state_index = u32::from_be_bytes(data[0..=3].try_into().unwrap());
+        let mode = *CycleMode::try_deserialize(&data[4..])?;
+        let value_count = data[5] as usize;
+
+        if value_count > MAX_CYCLE_VALUES_LEN {
+            return Err(ConfigSerializerError::InvalidCycleValues);
+        }
+
+        let mut offset = 6;
+        let mut values = vec![];
+
+        for _ in 0..value_count {
+            let value = *Value::try_deserialize(&data[offset..])?;
+            offset += value.serialize().len();
+
+            values.push(value);
+        }
+
+        let mut filter = Self::new(state_index, values, mode)?;
+
+        filter.is_reversed = match data.get(offset) {
+            Some(0x00) => false,
+            Some(0x01) => true,
+            Some(_) => return Err(ConfigSerializerError::UnknownEnumVariant),
+            None => return Err(ConfigSerializerError::WrongSize),
+        };
+
+        Ok(Box::new(filter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ValueType;
+
+    fn get_scenes() -> Vec<Value> {
+        vec![
+            Value::U8(0x00),
+            Value::U8(0x4d),
+            Value::U8(0xb3),
+            Value::U8(0xff),
+        ]
+    }
+
+    #[test]
+    fn wrap_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U8(0x00));
+
+        let mut filter = CycleStateFilter::new(0, get_scenes(), CycleMode::Wrap).unwrap();
+
+        for expected_value in [0x4d, 0xb3, 0xff, 0x00, 0x4d].iter() {
+            assert_eq!(
+                filter.filter(&ExtractorValue::None, &mut state_manager),
+                Ok(true)
+            );
+            assert_eq!(
+                *state_manager.get_value(0).unwrap(),
+                Value::U8(*expected_value)
+            );
+        }
+    }
+
+    #[test]
+    fn ping_pong_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U8(0x00));
+
+        let mut filter = CycleStateFilter::new(0, get_scenes(), CycleMode::PingPong).unwrap();
+
+        for expected_value in [0x4d, 0xb3, 0xff, 0xb3, 0x4d, 0x00, 0x4d].iter() {
+            assert_eq!(
+                filter.filter(&ExtractorValue::None, &mut state_manager),
+                Ok(true)
+            );
+            assert_eq!(
+                *state_manager.get_value(0).unwrap(),
+                Value::U8(*expected_value)
+            );
+        }
+    }
+
+    #[test]
+    fn single_value_ping_pong_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::Bool(true));
+
+        let mut filter =
+            CycleStateFilter::new(0, vec![Value::Bool(true)], CycleMode::PingPong).unwrap();
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+        assert_eq!(*state_manager.get_value(0).unwrap(), Value::Bool(true));
+    }
+
+    #[test]
+    fn value_not_in_cycle_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U8(0x80));
+
+        let mut filter = CycleStateFilter::new(0, get_scenes(), CycleMode::Wrap).unwrap();
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+        assert_eq!(*state_manager.get_value(0).unwrap(), Value::U8(0x00));
+    }
+
+    #[test]
+    fn wrong_state_type_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U16(0x0000));
+
+        let mut filter = CycleStateFilter::new(0, get_scenes(), CycleMode::Wrap).unwrap();
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Err(FilterError::WrongStateType)
+        );
+    }
+
+    #[test]
+    fn state_accesses_test() {
+        let filter = CycleStateFilter::new(0, get_scenes(), CycleMode::Wrap).unwrap();
+
+        assert_eq!(
+            filter.get_state_accesses(),
+            vec![
+                StateAccess::Read(0, vec![ValueType::U8]),
+                StateAccess::Write(0, Some(ValueType::U8)),
+            ]
+        );
+    }
+
+    #[test]
+    fn new_invalid_values_test() {
+        assert_eq!(
+            CycleStateFilter::new(0, vec![], CycleMode::Wrap),
+            Err(ConfigSerializerError::InvalidCycleValues)
+        );
+        assert_eq!(
+            CycleStateFilter::new(0, vec![Value::U8(0x00); 33], CycleMode::Wrap),
+            Err(ConfigSerializerError::InvalidCycleValues)
+        );
+        assert_eq!(
+            CycleStateFilter::new(
+                0,
+                vec![Value::try_from_bytes(&[0x61; 8]).unwrap(); 32],
+                CycleMode::Wrap
+            ),
+            Err(ConfigSerializerError::FilterTooLong)
+        );
+    }
+
+    #[test]
+    fn serialize_test() {
+        let filter = CycleStateFilter::new(
+            0xabab_abab,
+            vec![Value::U8(0x00), Value::U8(0xff)],
+            CycleMode::PingPong,
+        )
+        .unwrap();
+
+        let expected_data = vec![
+            0xab, 0xab, 0xab, 0xab, // state index
+            0x01, // mode
+            0x02, // value count
+            0x00, 0x00, // first value
+            0x00, 0xff, // second value
+            0x00, // is reversed
+        ];
+
+        assert_eq!(filter.serialize(), expected_data);
+    }
+
+    #[test]
+    fn deserialize_test() {
+        let data = vec![
+            0xab, 0xab, 0xab, 0xab, // state index
+            0x00, // mode
+            0x02, // value count
+            0x00, 0x00, // first value
+            0x00, 0xff, // second value
+            0x00, // is reversed
+        ];
+
+        let filter = Box::new(
+            CycleStateFilter::new(
+                0xabab_abab,
+                vec![Value::U8(0x00), Value::U8(0xff)],
+                CycleMode::Wrap,
+            )
+            .unwrap(),
+        );
+
+        assert_eq!(CycleStateFilter::try_deserialize(&data), Ok(filter));
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U8(0xb3));
+
+        let mut filter = CycleStateFilter::new(0, get_scenes(), CycleMode::PingPong).unwrap();
+
+        for _ in 0..2 {
+            filter
+                .filter(&ExtractorValue::None, &mut state_manager)
+                .unwrap();
+        }
+
+        assert_eq!(*state_manager.get_value(0).unwrap(), Value::U8(0xb3));
+
+        let mut filter = CycleStateFilter::try_deserialize(&filter.serialize()).unwrap();
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+        assert_eq!(*state_manager.get_value(0).unwrap(), Value::U8(0x4d));
+    }
+
+    #[test]
+    fn deserialize_wrong_size_test() {
+        let data = vec![0xab, 0xab, 0xab, 0xab, 0x00, 0x02, 0x00, 0x00, 0x00];
+
+        assert_eq!(
+            CycleStateFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::WrongSize)
+        );
+
+        let data = vec![0xab, 0xab, 0xab, 0xab, 0x00, 0x02, 0x00, 0x00, 0x00, 0xff];
+
+        assert_eq!(
+            CycleStateFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::WrongSize)
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_values_test() {
+        let data = vec![0xab, 0xab, 0xab, 0xab, 0x00, 0x00];
+
+        assert_eq!(
+            CycleStateFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidCycleValues)
+        );
+
+        let data = vec![
+            0xab, 0xab, 0xab, 0xab, 0x00, 0x02, 0x00, 0x00, 0x01, 0x00, 0xff,
+        ];
+
+        assert_eq!(
+            CycleStateFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidCycleValues)
+        );
+    }
+
+    #[test]
+    fn deserialize_too_many_values_test() {
+        let mut data = vec![0xab, 0xab, 0xab, 0xab, 0x00, MAX_CYCLE_VALUES_LEN as u8 + 1];
+
+        for _ in 0..=MAX_CYCLE_VALUES_LEN {
+            data.append(&mut Value::U8(0x00).serialize());
+        }
+
+        assert_eq!(
+            CycleStateFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidCycleValues)
+        );
+    }
+}
@@ -0,0 +1,197 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::filter::{Filter, FilterError, DATE_IN_CALENDAR_FILTER_CODE};
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::state_manager::StateManager;
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType};
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub struct DateInCalendarFilter {
+    calendar_index: u32,
+    // Matches dates outside of the calendar if false
+    included: bool,
+}
+
+impl DateInCalendarFilter {
+    pub fn new(calendar_index: u32, included: bool) -> Self {
+        Self {
+            calendar_index,
+            included,
+        }
+    }
+
+    pub fn get_calendar_index(&self) -> u32 {
+        self.calendar_index
+    }
+}
+
+impl Filter for DateInCalendarFilter {
+    fn filter(
+        &mut self,
+        _value: &ExtractorValue,
+        state_manager: &mut StateManager,
+    ) -> Result<bool, FilterError> {
+        let date = state_manager.get_local_date_time().date();
+
+        match state_manager.get_calendar(self.calendar_index) {
+            Some(calendar) => Ok(calendar.contains(&date) == self.included),
+            None => Err(FilterError::UnknownCalendar),
+        }
+    }
+
+    fn get_code(&self) -> u16 {
+        DATE_IN_CALENDAR_FILTER_CODE
+    }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![]
+    }
+}
+
+impl Serialize for DateInCalendarFilter {
+    fn serialize(&self) -> Vec<u8> {
+        let calendar_index = self.calendar_index.to_be_bytes();
+
+        vec![
+            calendar_index[0],
+            calendar_index[1],
+            calendar_index[2],
+            calendar_index[3],
+            if self.included { 0x01 } else { 0x00 },
+        ]
+    }
+}
+
+impl TryDeserialize for DateInCalendarFilter {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.len() < 5 {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        let calendar_index = u32::from_be_bytes(data[0..=3].try_into().unwrap());
+        let included = data[4] != 0x00;
+
+        Ok(Box::new(Self {
+            calendar_index,
+            included,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::{DateTime, NaiveDate};
+    use core::str::FromStr;
+
+    use crate::calendar::{Calendar, CalendarEntry};
+    use crate::time_zone::TimeZone;
+
+    fn state_manager(date_time: &str) -> StateManager {
+        let mut state_manager = StateManager::new();
+        state_manager.set_date_time(DateTime::from_str(date_time).unwrap());
+        state_manager.set_calendar(
+            0,
+            Calendar {
+                entries: vec![CalendarEntry::Range(
+                    NaiveDate::from_ymd(2022, 12, 26),
+                    NaiveDate::from_ymd(2022, 12, 26),
+                )],
+            },
+        );
+
+        state_manager
+    }
+
+    #[test]
+    fn included_test() {
+        let mut state_manager = state_manager("2022-12-26T12:00:00Z");
+
+        assert_eq!(
+            DateInCalendarFilter::new(0, true).filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+        assert_eq!(
+            DateInCalendarFilter::new(0, false).filter(&ExtractorValue::None, &mut state_manager),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn not_included_test() {
+        let mut state_manager = state_manager("2022-12-27T12:00:00Z");
+
+        assert_eq!(
+            DateInCalendarFilter::new(0, true).filter(&ExtractorValue::None, &mut state_manager),
+            Ok(false)
+        );
+        assert_eq!(
+            DateInCalendarFilter::new(0, false).filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn local_date_test() {
+        let mut state_manager = state_manager("2022-12-25T23:30:00Z");
+        state_manager.set_time_zone(Some(TimeZone {
+            offset: 3600,
+            dst: None,
+        }));
+
+        assert_eq!(
+            DateInCalendarFilter::new(0, true).filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn unknown_calendar_test() {
+        let mut state_manager = state_manager("2022-12-26T12:00:00Z");
+
+        assert_eq!(
+            DateInCalendarFilter::new(1, true).filter(&ExtractorValue::None, &mut state_manager),
+            Err(FilterError::UnknownCalendar)
+        );
+    }
+
+    #[test]
+    fn serialize_test() {
+        let filter = DateInCalendarFilter::new(0xabab_abab, true);
+
+        let expected_data = vec![0xab, 0xab, 0xab, 0xab, 0x01];
+
+        assert_eq!(filter.serialize(), expected_data);
+    }
+
+    #[test]
+    fn deserialize_test() {
+        let data = vec![0xab, 0xab, 0xab, 0xab, 0x00];
+
+        let filter = Box::new(DateInCalendarFilter::new(0xabab_abab, false));
+
+        assert_eq!(DateInCalendarFilter::try_deserialize(&data), Ok(filter));
+    }
+
+    #[test]
+    fn deserialize_wrong_size_test() {
+        let data = vec![0xab, 0xab, 0xab, 0xab];
+
+        assert_eq!(
+            DateInCalendarFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::WrongSize)
+        );
+    }
+}
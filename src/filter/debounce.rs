@@ -0,0 +1,222 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::filter::{Filter, FilterError, DEBOUNCE_FILTER_CODE};
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::state_manager::StateManager;
+use crate::validator::StateAccess;
+use crate::{serialize_integer_to_vec, try_deserialize_integer_from_vec};
+use crate::{ExtractorValue, ExtractorValueType};
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub struct DebounceFilter {
+    // Milliseconds without an event before the next one passes
+    interval: u32,
+    // Milliseconds since the Unix epoch
+    last_event: Option<i64>,
+}
+
+impl DebounceFilter {
+    pub fn new(interval: u32) -> Self {
+        Self {
+            interval,
+            last_event: None,
+        }
+    }
+}
+
+// Intervals restart if the clock was set back
+pub(crate) fn has_elapsed(since: Option<i64>, now: i64, interval: u32) -> bool {
+    match since {
+        Some(since) => now < since || now - since >= interval as i64,
+        None => true,
+    }
+}
+
+pub(crate) fn serialize_timestamp(data: &mut Vec<u8>, timestamp: Option<i64>) {
+    match timestamp {
+        Some(timestamp) => {
+            data.push(0x01);
+            serialize_integer_to_vec!(data, timestamp, i64);
+        }
+        None => data.push(0x00),
+    }
+}
+
+pub(crate) fn try_deserialize_timestamp(data: &[u8]) -> Result<Option<i64>, ConfigSerializerError> {
+    match data.first() {
+        Some(0x00) => Ok(None),
+        Some(0x01) if data.len() >= 9 => {
+            Ok(Some(i64::from_be_bytes(data[1..=8].try_into().unwrap())))
+        }
+        Some(0x01) | None => Err(ConfigSerializerError::WrongSize),
+        Some(_) => Err(ConfigSerializerError::UnknownEnumVariant),
+    }
+}
+
+impl Filter for DebounceFilter {
+    fn filter(
+        &mut self,
+        _value: &ExtractorValue,
+        state_manager: &mut StateManager,
+    ) -> Result<bool, FilterError> {
+        let now = state_manager.get_date_time().timestamp_millis();
+        let is_passing = has_elapsed(self.last_event, now, self.interval);
+
+        // Events that do not pass extend the quiet period as well
+        self.last_event = Some(now);
+
+        Ok(is_passing)
+    }
+
+    fn get_code(&self) -> u16 {
+        DEBOUNCE_FILTER_CODE
+    }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![]
+    }
+}
+
+impl Serialize for DebounceFilter {
+    fn serialize(&self) -> Vec<u8> {
+        let mut data = vec![];
+
+        serialize_integer_to_vec!(data, self.interval, u32);
+        serialize_timestamp(&mut data, self.last_event);
+
+        data
+    }
+}
+
+impl TryDeserialize for DebounceFilter {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        let mut offset = 0;
+
+        let interval = try_deserialize_integer_from_vec!(data, offset, u32);
+        let last_event = try_deserialize_timestamp(&data[offset..])?;
+
+        Ok(Box::new(Self {
+            interval,
+            last_event,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::DateTime;
+    use core::str::FromStr;
+
+    fn filter_at(
+        filter: &mut DebounceFilter,
+        state_manager: &mut StateManager,
+        date_time: &str,
+    ) -> Result<bool, FilterError> {
+        state_manager.set_date_time(DateTime::from_str(date_time).unwrap());
+        filter.filter(&ExtractorValue::None, state_manager)
+    }
+
+    #[test]
+    fn debounce_test() {
+        let mut state_manager = StateManager::new();
+
+        let mut filter = DebounceFilter::new(500);
+
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00Z"),
+            Ok(true)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00.3Z"),
+            Ok(false)
+        );
+        // Still bouncing, the previous event was less than 500 ms ago
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00.6Z"),
+            Ok(false)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:01.1Z"),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn clock_set_back_test() {
+        let mut state_manager = StateManager::new();
+
+        let mut filter = DebounceFilter::new(500);
+
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00Z"),
+            Ok(true)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:00:00Z"),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let mut state_manager = StateManager::new();
+
+        let mut filter = DebounceFilter::new(500);
+
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00Z"),
+            Ok(true)
+        );
+
+        let mut filter = DebounceFilter::try_deserialize(&filter.serialize()).unwrap();
+
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00.3Z"),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn serialize_test() {
+        let mut filter = DebounceFilter::new(0x0000_01f4);
+        filter.last_event = Some(0x0000_017e_2000_0000);
+
+        let expected_data = vec![
+            0x00, 0x00, 0x01, 0xf4, // interval
+            0x01, 0x00, 0x00, 0x01, 0x7e, 0x20, 0x00, 0x00, 0x00, // last event
+        ];
+
+        assert_eq!(filter.serialize(), expected_data);
+    }
+
+    #[test]
+    fn deserialize_test() {
+        let data = vec![0x00, 0x00, 0x01, 0xf4, 0x00];
+
+        let filter = Box::new(DebounceFilter::new(0x0000_01f4));
+
+        assert_eq!(DebounceFilter::try_deserialize(&data), Ok(filter));
+    }
+
+    #[test]
+    fn deserialize_wrong_size_test() {
+        let data = vec![0x00, 0x00, 0x01, 0xf4, 0x01, 0x00, 0x00];
+
+        assert_eq!(
+            DebounceFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::WrongSize)
+        );
+    }
+}
@@ -5,23 +5,36 @@ use alloc::vec;
 use alloc::vec::Vec;
 use core::convert::TryInto;
 
-use crate::filter::{Filter, FilterError, DECREMENT_STATE_BY_CONST_FILTER_CODE};
+use crate::filter::{
+    try_deserialize_coercion_policy, CoercionPolicy, Filter, FilterError, OverflowMode,
+    DECREMENT_STATE_BY_CONST_FILTER_CODE,
+};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::{ExtractorValue, Value};
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
 pub struct DecrementStateByConstFilter {
     state_index: u32,
     decrement_value: Value,
+    overflow_mode: OverflowMode,
+    coercion_policy: CoercionPolicy,
 }
 
 impl DecrementStateByConstFilter {
-    pub fn new(state_index: u32, decrement_value: Value) -> Self {
+    pub fn new(
+        state_index: u32,
+        decrement_value: Value,
+        overflow_mode: OverflowMode,
+        coercion_policy: CoercionPolicy,
+    ) -> Self {
         Self {
             state_index,
             decrement_value,
+            overflow_mode,
+            coercion_policy,
         }
     }
 }
@@ -32,30 +45,51 @@ impl Filter for DecrementStateByConstFilter {
         _value: &ExtractorValue,
         state_manager: &mut StateManager,
     ) -> Result<bool, FilterError> {
-        let new_value = match (
-            state_manager.get_value(self.state_index),
-            &self.decrement_value,
-        ) {
-            (Some(Value::U8(current_state)), Value::U8(decrement_value)) => {
-                Value::U8(current_state.wrapping_sub(*decrement_value))
-            }
-            (Some(Value::U16(current_state)), Value::U16(decrement_value)) => {
-                Value::U16(current_state.wrapping_sub(*decrement_value))
-            }
-            (Some(Value::U32(current_state)), Value::U32(decrement_value)) => {
-                Value::U32(current_state.wrapping_sub(*decrement_value))
-            }
-            _ => return Err(FilterError::WrongStateType),
+        let current_state = match state_manager.get_value(self.state_index) {
+            Some(current_state) => current_state,
+            None => return Err(FilterError::WrongStateType),
         };
 
-        state_manager.set_value(self.state_index, new_value);
+        match self
+            .coercion_policy
+            .sub(&self.overflow_mode, current_state, &self.decrement_value)?
+        {
+            Some(new_value) => {
+                state_manager.set_value(self.state_index, new_value);
 
-        Ok(true)
+                Ok(true)
+            }
+            None => Ok(false),
+        }
     }
 
     fn get_code(&self) -> u16 {
         DECREMENT_STATE_BY_CONST_FILTER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        let accepted_types = match self.decrement_value {
+            Value::U8(_)
+            | Value::U16(_)
+            | Value::U32(_)
+            | Value::I8(_)
+            | Value::I16(_)
+            | Value::I32(_)
+            | Value::Fixed(_) => self
+                .coercion_policy
+                .get_accepted_value_types(self.decrement_value.get_type()),
+            _ => vec![],
+        };
+
+        vec![
+            StateAccess::Read(self.state_index, accepted_types),
+            StateAccess::Write(self.state_index, None),
+        ]
+    }
 }
 
 impl Serialize for DecrementStateByConstFilter {
@@ -73,6 +107,9 @@ impl Serialize for DecrementStateByConstFilter {
 
         data.append(&mut decrement_value_bytes);
 
+        data.append(&mut self.overflow_mode.serialize());
+        data.append(&mut self.coercion_policy.serialize());
+
         return data;
     }
 }
@@ -85,10 +122,31 @@ impl TryDeserialize for DecrementStateByConstFilter {
 
         let state_index = u32::from_be_bytes(data[0..=3].try_into().unwrap());
         let decrement_value = *Value::try_deserialize(&data[4..])?;
+        let offset = 4 + decrement_value.serialize().len();
+
+        // Filters serialized without an overflow mode wrap around
+        let overflow_mode = if data.len() > offset {
+            *OverflowMode::try_deserialize(&data[offset..])?
+        } else {
+            OverflowMode::Wrap
+        };
+
+        let coercion_policy =
+            try_deserialize_coercion_policy(data, offset + overflow_mode.serialize().len())?;
+
+        // Clamp bounds have the type of the state, which only has to match the decrement value when
+        // values are not coerced
+        if coercion_policy == CoercionPolicy::Strict
+            && !overflow_mode.is_valid_for(&decrement_value)
+        {
+            return Err(ConfigSerializerError::InvalidOverflowMode);
+        }
 
         Ok(Box::new(Self {
             state_index,
             decrement_value,
+            overflow_mode,
+            coercion_policy,
         }))
     }
 }
@@ -102,7 +160,12 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0x0000_0005));
 
-        let mut filter = DecrementStateByConstFilter::new(0, Value::U32(0x0000_0005));
+        let mut filter = DecrementStateByConstFilter::new(
+            0,
+            Value::U32(0x0000_0005),
+            OverflowMode::Wrap,
+            CoercionPolicy::Strict,
+        );
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -119,7 +182,12 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0x0000_0000));
 
-        let mut filter = DecrementStateByConstFilter::new(0, Value::U32(0x0000_0001));
+        let mut filter = DecrementStateByConstFilter::new(
+            0,
+            Value::U32(0x0000_0001),
+            OverflowMode::Wrap,
+            CoercionPolicy::Strict,
+        );
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -131,12 +199,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn initial_zero_decrement_by_one_saturate_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U8(0x00));
+
+        let mut filter = DecrementStateByConstFilter::new(
+            0,
+            Value::U8(0x01),
+            OverflowMode::Saturate,
+            CoercionPolicy::Strict,
+        );
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true),
+        );
+        assert_eq!(*state_manager.get_value(0).unwrap(), Value::U8(0x00));
+    }
+
+    #[test]
+    fn initial_zero_decrement_by_one_fail_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U8(0x00));
+
+        let mut filter = DecrementStateByConstFilter::new(
+            0,
+            Value::U8(0x01),
+            OverflowMode::Fail,
+            CoercionPolicy::Strict,
+        );
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(false),
+        );
+        assert_eq!(*state_manager.get_value(0).unwrap(), Value::U8(0x00));
+    }
+
     #[test]
     fn wrong_state_type_test() {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U8(0x00));
 
-        let mut filter = DecrementStateByConstFilter::new(0, Value::U32(0x0000_0001));
+        let mut filter = DecrementStateByConstFilter::new(
+            0,
+            Value::U32(0x0000_0001),
+            OverflowMode::Wrap,
+            CoercionPolicy::Strict,
+        );
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -146,9 +257,19 @@ mod tests {
 
     #[test]
     fn serialize_test() {
-        let filter = DecrementStateByConstFilter::new(0xabab_abab, Value::U32(0xffff_ffff));
+        let filter = DecrementStateByConstFilter::new(
+            0xabab_abab,
+            Value::U32(0xffff_ffff),
+            OverflowMode::Wrap,
+            CoercionPolicy::Strict,
+        );
 
-        let expected_data = vec![0xab, 0xab, 0xab, 0xab, 0x02, 0xff, 0xff, 0xff, 0xff];
+        let expected_data = vec![
+            0xab, 0xab, 0xab, 0xab, // state index
+            0x02, 0xff, 0xff, 0xff, 0xff, // decrement value
+            0x00, // overflow mode
+            0x00, // coercion policy
+        ];
 
         assert_eq!(filter.serialize(), expected_data);
     }
@@ -160,6 +281,8 @@ mod tests {
         let filter = Box::new(DecrementStateByConstFilter::new(
             0xabab_abab,
             Value::U32(0xffff_ffff),
+            OverflowMode::Wrap,
+            CoercionPolicy::Strict,
         ));
 
         assert_eq!(
@@ -177,4 +300,39 @@ mod tests {
             Err(ConfigSerializerError::WrongSize)
         );
     }
+
+    #[test]
+    fn deserialize_clamp_test() {
+        let data = vec![
+            0xab, 0xab, 0xab, 0xab, // state index
+            0x00, 0x10, // decrement value
+            0x02, 0x00, 0x05, 0x00, 0xfa, // overflow mode
+        ];
+
+        let filter = Box::new(DecrementStateByConstFilter::new(
+            0xabab_abab,
+            Value::U8(0x10),
+            OverflowMode::Clamp(Value::U8(0x05), Value::U8(0xfa)),
+            CoercionPolicy::Strict,
+        ));
+
+        assert_eq!(
+            DecrementStateByConstFilter::try_deserialize(&data),
+            Ok(filter)
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_overflow_mode_test() {
+        let data = vec![
+            0xab, 0xab, 0xab, 0xab, // state index
+            0x00, 0x10, // decrement value
+            0x02, 0x01, 0x00, 0x05, 0x01, 0x00, 0xfa, // overflow mode
+        ];
+
+        assert_eq!(
+            DecrementStateByConstFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidOverflowMode)
+        );
+    }
 }
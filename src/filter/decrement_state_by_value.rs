@@ -5,20 +5,34 @@ use alloc::vec;
 use alloc::vec::Vec;
 use core::convert::TryInto;
 
-use crate::filter::{Filter, FilterError, DECREMENT_STATE_BY_VALUE_FILTER_CODE};
+use crate::filter::{
+    try_deserialize_coercion_policy, CoercionPolicy, Filter, FilterError, OverflowMode,
+    DECREMENT_STATE_BY_VALUE_FILTER_CODE,
+};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::{ExtractorValue, Value};
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value, ValueType};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
 pub struct DecrementStateByValueFilter {
     state_index: u32,
+    overflow_mode: OverflowMode,
+    coercion_policy: CoercionPolicy,
 }
 
 impl DecrementStateByValueFilter {
-    pub fn new(state_index: u32) -> Self {
-        Self { state_index }
+    pub fn new(
+        state_index: u32,
+        overflow_mode: OverflowMode,
+        coercion_policy: CoercionPolicy,
+    ) -> Self {
+        Self {
+            state_index,
+            overflow_mode,
+            coercion_policy,
+        }
     }
 }
 
@@ -32,42 +46,81 @@ impl Filter for DecrementStateByValueFilter {
             ExtractorValue::U8(decrement_value) => Value::U8(decrement_value),
             ExtractorValue::U16(decrement_value) => Value::U16(decrement_value),
             ExtractorValue::U32(decrement_value) => Value::U32(decrement_value),
+            ExtractorValue::I8(decrement_value) => Value::I8(decrement_value),
+            ExtractorValue::I16(decrement_value) => Value::I16(decrement_value),
+            ExtractorValue::I32(decrement_value) => Value::I32(decrement_value),
+            ExtractorValue::Fixed(decrement_value) => Value::Fixed(decrement_value),
             _ => return Err(FilterError::WrongValueType),
         };
 
-        let new_value = match (state_manager.get_value(self.state_index), decrement_value) {
-            (Some(Value::U8(current_state)), Value::U8(decrement_value)) => {
-                Value::U8(current_state.wrapping_sub(decrement_value))
-            }
-            (Some(Value::U16(current_state)), Value::U16(decrement_value)) => {
-                Value::U16(current_state.wrapping_sub(decrement_value))
-            }
-            (Some(Value::U32(current_state)), Value::U32(decrement_value)) => {
-                Value::U32(current_state.wrapping_sub(decrement_value))
-            }
-            _ => return Err(FilterError::WrongStateType),
+        let current_state = match state_manager.get_value(self.state_index) {
+            Some(current_state) => current_state,
+            None => return Err(FilterError::WrongStateType),
         };
 
-        state_manager.set_value(self.state_index, new_value);
+        match self
+            .coercion_policy
+            .sub(&self.overflow_mode, current_state, &decrement_value)?
+        {
+            Some(new_value) => {
+                state_manager.set_value(self.state_index, new_value);
 
-        Ok(true)
+                Ok(true)
+            }
+            None => Ok(false),
+        }
     }
 
     fn get_code(&self) -> u16 {
         DECREMENT_STATE_BY_VALUE_FILTER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        vec![
+            ExtractorValueType::U8,
+            ExtractorValueType::U16,
+            ExtractorValueType::U32,
+            ExtractorValueType::I8,
+            ExtractorValueType::I16,
+            ExtractorValueType::I32,
+            ExtractorValueType::Fixed,
+        ]
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![
+            StateAccess::Read(
+                self.state_index,
+                vec![
+                    ValueType::U8,
+                    ValueType::U16,
+                    ValueType::U32,
+                    ValueType::I8,
+                    ValueType::I16,
+                    ValueType::I32,
+                    ValueType::Fixed,
+                ],
+            ),
+            StateAccess::Write(self.state_index, None),
+        ]
+    }
 }
 
 impl Serialize for DecrementStateByValueFilter {
     fn serialize(&self) -> Vec<u8> {
         let state_index = self.state_index.to_be_bytes();
 
-        vec![
+        let mut data = vec![
             state_index[0],
             state_index[1],
             state_index[2],
             state_index[3],
-        ]
+        ];
+
+        data.append(&mut self.overflow_mode.serialize());
+        data.append(&mut self.coercion_policy.serialize());
+
+        data
     }
 }
 
@@ -79,7 +132,21 @@ impl TryDeserialize for DecrementStateByValueFilter {
 
         let state_index = u32::from_be_bytes(data[0..=3].try_into().unwrap());
 
-        Ok(Box::new(Self { state_index }))
+        // Filters serialized without an overflow mode wrap around
+        let overflow_mode = if data.len() > 4 {
+            *OverflowMode::try_deserialize(&data[4..])?
+        } else {
+            OverflowMode::Wrap
+        };
+
+        let coercion_policy =
+            try_deserialize_coercion_policy(data, 4 + overflow_mode.serialize().len())?;
+
+        Ok(Box::new(Self {
+            state_index,
+            overflow_mode,
+            coercion_policy,
+        }))
     }
 }
 
@@ -92,7 +159,8 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0x0000_0005));
 
-        let mut filter = DecrementStateByValueFilter::new(0);
+        let mut filter =
+            DecrementStateByValueFilter::new(0, OverflowMode::Wrap, CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::U32(0x0000_0005), &mut state_manager),
@@ -109,7 +177,8 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0x0000_0000));
 
-        let mut filter = DecrementStateByValueFilter::new(0);
+        let mut filter =
+            DecrementStateByValueFilter::new(0, OverflowMode::Wrap, CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::U32(0x0000_0001), &mut state_manager),
@@ -126,7 +195,8 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U8(0x00));
 
-        let mut filter = DecrementStateByValueFilter::new(0);
+        let mut filter =
+            DecrementStateByValueFilter::new(0, OverflowMode::Wrap, CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::U32(0x0000_0001), &mut state_manager),
@@ -139,7 +209,8 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U8(0x00));
 
-        let mut filter = DecrementStateByValueFilter::new(0);
+        let mut filter =
+            DecrementStateByValueFilter::new(0, OverflowMode::Wrap, CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -149,9 +220,13 @@ mod tests {
 
     #[test]
     fn serialize_test() {
-        let filter = DecrementStateByValueFilter::new(0xabab_abab);
+        let filter = DecrementStateByValueFilter::new(
+            0xabab_abab,
+            OverflowMode::Wrap,
+            CoercionPolicy::Strict,
+        );
 
-        let expected_data = vec![0xab, 0xab, 0xab, 0xab];
+        let expected_data = vec![0xab, 0xab, 0xab, 0xab, 0x00, 0x00];
 
         assert_eq!(filter.serialize(), expected_data);
     }
@@ -160,7 +235,11 @@ mod tests {
     fn deserialize_test() {
         let data = vec![0xab, 0xab, 0xab, 0xab];
 
-        let filter = Box::new(DecrementStateByValueFilter::new(0xabab_abab));
+        let filter = Box::new(DecrementStateByValueFilter::new(
+            0xabab_abab,
+            OverflowMode::Wrap,
+            CoercionPolicy::Strict,
+        ));
 
         assert_eq!(
             DecrementStateByValueFilter::try_deserialize(&data),
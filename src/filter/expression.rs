@@ -0,0 +1,217 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::expression::{Expression, Instruction};
+use crate::filter::{
+    Filter, FilterError, EXPRESSION_FILTER_CODE, NUMERIC_EXTRACTOR_VALUE_TYPES, NUMERIC_VALUE_TYPES,
+};
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::state_manager::StateManager;
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, ValueType};
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub struct ExpressionFilter {
+    expression: Expression,
+}
+
+impl ExpressionFilter {
+    pub fn new(expression: Expression) -> Result<Self, ConfigSerializerError> {
+        if let Err(err) = expression.validate() {
+            return Err(ConfigSerializerError::InvalidExpression(err));
+        }
+
+        Ok(Self { expression })
+    }
+}
+
+impl Filter for ExpressionFilter {
+    fn filter(
+        &mut self,
+        value: &ExtractorValue,
+        state_manager: &mut StateManager,
+    ) -> Result<bool, FilterError> {
+        self.expression.evaluate(value, state_manager)
+    }
+
+    fn get_code(&self) -> u16 {
+        EXPRESSION_FILTER_CODE
+    }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        if !self
+            .expression
+            .instructions
+            .contains(&Instruction::LoadValue)
+        {
+            return ExtractorValueType::all();
+        }
+
+        let mut accepted_types = NUMERIC_EXTRACTOR_VALUE_TYPES.to_vec();
+        accepted_types.push(ExtractorValueType::Bool);
+
+        accepted_types
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        let mut read_states = BTreeSet::new();
+        let mut written_states = BTreeSet::new();
+
+        for instruction in self.expression.instructions.iter() {
+            match *instruction {
+                Instruction::LoadState(state_index) => {
+                    read_states.insert(state_index);
+                }
+                // Written values take the type of the state
+                Instruction::StoreState(state_index) => {
+                    read_states.insert(state_index);
+                    written_states.insert(state_index);
+                }
+                _ => {}
+            }
+        }
+
+        let mut accepted_types = NUMERIC_VALUE_TYPES.to_vec();
+        accepted_types.push(ValueType::Bool);
+
+        let mut accesses = vec![];
+
+        for state_index in read_states.into_iter() {
+            accesses.push(StateAccess::Read(state_index, accepted_types.clone()));
+        }
+
+        for state_index in written_states.into_iter() {
+            accesses.push(StateAccess::Write(state_index, None));
+        }
+
+        accesses
+    }
+}
+
+impl Serialize for ExpressionFilter {
+    fn serialize(&self) -> Vec<u8> {
+        self.expression.serialize()
+    }
+}
+
+impl TryDeserialize for ExpressionFilter {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        let expression = *Expression::try_deserialize(data)?;
+
+        Ok(Box::new(Self { expression }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::str::FromStr;
+
+    use crate::expression::ExpressionError;
+    use crate::Value;
+
+    #[test]
+    fn filter_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U16(0x0010));
+
+        let mut filter =
+            ExpressionFilter::new(Expression::from_str("state[0] = state[0] - value").unwrap())
+                .unwrap();
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::U8(0x01), &mut state_manager),
+            Ok(true)
+        );
+        assert_eq!(*state_manager.get_value(0).unwrap(), Value::U16(0x000f));
+    }
+
+    #[test]
+    fn accesses_test() {
+        let filter = ExpressionFilter::new(
+            Expression::from_str("state[2] = state[1] + 1; state[1] > 5").unwrap(),
+        )
+        .unwrap();
+
+        let accepted_types = vec![
+            ValueType::U8,
+            ValueType::U16,
+            ValueType::U32,
+            ValueType::I8,
+            ValueType::I16,
+            ValueType::I32,
+            ValueType::Fixed,
+            ValueType::Bool,
+        ];
+
+        assert_eq!(
+            filter.get_state_accesses(),
+            vec![
+                StateAccess::Read(1, accepted_types.clone()),
+                StateAccess::Read(2, accepted_types),
+                StateAccess::Write(2, None),
+            ]
+        );
+        assert_eq!(filter.get_accepted_value_types(), ExtractorValueType::all());
+    }
+
+    #[test]
+    fn serialize_test() {
+        let filter = ExpressionFilter::new(Expression::from_str("value != 1").unwrap()).unwrap();
+
+        let expected_data = vec![0x03, 0x01, 0x00, 0x00, 0x00, 0x00, 0x01, 0x0c];
+
+        assert_eq!(filter.serialize(), expected_data);
+    }
+
+    #[test]
+    fn deserialize_test() {
+        let data = vec![0x03, 0x01, 0x00, 0x00, 0x00, 0x00, 0x01, 0x0c];
+
+        let filter =
+            Box::new(ExpressionFilter::new(Expression::from_str("value != 1").unwrap()).unwrap());
+
+        assert_eq!(ExpressionFilter::try_deserialize(&data), Ok(filter));
+    }
+
+    #[test]
+    fn deserialize_wrong_size_test() {
+        let data = vec![0x03, 0x01, 0x00, 0x00, 0x00, 0x00, 0x01];
+
+        assert_eq!(
+            ExpressionFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::WrongSize)
+        );
+    }
+
+    #[test]
+    fn new_invalid_expression_test() {
+        let expression = Expression {
+            instructions: vec![Instruction::Jump(2)],
+        };
+
+        assert_eq!(
+            ExpressionFilter::new(expression),
+            Err(ConfigSerializerError::InvalidExpression(
+                ExpressionError::InvalidJump
+            ))
+        );
+
+        let expression = Expression {
+            instructions: vec![Instruction::LoadValue; 256],
+        };
+
+        assert_eq!(
+            ExpressionFilter::new(expression),
+            Err(ConfigSerializerError::InvalidExpression(
+                ExpressionError::TooManyInstructions
+            ))
+        );
+    }
+}
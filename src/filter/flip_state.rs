@@ -8,7 +8,8 @@ use core::convert::TryInto;
 use crate::filter::{Filter, FilterError, FLIP_STATE_FILTER_CODE};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::{ExtractorValue, Value};
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value, ValueType};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -41,6 +42,17 @@ impl Filter for FlipStateFilter {
     fn get_code(&self) -> u16 {
         FLIP_STATE_FILTER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![
+            StateAccess::Read(self.state_index, vec![ValueType::Bool]),
+            StateAccess::Write(self.state_index, None),
+        ]
+    }
 }
 
 impl Serialize for FlipStateFilter {
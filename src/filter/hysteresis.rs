@@ -0,0 +1,462 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::{TryFrom, TryInto};
+
+use crate::filter::{
+    try_deserialize_coercion_policy, CoercionPolicy, Filter, FilterError, HYSTERESIS_FILTER_CODE,
+    NUMERIC_VALUE_TYPES,
+};
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::state_manager::StateManager;
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value, ValueType};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CrossingDirection {
+    // Passes when the value rises above the upper threshold
+    Rising,
+    // Passes when the value drops below the lower threshold
+    Falling,
+    Both,
+}
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub struct HysteresisFilter {
+    // Bool state, true after the value rose above the upper threshold and until it drops below
+    // the lower one
+    state_index: u32,
+    lower_threshold: Value,
+    upper_threshold: Value,
+    direction: CrossingDirection,
+    coercion_policy: CoercionPolicy,
+}
+
+impl HysteresisFilter {
+    pub fn new(
+        state_index: u32,
+        lower_threshold: Value,
+        upper_threshold: Value,
+        direction: CrossingDirection,
+        coercion_policy: CoercionPolicy,
+    ) -> Result<Self, ConfigSerializerError> {
+        // Thresholds have to be numbers and the lower one can not be above the upper one
+        match coercion_policy.compare_numbers(&lower_threshold, &upper_threshold) {
+            Some(Ordering::Less) | Some(Ordering::Equal) => {}
+            _ => return Err(ConfigSerializerError::InvalidHysteresisThresholds),
+        }
+
+        Ok(Self {
+            state_index,
+            lower_threshold,
+            upper_threshold,
+            direction,
+            coercion_policy,
+        })
+    }
+
+    fn compare(&self, value: &Value, threshold: &Value) -> Result<Ordering, FilterError> {
+        self.coercion_policy
+            .compare_numbers(value, threshold)
+            .ok_or(FilterError::WrongValueType)
+    }
+}
+
+impl Filter for HysteresisFilter {
+    fn filter(
+        &mut self,
+        value: &ExtractorValue,
+        state_manager: &mut StateManager,
+    ) -> Result<bool, FilterError> {
+        let value = Value::try_from(value).map_err(|_| FilterError::WrongValueType)?;
+
+        let is_above = match state_manager.get_value(self.state_index) {
+            Some(Value::Bool(is_above)) => *is_above,
+            _ => return Err(FilterError::WrongStateType),
+        };
+
+        // Values between the thresholds keep the previous crossing
+        if !is_above && self.compare(&value, &self.upper_threshold)? == Ordering::Greater {
+            state_manager.set_value(self.state_index, Value::Bool(true));
+
+            Ok(self.direction != CrossingDirection::Falling)
+        } else if is_above && self.compare(&value, &self.lower_threshold)? == Ordering::Less {
+            state_manager.set_value(self.state_index, Value::Bool(false));
+
+            Ok(self.direction != CrossingDirection::Rising)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn get_code(&self) -> u16 {
+        HYSTERESIS_FILTER_CODE
+    }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        if !NUMERIC_VALUE_TYPES.contains(&self.upper_threshold.get_type()) {
+            return vec![];
+        }
+
+        self.coercion_policy.get_accepted_extractor_value_types(
+            ExtractorValue::from(&self.upper_threshold).get_type(),
+        )
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![
+            StateAccess::Read(self.state_index, vec![ValueType::Bool]),
+            StateAccess::Write(self.state_index, Some(ValueType::Bool)),
+        ]
+    }
+}
+
+impl Serialize for CrossingDirection {
+    fn serialize(&self) -> Vec<u8> {
+        match self {
+            CrossingDirection::Rising => vec![0x00],
+            CrossingDirection::Falling => vec![0x01],
+            CrossingDirection::Both => vec![0x02],
+        }
+    }
+}
+
+impl TryDeserialize for CrossingDirection {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.is_empty() {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        match data[0] {
+            0x00 => Ok(Box::new(CrossingDirection::Rising)),
+            0x01 => Ok(Box::new(CrossingDirection::Falling)),
+            0x02 => Ok(Box::new(CrossingDirection::Both)),
+            _ => Err(ConfigSerializerError::UnknownEnumVariant),
+        }
+    }
+}
+
+impl Serialize for HysteresisFilter {
+    fn serialize(&self) -> Vec<u8> {
+        let state_index_bytes = self.state_index.to_be_bytes();
+
+        let mut data = vec![
+            state_index_bytes[0],
+            state_index_bytes[1],
+            state_index_bytes[2],
+            state_index_bytes[3],
+        ];
+
+        data.append(&mut self.direction.serialize());
+        data.append(&mut self.lower_threshold.serialize());
+        data.append(&mut self.upper_threshold.serialize());
+        data.append(&mut self.coercion_policy.serialize());
+
+        data
+    }
+}
+
+impl TryDeserialize for HysteresisFilter {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.len() < 5 {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        let state_index = u32::from_be_bytes(data[0..=3].try_into().unwrap());
+        let direction = *CrossingDirection::try_deserialize(&data[4..])?;
+
+        let mut offset = 5;
+
+        let lower_threshold = *Value::try_deserialize(&data[offset..])?;
+        offset += lower_threshold.serialize().len();
+
+        let upper_threshold = *Value::try_deserialize(&data[offset..])?;
+        offset += upper_threshold.serialize().len();
+
+        let coercion_policy = try_deserialize_coercion_policy(data, offset)?;
+
+        Ok(Box::new(Self::new(
+            state_index,
+            lower_threshold,
+            upper_threshold,
+            direction,
+            coercion_policy,
+        )?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter_values(
+        filter: &mut HysteresisFilter,
+        state_manager: &mut StateManager,
+        values: &[i16],
+    ) -> Vec<bool> {
+        values
+            .iter()
+            .map(|value| {
+                filter
+                    .filter(&ExtractorValue::I16(*value), state_manager)
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rising_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::Bool(false));
+
+        let mut filter = HysteresisFilter::new(
+            0,
+            Value::I16(23),
+            Value::I16(25),
+            CrossingDirection::Rising,
+            CoercionPolicy::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter_values(&mut filter, &mut state_manager, &[24, 25, 26, 27, 24, 26]),
+            vec![false, false, true, false, false, false]
+        );
+        assert_eq!(*state_manager.get_value(0).unwrap(), Value::Bool(true));
+
+        // Re-armed below the lower threshold
+        assert_eq!(
+            filter_values(&mut filter, &mut state_manager, &[22, 26]),
+            vec![false, true]
+        );
+    }
+
+    #[test]
+    fn falling_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::Bool(true));
+
+        let mut filter = HysteresisFilter::new(
+            0,
+            Value::I16(23),
+            Value::I16(25),
+            CrossingDirection::Falling,
+            CoercionPolicy::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter_values(
+                &mut filter,
+                &mut state_manager,
+                &[24, 23, 22, 20, 24, 26, 22]
+            ),
+            vec![false, false, true, false, false, false, true]
+        );
+        assert_eq!(*state_manager.get_value(0).unwrap(), Value::Bool(false));
+    }
+
+    #[test]
+    fn both_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::Bool(false));
+
+        let mut filter = HysteresisFilter::new(
+            0,
+            Value::I16(-5),
+            Value::I16(5),
+            CrossingDirection::Both,
+            CoercionPolicy::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter_values(&mut filter, &mut state_manager, &[0, 6, 0, -6, -10, 0, 6]),
+            vec![false, true, false, true, false, false, true]
+        );
+    }
+
+    #[test]
+    fn widen_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::Bool(false));
+
+        let mut filter = HysteresisFilter::new(
+            0,
+            Value::U8(23),
+            Value::U8(25),
+            CrossingDirection::Rising,
+            CoercionPolicy::Widen,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::U16(0x0100), &mut state_manager),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn wrong_value_type_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::Bool(false));
+
+        let mut filter = HysteresisFilter::new(
+            0,
+            Value::U8(23),
+            Value::U8(25),
+            CrossingDirection::Rising,
+            CoercionPolicy::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::U16(0x0100), &mut state_manager),
+            Err(FilterError::WrongValueType)
+        );
+    }
+
+    #[test]
+    fn wrong_state_type_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U8(0x00));
+
+        let mut filter = HysteresisFilter::new(
+            0,
+            Value::U8(23),
+            Value::U8(25),
+            CrossingDirection::Rising,
+            CoercionPolicy::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::U8(0x20), &mut state_manager),
+            Err(FilterError::WrongStateType)
+        );
+    }
+
+    #[test]
+    fn accesses_test() {
+        let filter = HysteresisFilter::new(
+            0,
+            Value::U8(23),
+            Value::U8(25),
+            CrossingDirection::Rising,
+            CoercionPolicy::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter.get_accepted_value_types(),
+            vec![ExtractorValueType::U8]
+        );
+        assert_eq!(
+            filter.get_state_accesses(),
+            vec![
+                StateAccess::Read(0, vec![ValueType::Bool]),
+                StateAccess::Write(0, Some(ValueType::Bool)),
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_test() {
+        let filter = HysteresisFilter::new(
+            0xabab_abab,
+            Value::U8(0x17),
+            Value::U8(0x19),
+            CrossingDirection::Both,
+            CoercionPolicy::Widen,
+        )
+        .unwrap();
+
+        let expected_data = vec![
+            0xab, 0xab, 0xab, 0xab, // state index
+            0x02, // direction
+            0x00, 0x17, // lower threshold
+            0x00, 0x19, // upper threshold
+            0x01, // coercion policy
+        ];
+
+        assert_eq!(filter.serialize(), expected_data);
+    }
+
+    #[test]
+    fn deserialize_test() {
+        let data = vec![
+            0xab, 0xab, 0xab, 0xab, // state index
+            0x00, // direction
+            0x00, 0x17, // lower threshold
+            0x00, 0x19, // upper threshold
+        ];
+
+        let filter = Box::new(
+            HysteresisFilter::new(
+                0xabab_abab,
+                Value::U8(0x17),
+                Value::U8(0x19),
+                CrossingDirection::Rising,
+                CoercionPolicy::Strict,
+            )
+            .unwrap(),
+        );
+
+        assert_eq!(HysteresisFilter::try_deserialize(&data), Ok(filter));
+    }
+
+    #[test]
+    fn deserialize_wrong_size_test() {
+        let data = vec![0xab, 0xab, 0xab, 0xab, 0x00, 0x00, 0x17, 0x00];
+
+        assert_eq!(
+            HysteresisFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::WrongSize)
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_thresholds_test() {
+        let data = vec![0xab, 0xab, 0xab, 0xab, 0x00, 0x00, 0x19, 0x00, 0x17];
+
+        assert_eq!(
+            HysteresisFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidHysteresisThresholds)
+        );
+
+        let data = vec![0xab, 0xab, 0xab, 0xab, 0x00, 0x03, 0x01, 0x03, 0x01];
+
+        assert_eq!(
+            HysteresisFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidHysteresisThresholds)
+        );
+    }
+
+    #[test]
+    fn new_invalid_thresholds_test() {
+        assert_eq!(
+            HysteresisFilter::new(
+                0,
+                Value::U8(0x19),
+                Value::U8(0x17),
+                CrossingDirection::Both,
+                CoercionPolicy::Strict,
+            ),
+            Err(ConfigSerializerError::InvalidHysteresisThresholds)
+        );
+        assert_eq!(
+            HysteresisFilter::new(
+                0,
+                Value::Rgb(0x00, 0x00, 0x00),
+                Value::Rgb(0xff, 0xff, 0xff),
+                CrossingDirection::Both,
+                CoercionPolicy::Strict,
+            ),
+            Err(ConfigSerializerError::InvalidHysteresisThresholds)
+        );
+    }
+}
@@ -5,23 +5,36 @@ use alloc::vec;
 use alloc::vec::Vec;
 use core::convert::TryInto;
 
-use crate::filter::{Filter, FilterError, INCREMENT_STATE_BY_CONST_FILTER_CODE};
+use crate::filter::{
+    try_deserialize_coercion_policy, CoercionPolicy, Filter, FilterError, OverflowMode,
+    INCREMENT_STATE_BY_CONST_FILTER_CODE,
+};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::{ExtractorValue, Value};
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
 pub struct IncrementStateByConstFilter {
     state_index: u32,
     increment_value: Value,
+    overflow_mode: OverflowMode,
+    coercion_policy: CoercionPolicy,
 }
 
 impl IncrementStateByConstFilter {
-    pub fn new(state_index: u32, increment_value: Value) -> Self {
+    pub fn new(
+        state_index: u32,
+        increment_value: Value,
+        overflow_mode: OverflowMode,
+        coercion_policy: CoercionPolicy,
+    ) -> Self {
         Self {
             state_index,
             increment_value,
+            overflow_mode,
+            coercion_policy,
         }
     }
 }
@@ -32,30 +45,51 @@ impl Filter for IncrementStateByConstFilter {
         _value: &ExtractorValue,
         state_manager: &mut StateManager,
     ) -> Result<bool, FilterError> {
-        let new_value = match (
-            state_manager.get_value(self.state_index),
-            &self.increment_value,
-        ) {
-            (Some(Value::U8(current_state)), Value::U8(increment_value)) => {
-                Value::U8(current_state.wrapping_add(*increment_value))
-            }
-            (Some(Value::U16(current_state)), Value::U16(increment_value)) => {
-                Value::U16(current_state.wrapping_add(*increment_value))
-            }
-            (Some(Value::U32(current_state)), Value::U32(increment_value)) => {
-                Value::U32(current_state.wrapping_add(*increment_value))
-            }
-            _ => return Err(FilterError::WrongStateType),
+        let current_state = match state_manager.get_value(self.state_index) {
+            Some(current_state) => current_state,
+            None => return Err(FilterError::WrongStateType),
         };
 
-        state_manager.set_value(self.state_index, new_value);
+        match self
+            .coercion_policy
+            .add(&self.overflow_mode, current_state, &self.increment_value)?
+        {
+            Some(new_value) => {
+                state_manager.set_value(self.state_index, new_value);
 
-        Ok(true)
+                Ok(true)
+            }
+            None => Ok(false),
+        }
     }
 
     fn get_code(&self) -> u16 {
         INCREMENT_STATE_BY_CONST_FILTER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        let accepted_types = match self.increment_value {
+            Value::U8(_)
+            | Value::U16(_)
+            | Value::U32(_)
+            | Value::I8(_)
+            | Value::I16(_)
+            | Value::I32(_)
+            | Value::Fixed(_) => self
+                .coercion_policy
+                .get_accepted_value_types(self.increment_value.get_type()),
+            _ => vec![],
+        };
+
+        vec![
+            StateAccess::Read(self.state_index, accepted_types),
+            StateAccess::Write(self.state_index, None),
+        ]
+    }
 }
 
 impl Serialize for IncrementStateByConstFilter {
@@ -73,6 +107,9 @@ impl Serialize for IncrementStateByConstFilter {
 
         data.append(&mut increment_value_bytes);
 
+        data.append(&mut self.overflow_mode.serialize());
+        data.append(&mut self.coercion_policy.serialize());
+
         return data;
     }
 }
@@ -85,10 +122,31 @@ impl TryDeserialize for IncrementStateByConstFilter {
 
         let state_index = u32::from_be_bytes(data[0..=3].try_into().unwrap());
         let increment_value = *Value::try_deserialize(&data[4..])?;
+        let offset = 4 + increment_value.serialize().len();
+
+        // Filters serialized without an overflow mode wrap around
+        let overflow_mode = if data.len() > offset {
+            *OverflowMode::try_deserialize(&data[offset..])?
+        } else {
+            OverflowMode::Wrap
+        };
+
+        let coercion_policy =
+            try_deserialize_coercion_policy(data, offset + overflow_mode.serialize().len())?;
+
+        // Clamp bounds have the type of the state, which only has to match the increment value when
+        // values are not coerced
+        if coercion_policy == CoercionPolicy::Strict
+            && !overflow_mode.is_valid_for(&increment_value)
+        {
+            return Err(ConfigSerializerError::InvalidOverflowMode);
+        }
 
         Ok(Box::new(Self {
             state_index,
             increment_value,
+            overflow_mode,
+            coercion_policy,
         }))
     }
 }
@@ -102,7 +160,12 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0x0000_0000));
 
-        let mut filter = IncrementStateByConstFilter::new(0, Value::U32(0x0000_0005));
+        let mut filter = IncrementStateByConstFilter::new(
+            0,
+            Value::U32(0x0000_0005),
+            OverflowMode::Wrap,
+            CoercionPolicy::Strict,
+        );
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -119,7 +182,12 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0xffff_ffff));
 
-        let mut filter = IncrementStateByConstFilter::new(0, Value::U32(0x0000_0001));
+        let mut filter = IncrementStateByConstFilter::new(
+            0,
+            Value::U32(0x0000_0001),
+            OverflowMode::Wrap,
+            CoercionPolicy::Strict,
+        );
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -131,12 +199,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn initial_negative_increment_by_one_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::I8(-1));
+
+        let mut filter = IncrementStateByConstFilter::new(
+            0,
+            Value::I8(1),
+            OverflowMode::Wrap,
+            CoercionPolicy::Strict,
+        );
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true),
+        );
+        assert_eq!(*state_manager.get_value(0).unwrap(), Value::I8(0));
+    }
+
     #[test]
     fn wrong_state_type_test() {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U8(0x00));
 
-        let mut filter = IncrementStateByConstFilter::new(0, Value::U32(0x0000_0001));
+        let mut filter = IncrementStateByConstFilter::new(
+            0,
+            Value::U32(0x0000_0001),
+            OverflowMode::Wrap,
+            CoercionPolicy::Strict,
+        );
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -146,9 +238,19 @@ mod tests {
 
     #[test]
     fn serialize_test() {
-        let filter = IncrementStateByConstFilter::new(0xabab_abab, Value::U32(0xffff_ffff));
+        let filter = IncrementStateByConstFilter::new(
+            0xabab_abab,
+            Value::U32(0xffff_ffff),
+            OverflowMode::Wrap,
+            CoercionPolicy::Strict,
+        );
 
-        let expected_data = vec![0xab, 0xab, 0xab, 0xab, 0x02, 0xff, 0xff, 0xff, 0xff];
+        let expected_data = vec![
+            0xab, 0xab, 0xab, 0xab, // state index
+            0x02, 0xff, 0xff, 0xff, 0xff, // increment value
+            0x00, // overflow mode
+            0x00, // coercion policy
+        ];
 
         assert_eq!(filter.serialize(), expected_data);
     }
@@ -160,6 +262,8 @@ mod tests {
         let filter = Box::new(IncrementStateByConstFilter::new(
             0xabab_abab,
             Value::U32(0xffff_ffff),
+            OverflowMode::Wrap,
+            CoercionPolicy::Strict,
         ));
 
         assert_eq!(
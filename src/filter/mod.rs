@@ -1,9 +1,14 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
 use core::fmt::Debug;
 use downcast_rs::{impl_downcast, Downcast};
 
+use crate::expression::ExpressionError;
 use crate::serializer::Serialize;
 use crate::state_manager::StateManager;
-use crate::ExtractorValue;
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType};
 
 mod value_equal_to_const;
 pub use value_equal_to_const::*;
@@ -50,6 +55,45 @@ pub use set_state_to_state::*;
 mod state_equal_to_state;
 pub use state_equal_to_state::*;
 
+mod time_matches_solar_event;
+pub use time_matches_solar_event::*;
+
+mod cron_schedule;
+pub use cron_schedule::*;
+
+mod date_in_calendar;
+pub use date_in_calendar::*;
+
+mod overflow_mode;
+pub use overflow_mode::*;
+
+mod coercion_policy;
+pub use coercion_policy::*;
+
+mod comparison;
+pub use comparison::*;
+
+mod expression;
+pub use expression::*;
+
+mod cycle_state;
+pub use cycle_state::*;
+
+mod debounce;
+pub use debounce::*;
+
+mod throttle;
+pub use throttle::*;
+
+mod hysteresis;
+pub use hysteresis::*;
+
+mod state_changed;
+pub use state_changed::*;
+
+// Filters are stored behind a u8 length in matchers
+pub const MAX_FILTER_LEN: usize = u8::MAX as usize;
+
 pub const VALUE_EQUAL_TO_CONST_FILTER_CODE: u16 = 0x0000;
 pub const STATE_EQUAL_TO_CONST_FILTER_CODE: u16 = 0x0001;
 pub const STATE_EQUAL_TO_VALUE_FILTER_CODE: u16 = 0x0002;
@@ -65,11 +109,23 @@ pub const STATE_MORE_THAN_CONST_FILTER_CODE: u16 = 0x000b;
 pub const STATE_LESS_THAN_CONST_FILTER_CODE: u16 = 0x000c;
 pub const SET_STATE_TO_STATE_FILTER_CODE: u16 = 0x000d;
 pub const STATE_EQUAL_TO_STATE_FILTER_CODE: u16 = 0x000e;
+pub const TIME_MATCHES_SOLAR_EVENT_FILTER_CODE: u16 = 0x000f;
+pub const CRON_SCHEDULE_FILTER_CODE: u16 = 0x0010;
+pub const DATE_IN_CALENDAR_FILTER_CODE: u16 = 0x0011;
+pub const COMPARISON_FILTER_CODE: u16 = 0x0012;
+pub const EXPRESSION_FILTER_CODE: u16 = 0x0013;
+pub const CYCLE_STATE_FILTER_CODE: u16 = 0x0014;
+pub const DEBOUNCE_FILTER_CODE: u16 = 0x0015;
+pub const THROTTLE_FILTER_CODE: u16 = 0x0016;
+pub const HYSTERESIS_FILTER_CODE: u16 = 0x0017;
+pub const STATE_CHANGED_FILTER_CODE: u16 = 0x0018;
 
 #[derive(Debug, PartialEq)]
 pub enum FilterError {
     WrongValueType,
     WrongStateType,
+    UnknownCalendar,
+    ExpressionError(ExpressionError),
 }
 
 pub trait Filter: Downcast + Debug + Serialize {
@@ -79,6 +135,8 @@ pub trait Filter: Downcast + Debug + Serialize {
         state_manager: &mut StateManager,
     ) -> Result<bool, FilterError>;
     fn get_code(&self) -> u16;
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType>;
+    fn get_state_accesses(&self) -> Vec<StateAccess>;
 }
 
 impl_downcast!(Filter);
@@ -0,0 +1,270 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::filter::FilterError;
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::Value;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OverflowMode {
+    Wrap,
+    Saturate,
+    // Inclusive bounds of the same type as the state
+    Clamp(Value, Value),
+    // The filter does not match and the state is left unchanged
+    Fail,
+}
+
+macro_rules! apply_overflow_mode {
+    ($mode:expr, $variant:ident, $lhs:expr, $rhs:expr, $wrapping:ident, $saturating:ident, $checked:ident) => {
+        match $mode {
+            OverflowMode::Wrap => Some(Value::$variant($lhs.$wrapping($rhs))),
+            OverflowMode::Saturate => Some(Value::$variant($lhs.$saturating($rhs))),
+            OverflowMode::Clamp(Value::$variant(min), Value::$variant(max)) => {
+                Some(Value::$variant($lhs.$saturating($rhs).max(*min).min(*max)))
+            }
+            OverflowMode::Clamp(_, _) => return Err(FilterError::WrongStateType),
+            OverflowMode::Fail => $lhs.$checked($rhs).map(Value::$variant),
+        }
+    };
+}
+
+macro_rules! impl_overflow_mode_operation {
+    ($name:ident, $wrapping:ident, $saturating:ident, $checked:ident) => {
+        // Returns None if the operation overflowed in fail mode
+        pub fn $name(&self, lhs: &Value, rhs: &Value) -> Result<Option<Value>, FilterError> {
+            let result = match (lhs, rhs) {
+                (Value::U8(lhs), Value::U8(rhs)) => {
+                    apply_overflow_mode!(self, U8, lhs, *rhs, $wrapping, $saturating, $checked)
+                }
+                (Value::U16(lhs), Value::U16(rhs)) => {
+                    apply_overflow_mode!(self, U16, lhs, *rhs, $wrapping, $saturating, $checked)
+                }
+                (Value::U32(lhs), Value::U32(rhs)) => {
+                    apply_overflow_mode!(self, U32, lhs, *rhs, $wrapping, $saturating, $checked)
+                }
+                (Value::I8(lhs), Value::I8(rhs)) => {
+                    apply_overflow_mode!(self, I8, lhs, *rhs, $wrapping, $saturating, $checked)
+                }
+                (Value::I16(lhs), Value::I16(rhs)) => {
+                    apply_overflow_mode!(self, I16, lhs, *rhs, $wrapping, $saturating, $checked)
+                }
+                (Value::I32(lhs), Value::I32(rhs)) => {
+                    apply_overflow_mode!(self, I32, lhs, *rhs, $wrapping, $saturating, $checked)
+                }
+                (Value::Fixed(lhs), Value::Fixed(rhs)) => {
+                    apply_overflow_mode!(self, Fixed, lhs, *rhs, $wrapping, $saturating, $checked)
+                }
+                _ => return Err(FilterError::WrongStateType),
+            };
+
+            Ok(result)
+        }
+    };
+}
+
+impl OverflowMode {
+    impl_overflow_mode_operation!(add, wrapping_add, saturating_add, checked_add);
+    impl_overflow_mode_operation!(sub, wrapping_sub, saturating_sub, checked_sub);
+
+    // Bounds have to be numbers of the given type
+    pub fn is_valid_for(&self, value: &Value) -> bool {
+        match self {
+            OverflowMode::Clamp(min, max) => {
+                min.get_type() == value.get_type()
+                    && max.get_type() == value.get_type()
+                    && min <= max
+            }
+            _ => true,
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match self {
+            OverflowMode::Clamp(min, _) => {
+                matches!(
+                    min,
+                    Value::U8(_)
+                        | Value::U16(_)
+                        | Value::U32(_)
+                        | Value::I8(_)
+                        | Value::I16(_)
+                        | Value::I32(_)
+                        | Value::Fixed(_)
+                ) && self.is_valid_for(min)
+            }
+            _ => true,
+        }
+    }
+}
+
+impl Serialize for OverflowMode {
+    fn serialize(&self) -> Vec<u8> {
+        match self {
+            OverflowMode::Wrap => vec![0x00],
+            OverflowMode::Saturate => vec![0x01],
+            OverflowMode::Clamp(min, max) => {
+                let mut data = vec![0x02];
+                data.append(&mut min.serialize());
+                data.append(&mut max.serialize());
+
+                data
+            }
+            OverflowMode::Fail => vec![0x03],
+        }
+    }
+}
+
+impl TryDeserialize for OverflowMode {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.is_empty() {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        let overflow_mode = match data[0] {
+            0x00 => OverflowMode::Wrap,
+            0x01 => OverflowMode::Saturate,
+            0x02 => {
+                let min = *Value::try_deserialize(&data[1..])?;
+                let max = *Value::try_deserialize(&data[1 + min.serialize().len()..])?;
+
+                OverflowMode::Clamp(min, max)
+            }
+            0x03 => OverflowMode::Fail,
+            _ => return Err(ConfigSerializerError::UnknownEnumVariant),
+        };
+
+        if !overflow_mode.is_valid() {
+            return Err(ConfigSerializerError::InvalidOverflowMode);
+        }
+
+        Ok(Box::new(overflow_mode))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_test() {
+        assert_eq!(
+            OverflowMode::Wrap.sub(&Value::U8(0x00), &Value::U8(0x01)),
+            Ok(Some(Value::U8(0xff)))
+        );
+        assert_eq!(
+            OverflowMode::Wrap.add(&Value::I8(127), &Value::I8(1)),
+            Ok(Some(Value::I8(-128)))
+        );
+    }
+
+    #[test]
+    fn saturate_test() {
+        assert_eq!(
+            OverflowMode::Saturate.sub(&Value::U8(0x00), &Value::U8(0x01)),
+            Ok(Some(Value::U8(0x00)))
+        );
+        assert_eq!(
+            OverflowMode::Saturate.add(&Value::U16(0xfffe), &Value::U16(0x0005)),
+            Ok(Some(Value::U16(0xffff)))
+        );
+    }
+
+    #[test]
+    fn clamp_test() {
+        let overflow_mode = OverflowMode::Clamp(Value::U8(0x10), Value::U8(0xf0));
+
+        assert_eq!(
+            overflow_mode.sub(&Value::U8(0x20), &Value::U8(0x20)),
+            Ok(Some(Value::U8(0x10)))
+        );
+        assert_eq!(
+            overflow_mode.add(&Value::U8(0xe0), &Value::U8(0x40)),
+            Ok(Some(Value::U8(0xf0)))
+        );
+        assert_eq!(
+            overflow_mode.add(&Value::U8(0x20), &Value::U8(0x20)),
+            Ok(Some(Value::U8(0x40)))
+        );
+        assert_eq!(
+            overflow_mode.add(&Value::U16(0x0020), &Value::U16(0x0020)),
+            Err(FilterError::WrongStateType)
+        );
+    }
+
+    #[test]
+    fn fail_test() {
+        assert_eq!(
+            OverflowMode::Fail.sub(&Value::U32(0x0000_0000), &Value::U32(0x0000_0001)),
+            Ok(None)
+        );
+        assert_eq!(
+            OverflowMode::Fail.add(&Value::I16(-1), &Value::I16(1)),
+            Ok(Some(Value::I16(0)))
+        );
+    }
+
+    #[test]
+    fn wrong_type_test() {
+        assert_eq!(
+            OverflowMode::Wrap.add(&Value::U8(0x00), &Value::U16(0x0001)),
+            Err(FilterError::WrongStateType)
+        );
+        assert_eq!(
+            OverflowMode::Wrap.add(&Value::Bool(false), &Value::Bool(true)),
+            Err(FilterError::WrongStateType)
+        );
+    }
+
+    #[test]
+    fn serialize_test() {
+        let overflow_mode = OverflowMode::Clamp(Value::U8(0x10), Value::U8(0xf0));
+
+        let expected_data = vec![0x02, 0x00, 0x10, 0x00, 0xf0];
+
+        assert_eq!(overflow_mode.serialize(), expected_data);
+        assert_eq!(OverflowMode::Fail.serialize(), vec![0x03]);
+    }
+
+    #[test]
+    fn deserialize_test() {
+        let data = vec![0x02, 0x00, 0x10, 0x00, 0xf0];
+
+        assert_eq!(
+            OverflowMode::try_deserialize(&data),
+            Ok(Box::new(OverflowMode::Clamp(
+                Value::U8(0x10),
+                Value::U8(0xf0)
+            )))
+        );
+        assert_eq!(
+            OverflowMode::try_deserialize(&[0x01]),
+            Ok(Box::new(OverflowMode::Saturate))
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_test() {
+        let data = vec![0x02, 0x00, 0xf0, 0x00, 0x10];
+
+        assert_eq!(
+            OverflowMode::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidOverflowMode)
+        );
+
+        let data = vec![0x02, 0x00, 0x10, 0x01, 0x00, 0xf0];
+
+        assert_eq!(
+            OverflowMode::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidOverflowMode)
+        );
+
+        assert_eq!(
+            OverflowMode::try_deserialize(&[0x04]),
+            Err(ConfigSerializerError::UnknownEnumVariant)
+        );
+    }
+}
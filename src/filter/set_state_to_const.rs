@@ -8,7 +8,8 @@ use core::convert::TryInto;
 use crate::filter::{Filter, FilterError, SET_STATE_TO_CONST_FILTER_CODE};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::{ExtractorValue, Value};
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -40,6 +41,17 @@ impl Filter for SetStateToConstFilter {
     fn get_code(&self) -> u16 {
         SET_STATE_TO_CONST_FILTER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![StateAccess::Write(
+            self.state_index,
+            Some(self.target_value.get_type()),
+        )]
+    }
 }
 
 impl Serialize for SetStateToConstFilter {
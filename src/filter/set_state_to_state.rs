@@ -8,7 +8,8 @@ use core::convert::TryInto;
 use crate::filter::{Filter, FilterError, SET_STATE_TO_STATE_FILTER_CODE};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::ExtractorValue;
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, ValueType};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -45,6 +46,17 @@ impl Filter for SetStateToStateFilter {
     fn get_code(&self) -> u16 {
         SET_STATE_TO_STATE_FILTER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![
+            StateAccess::Read(self.target_state_index, ValueType::all()),
+            StateAccess::Copy(self.state_index, self.target_state_index),
+        ]
+    }
 }
 
 impl Serialize for SetStateToStateFilter {
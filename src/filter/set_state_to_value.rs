@@ -3,12 +3,13 @@ extern crate alloc;
 use alloc::boxed::Box;
 use alloc::vec;
 use alloc::vec::Vec;
-use core::convert::TryInto;
+use core::convert::{TryFrom, TryInto};
 
 use crate::filter::{Filter, FilterError, SET_STATE_TO_VALUE_FILTER_CODE};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::{ExtractorValue, Value};
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -28,11 +29,9 @@ impl Filter for SetStateToValueFilter {
         value: &ExtractorValue,
         state_manager: &mut StateManager,
     ) -> Result<bool, FilterError> {
-        let target_value = match *value {
-            ExtractorValue::U8(increment_value) => Value::U8(increment_value),
-            ExtractorValue::U16(increment_value) => Value::U16(increment_value),
-            ExtractorValue::U32(increment_value) => Value::U32(increment_value),
-            _ => return Err(FilterError::WrongValueType),
+        let target_value = match Value::try_from(value) {
+            Ok(target_value) => target_value,
+            Err(_) => return Err(FilterError::WrongValueType),
         };
 
         state_manager.set_value(self.state_index, target_value);
@@ -43,6 +42,28 @@ impl Filter for SetStateToValueFilter {
     fn get_code(&self) -> u16 {
         SET_STATE_TO_VALUE_FILTER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        vec![
+            ExtractorValueType::U8,
+            ExtractorValueType::U16,
+            ExtractorValueType::U32,
+            ExtractorValueType::Bool,
+            ExtractorValueType::I8,
+            ExtractorValueType::I16,
+            ExtractorValueType::I32,
+            ExtractorValueType::Fixed,
+            ExtractorValueType::Bytes,
+            ExtractorValueType::Rgb,
+            ExtractorValueType::RgbB,
+            ExtractorValueType::Rgbw,
+            ExtractorValueType::RgbwB,
+        ]
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![StateAccess::Write(self.state_index, None)]
+    }
 }
 
 impl Serialize for SetStateToValueFilter {
@@ -0,0 +1,344 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::filter::{Filter, FilterError, STATE_CHANGED_FILTER_CODE};
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::state_manager::StateManager;
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value, ValueType};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeDirection {
+    Any,
+    // Only for bool states
+    BecameTrue,
+    BecameFalse,
+}
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub struct StateChangedFilter {
+    state_index: u32,
+    // Holds the value of the state when the filter last ran
+    shadow_state_index: u32,
+    direction: ChangeDirection,
+}
+
+impl StateChangedFilter {
+    pub fn new(state_index: u32, shadow_state_index: u32, direction: ChangeDirection) -> Self {
+        Self {
+            state_index,
+            shadow_state_index,
+            direction,
+        }
+    }
+}
+
+impl Filter for StateChangedFilter {
+    fn filter(
+        &mut self,
+        _value: &ExtractorValue,
+        state_manager: &mut StateManager,
+    ) -> Result<bool, FilterError> {
+        let current_state = match state_manager.get_value(self.state_index) {
+            Some(current_state) => current_state.clone(),
+            None => return Err(FilterError::WrongStateType),
+        };
+
+        let is_direction_matching = match (self.direction, &current_state) {
+            (ChangeDirection::Any, _) => true,
+            (ChangeDirection::BecameTrue, Value::Bool(value)) => *value,
+            (ChangeDirection::BecameFalse, Value::Bool(value)) => !*value,
+            _ => return Err(FilterError::WrongStateType),
+        };
+
+        let is_changed = match state_manager.get_value(self.shadow_state_index) {
+            Some(last_state) => *last_state != current_state,
+            None => return Err(FilterError::WrongStateType),
+        };
+
+        if is_changed {
+            state_manager.set_value(self.shadow_state_index, current_state);
+        }
+
+        Ok(is_changed && is_direction_matching)
+    }
+
+    fn get_code(&self) -> u16 {
+        STATE_CHANGED_FILTER_CODE
+    }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        let accepted_types = match self.direction {
+            ChangeDirection::Any => ValueType::all(),
+            ChangeDirection::BecameTrue | ChangeDirection::BecameFalse => vec![ValueType::Bool],
+        };
+
+        let shadow_write = match self.direction {
+            // The shadow state takes the type of the watched state
+            ChangeDirection::Any => StateAccess::Copy(self.shadow_state_index, self.state_index),
+            ChangeDirection::BecameTrue | ChangeDirection::BecameFalse => {
+                StateAccess::Write(self.shadow_state_index, Some(ValueType::Bool))
+            }
+        };
+
+        vec![
+            StateAccess::Read(self.state_index, accepted_types.clone()),
+            StateAccess::Read(self.shadow_state_index, accepted_types),
+            shadow_write,
+        ]
+    }
+}
+
+impl Serialize for ChangeDirection {
+    fn serialize(&self) -> Vec<u8> {
+        match self {
+            ChangeDirection::Any => vec![0x00],
+            ChangeDirection::BecameTrue => vec![0x01],
+            ChangeDirection::BecameFalse => vec![0x02],
+        }
+    }
+}
+
+impl TryDeserialize for ChangeDirection {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.is_empty() {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        match data[0] {
+            0x00 => Ok(Box::new(ChangeDirection::Any)),
+            0x01 => Ok(Box::new(ChangeDirection::BecameTrue)),
+            0x02 => Ok(Box::new(ChangeDirection::BecameFalse)),
+            _ => Err(ConfigSerializerError::UnknownEnumVariant),
+        }
+    }
+}
+
+impl Serialize for StateChangedFilter {
+    fn serialize(&self) -> Vec<u8> {
+        let state_index_bytes = self.state_index.to_be_bytes();
+        let shadow_state_index_bytes = self.shadow_state_index.to_be_bytes();
+
+        let mut data = vec![
+            state_index_bytes[0],
+            state_index_bytes[1],
+            state_index_bytes[2],
+            state_index_bytes[3],
+            shadow_state_index_bytes[0],
+            shadow_state_index_bytes[1],
+            shadow_state_index_bytes[2],
+            shadow_state_index_bytes[3],
+        ];
+
+        data.append(&mut self.direction.serialize());
+
+        data
+    }
+}
+
+impl TryDeserialize for StateChangedFilter {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.len() < 9 {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        let state_index = u32::from_be_bytes(data[0..=3].try_into().unwrap());
+        let shadow_state_index = u32::from_be_bytes(data[4..=7].try_into().unwrap());
+        let direction = *ChangeDirection::try_deserialize(&data[8..])?;
+
+        Ok(Box::new(Self::new(
+            state_index,
+            shadow_state_index,
+            direction,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::filter::FlipStateFilter;
+
+    #[test]
+    fn any_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U8(0x00));
+        state_manager.set_value(1, Value::U8(0x00));
+
+        let mut filter = StateChangedFilter::new(0, 1, ChangeDirection::Any);
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(false)
+        );
+
+        state_manager.set_value(0, Value::U8(0xff));
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn direction_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::Bool(false));
+        state_manager.set_value(1, Value::Bool(false));
+        state_manager.set_value(2, Value::Bool(false));
+
+        let mut flip_filter = FlipStateFilter::new(0);
+        let mut became_true_filter = StateChangedFilter::new(0, 1, ChangeDirection::BecameTrue);
+        let mut became_false_filter = StateChangedFilter::new(0, 2, ChangeDirection::BecameFalse);
+
+        assert_eq!(
+            became_true_filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(false)
+        );
+        assert_eq!(
+            became_false_filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(false)
+        );
+
+        for expected_value in [true, false, true].iter() {
+            flip_filter
+                .filter(&ExtractorValue::None, &mut state_manager)
+                .unwrap();
+
+            assert_eq!(
+                became_true_filter.filter(&ExtractorValue::None, &mut state_manager),
+                Ok(*expected_value)
+            );
+            assert_eq!(
+                became_false_filter.filter(&ExtractorValue::None, &mut state_manager),
+                Ok(!*expected_value)
+            );
+        }
+    }
+
+    #[test]
+    fn wrong_state_type_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U8(0x00));
+
+        state_manager.set_value(1, Value::U8(0x00));
+
+        let mut filter = StateChangedFilter::new(0, 1, ChangeDirection::BecameTrue);
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Err(FilterError::WrongStateType)
+        );
+    }
+
+    #[test]
+    fn missing_shadow_state_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::Bool(true));
+
+        let mut filter = StateChangedFilter::new(0, 1, ChangeDirection::BecameTrue);
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Err(FilterError::WrongStateType)
+        );
+    }
+
+    #[test]
+    fn shadow_state_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U8(0x00));
+        state_manager.set_value(1, Value::U8(0xff));
+
+        let mut filter = StateChangedFilter::new(0, 1, ChangeDirection::Any);
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+        assert_eq!(*state_manager.get_value(1).unwrap(), Value::U8(0x00));
+
+        // A new instance picks up where the previous one left off
+        let mut filter = StateChangedFilter::new(0, 1, ChangeDirection::Any);
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn state_accesses_test() {
+        let filter = StateChangedFilter::new(0, 1, ChangeDirection::BecameFalse);
+
+        assert_eq!(
+            filter.get_state_accesses(),
+            vec![
+                StateAccess::Read(0, vec![ValueType::Bool]),
+                StateAccess::Read(1, vec![ValueType::Bool]),
+                StateAccess::Write(1, Some(ValueType::Bool)),
+            ]
+        );
+
+        let filter = StateChangedFilter::new(0, 1, ChangeDirection::Any);
+
+        assert_eq!(
+            filter.get_state_accesses(),
+            vec![
+                StateAccess::Read(0, ValueType::all()),
+                StateAccess::Read(1, ValueType::all()),
+                StateAccess::Copy(1, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_test() {
+        let filter = StateChangedFilter::new(0xabab_abab, 0x0123_4567, ChangeDirection::BecameTrue);
+
+        let expected_data = vec![
+            0xab, 0xab, 0xab, 0xab, // state index
+            0x01, 0x23, 0x45, 0x67, // shadow state index
+            0x01, // direction
+        ];
+
+        assert_eq!(filter.serialize(), expected_data);
+    }
+
+    #[test]
+    fn deserialize_test() {
+        let data = vec![0xab, 0xab, 0xab, 0xab, 0x01, 0x23, 0x45, 0x67, 0x00];
+
+        let filter = Box::new(StateChangedFilter::new(
+            0xabab_abab,
+            0x0123_4567,
+            ChangeDirection::Any,
+        ));
+
+        assert_eq!(StateChangedFilter::try_deserialize(&data), Ok(filter));
+    }
+
+    #[test]
+    fn deserialize_unknown_direction_test() {
+        let data = vec![0xab, 0xab, 0xab, 0xab, 0x01, 0x23, 0x45, 0x67, 0x03];
+
+        assert_eq!(
+            StateChangedFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::UnknownEnumVariant)
+        );
+    }
+}
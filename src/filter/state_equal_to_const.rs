@@ -5,23 +5,29 @@ use alloc::vec;
 use alloc::vec::Vec;
 use core::convert::TryInto;
 
-use crate::filter::{Filter, FilterError, STATE_EQUAL_TO_CONST_FILTER_CODE};
+use crate::filter::{
+    try_deserialize_coercion_policy, CoercionPolicy, Filter, FilterError,
+    STATE_EQUAL_TO_CONST_FILTER_CODE,
+};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::{ExtractorValue, Value};
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
 pub struct StateEqualToConstFilter {
     state_index: u32,
     required_value: Value,
+    coercion_policy: CoercionPolicy,
 }
 
 impl StateEqualToConstFilter {
-    pub fn new(state_index: u32, required_value: Value) -> Self {
+    pub fn new(state_index: u32, required_value: Value, coercion_policy: CoercionPolicy) -> Self {
         Self {
             state_index,
             required_value,
+            coercion_policy,
         }
     }
 }
@@ -33,17 +39,41 @@ impl Filter for StateEqualToConstFilter {
         state_manager: &mut StateManager,
     ) -> Result<bool, FilterError> {
         match state_manager.get_value(self.state_index) {
-            Some(Value::U8(value)) => Ok(Value::U8(*value) == self.required_value),
-            Some(Value::U16(value)) => Ok(Value::U16(*value) == self.required_value),
-            Some(Value::U32(value)) => Ok(Value::U32(*value) == self.required_value),
-            Some(Value::Bool(value)) => Ok(Value::Bool(*value) == self.required_value),
-            _ => Err(FilterError::WrongStateType),
+            Some(Value::Rgb(_, _, _))
+            | Some(Value::RgbB(_, _, _, _))
+            | Some(Value::Rgbw(_, _, _, _))
+            | Some(Value::RgbwB(_, _, _, _, _))
+            | None => Err(FilterError::WrongStateType),
+            Some(value) => Ok(self.coercion_policy.equals(value, &self.required_value)),
         }
     }
 
     fn get_code(&self) -> u16 {
         STATE_EQUAL_TO_CONST_FILTER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        let accepted_types = match self.required_value {
+            Value::U8(_)
+            | Value::U16(_)
+            | Value::U32(_)
+            | Value::Bool(_)
+            | Value::I8(_)
+            | Value::I16(_)
+            | Value::I32(_)
+            | Value::Fixed(_)
+            | Value::Bytes(_) => self
+                .coercion_policy
+                .get_accepted_value_types(self.required_value.get_type()),
+            _ => vec![],
+        };
+
+        vec![StateAccess::Read(self.state_index, accepted_types)]
+    }
 }
 
 impl Serialize for StateEqualToConstFilter {
@@ -60,6 +90,7 @@ impl Serialize for StateEqualToConstFilter {
         let mut required_value_bytes = self.required_value.serialize();
 
         data.append(&mut required_value_bytes);
+        data.append(&mut self.coercion_policy.serialize());
 
         return data;
     }
@@ -73,10 +104,13 @@ impl TryDeserialize for StateEqualToConstFilter {
 
         let state_index = u32::from_be_bytes(data[0..=3].try_into().unwrap());
         let required_value = *Value::try_deserialize(&data[4..])?;
+        let coercion_policy =
+            try_deserialize_coercion_policy(data, 4 + required_value.serialize().len())?;
 
         Ok(Box::new(Self {
             state_index,
             required_value,
+            coercion_policy,
         }))
     }
 }
@@ -90,7 +124,8 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0x0000_0000));
 
-        let mut filter = StateEqualToConstFilter::new(0, Value::U32(0x0000_0000));
+        let mut filter =
+            StateEqualToConstFilter::new(0, Value::U32(0x0000_0000), CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -103,7 +138,8 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0xffff_ffff));
 
-        let mut filter = StateEqualToConstFilter::new(0, Value::U32(0x0000_0000));
+        let mut filter =
+            StateEqualToConstFilter::new(0, Value::U32(0x0000_0000), CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -116,7 +152,7 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0xffff_ffff));
 
-        let mut filter = StateEqualToConstFilter::new(0, Value::U8(0x00));
+        let mut filter = StateEqualToConstFilter::new(0, Value::U8(0x00), CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -128,7 +164,8 @@ mod tests {
     fn wrong_state_type_test() {
         let mut state_manager = StateManager::new();
 
-        let mut filter = StateEqualToConstFilter::new(0, Value::U32(0x0000_0000));
+        let mut filter =
+            StateEqualToConstFilter::new(0, Value::U32(0x0000_0000), CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -138,9 +175,17 @@ mod tests {
 
     #[test]
     fn serialize_test() {
-        let filter = StateEqualToConstFilter::new(0xabab_abab, Value::U32(0xffff_ffff));
+        let filter = StateEqualToConstFilter::new(
+            0xabab_abab,
+            Value::U32(0xffff_ffff),
+            CoercionPolicy::Strict,
+        );
 
-        let expected_data = vec![0xab, 0xab, 0xab, 0xab, 0x02, 0xff, 0xff, 0xff, 0xff];
+        let expected_data = vec![
+            0xab, 0xab, 0xab, 0xab, // state index
+            0x02, 0xff, 0xff, 0xff, 0xff, // required value
+            0x00, // coercion policy
+        ];
 
         assert_eq!(filter.serialize(), expected_data);
     }
@@ -152,6 +197,7 @@ mod tests {
         let filter = Box::new(StateEqualToConstFilter::new(
             0xabab_abab,
             Value::U32(0xffff_ffff),
+            CoercionPolicy::Strict,
         ));
 
         assert_eq!(StateEqualToConstFilter::try_deserialize(&data), Ok(filter));
@@ -5,23 +5,29 @@ use alloc::vec;
 use alloc::vec::Vec;
 use core::convert::TryInto;
 
-use crate::filter::{Filter, FilterError, STATE_EQUAL_TO_STATE_FILTER_CODE};
+use crate::filter::{
+    try_deserialize_coercion_policy, CoercionPolicy, Filter, FilterError,
+    STATE_EQUAL_TO_STATE_FILTER_CODE,
+};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::ExtractorValue;
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, ValueType};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
 pub struct StateEqualToStateFilter {
     state_index: u32,
     target_state_index: u32,
+    coercion_policy: CoercionPolicy,
 }
 
 impl StateEqualToStateFilter {
-    pub fn new(state_index: u32, target_state_index: u32) -> Self {
+    pub fn new(state_index: u32, target_state_index: u32, coercion_policy: CoercionPolicy) -> Self {
         Self {
             state_index,
             target_state_index,
+            coercion_policy,
         }
     }
 }
@@ -38,7 +44,7 @@ impl Filter for StateEqualToStateFilter {
         };
 
         match state_manager.get_value(self.target_state_index) {
-            Some(value) => Ok(value == current_state),
+            Some(value) => Ok(self.coercion_policy.equals(current_state, value)),
             _ => Err(FilterError::WrongStateType),
         }
     }
@@ -46,6 +52,22 @@ impl Filter for StateEqualToStateFilter {
     fn get_code(&self) -> u16 {
         STATE_EQUAL_TO_STATE_FILTER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![
+            StateAccess::Read(self.state_index, ValueType::all()),
+            StateAccess::Read(self.target_state_index, ValueType::all()),
+            StateAccess::Compare(
+                self.state_index,
+                self.target_state_index,
+                self.coercion_policy,
+            ),
+        ]
+    }
 }
 
 impl Serialize for StateEqualToStateFilter {
@@ -53,7 +75,7 @@ impl Serialize for StateEqualToStateFilter {
         let state_index = self.state_index.to_be_bytes();
         let target_state_index = self.target_state_index.to_be_bytes();
 
-        vec![
+        let mut data = vec![
             state_index[0],
             state_index[1],
             state_index[2],
@@ -62,7 +84,11 @@ impl Serialize for StateEqualToStateFilter {
             target_state_index[1],
             target_state_index[2],
             target_state_index[3],
-        ]
+        ];
+
+        data.append(&mut self.coercion_policy.serialize());
+
+        data
     }
 }
 
@@ -74,10 +100,12 @@ impl TryDeserialize for StateEqualToStateFilter {
 
         let state_index = u32::from_be_bytes(data[0..=3].try_into().unwrap());
         let target_state_index = u32::from_be_bytes(data[4..=7].try_into().unwrap());
+        let coercion_policy = try_deserialize_coercion_policy(data, 8)?;
 
         Ok(Box::new(Self {
             state_index,
             target_state_index,
+            coercion_policy,
         }))
     }
 }
@@ -94,7 +122,7 @@ mod tests {
         state_manager.set_value(0, Value::U32(0x0000_0000));
         state_manager.set_value(1, Value::U32(0x0000_0000));
 
-        let mut filter = StateEqualToStateFilter::new(0, 1);
+        let mut filter = StateEqualToStateFilter::new(0, 1, CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -108,7 +136,7 @@ mod tests {
         state_manager.set_value(0, Value::U32(0xffff_ffff));
         state_manager.set_value(1, Value::U32(0xabab_abab));
 
-        let mut filter = StateEqualToStateFilter::new(0, 1);
+        let mut filter = StateEqualToStateFilter::new(0, 1, CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -122,7 +150,7 @@ mod tests {
         state_manager.set_value(0, Value::U32(0x0000_0000));
         state_manager.set_value(1, Value::U16(0x0000));
 
-        let mut filter = StateEqualToStateFilter::new(0, 1);
+        let mut filter = StateEqualToStateFilter::new(0, 1, CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -132,9 +160,9 @@ mod tests {
 
     #[test]
     fn serialize_test() {
-        let filter = StateEqualToStateFilter::new(0xabab_abab, 0xffff_ffff);
+        let filter = StateEqualToStateFilter::new(0xabab_abab, 0xffff_ffff, CoercionPolicy::Strict);
 
-        let expected_data = vec![0xab, 0xab, 0xab, 0xab, 0xff, 0xff, 0xff, 0xff];
+        let expected_data = vec![0xab, 0xab, 0xab, 0xab, 0xff, 0xff, 0xff, 0xff, 0x00];
 
         assert_eq!(filter.serialize(), expected_data);
     }
@@ -143,7 +171,11 @@ mod tests {
     fn deserialize_test() {
         let data = vec![0xab, 0xab, 0xab, 0xab, 0xff, 0xff, 0xff, 0xff];
 
-        let filter = Box::new(StateEqualToStateFilter::new(0xabab_abab, 0xffff_ffff));
+        let filter = Box::new(StateEqualToStateFilter::new(
+            0xabab_abab,
+            0xffff_ffff,
+            CoercionPolicy::Strict,
+        ));
 
         assert_eq!(StateEqualToStateFilter::try_deserialize(&data), Ok(filter));
     }
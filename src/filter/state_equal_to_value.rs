@@ -3,22 +3,30 @@ extern crate alloc;
 use alloc::boxed::Box;
 use alloc::vec;
 use alloc::vec::Vec;
-use core::convert::TryInto;
+use core::convert::{TryFrom, TryInto};
 
-use crate::filter::{Filter, FilterError, STATE_EQUAL_TO_VALUE_FILTER_CODE};
+use crate::filter::{
+    try_deserialize_coercion_policy, CoercionPolicy, Filter, FilterError,
+    STATE_EQUAL_TO_VALUE_FILTER_CODE,
+};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::{ExtractorValue, Value};
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value, ValueType};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
 pub struct StateEqualToValueFilter {
     state_index: u32,
+    coercion_policy: CoercionPolicy,
 }
 
 impl StateEqualToValueFilter {
-    pub fn new(state_index: u32) -> Self {
-        Self { state_index }
+    pub fn new(state_index: u32, coercion_policy: CoercionPolicy) -> Self {
+        Self {
+            state_index,
+            coercion_policy,
+        }
     }
 }
 
@@ -29,37 +37,57 @@ impl Filter for StateEqualToValueFilter {
         state_manager: &mut StateManager,
     ) -> Result<bool, FilterError> {
         let current_state = match state_manager.get_value(self.state_index) {
-            Some(Value::U8(value)) => Value::U8(*value),
-            Some(Value::U16(value)) => Value::U16(*value),
-            Some(Value::U32(value)) => Value::U32(*value),
-            Some(Value::Bool(value)) => Value::Bool(*value),
-            _ => return Err(FilterError::WrongStateType),
+            Some(current_state) => current_state,
+            None => return Err(FilterError::WrongStateType),
         };
 
-        match *value {
-            ExtractorValue::U8(value) => Ok(Value::U8(value) == current_state),
-            ExtractorValue::U16(value) => Ok(Value::U16(value) == current_state),
-            ExtractorValue::U32(value) => Ok(Value::U32(value) == current_state),
-            ExtractorValue::Bool(value) => Ok(Value::Bool(value) == current_state),
-            _ => Err(FilterError::WrongValueType),
+        match Value::try_from(value) {
+            Ok(value) => Ok(self.coercion_policy.equals(current_state, &value)),
+            Err(_) => Err(FilterError::WrongValueType),
         }
     }
 
     fn get_code(&self) -> u16 {
         STATE_EQUAL_TO_VALUE_FILTER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        vec![
+            ExtractorValueType::U8,
+            ExtractorValueType::U16,
+            ExtractorValueType::U32,
+            ExtractorValueType::Bool,
+            ExtractorValueType::I8,
+            ExtractorValueType::I16,
+            ExtractorValueType::I32,
+            ExtractorValueType::Fixed,
+            ExtractorValueType::Bytes,
+            ExtractorValueType::Rgb,
+            ExtractorValueType::RgbB,
+            ExtractorValueType::Rgbw,
+            ExtractorValueType::RgbwB,
+        ]
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![StateAccess::Read(self.state_index, ValueType::all())]
+    }
 }
 
 impl Serialize for StateEqualToValueFilter {
     fn serialize(&self) -> Vec<u8> {
         let state_index = self.state_index.to_be_bytes();
 
-        vec![
+        let mut data = vec![
             state_index[0],
             state_index[1],
             state_index[2],
             state_index[3],
-        ]
+        ];
+
+        data.append(&mut self.coercion_policy.serialize());
+
+        data
     }
 }
 
@@ -70,8 +98,12 @@ impl TryDeserialize for StateEqualToValueFilter {
         }
 
         let state_index = u32::from_be_bytes(data[0..=3].try_into().unwrap());
+        let coercion_policy = try_deserialize_coercion_policy(data, 4)?;
 
-        Ok(Box::new(Self { state_index }))
+        Ok(Box::new(Self {
+            state_index,
+            coercion_policy,
+        }))
     }
 }
 
@@ -84,7 +116,7 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0x0000_0000));
 
-        let mut filter = StateEqualToValueFilter::new(0);
+        let mut filter = StateEqualToValueFilter::new(0, CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::U32(0x0000_0000), &mut state_manager),
@@ -97,7 +129,7 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0xffff_ffff));
 
-        let mut filter = StateEqualToValueFilter::new(0);
+        let mut filter = StateEqualToValueFilter::new(0, CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::U32(0x0000_0000), &mut state_manager),
@@ -105,12 +137,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bytes_equal_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::try_from_bytes(&[0x61, 0x62]).unwrap());
+
+        let mut filter = StateEqualToValueFilter::new(0, CoercionPolicy::Strict);
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::Bytes(&[0x61, 0x62]), &mut state_manager),
+            Ok(true)
+        );
+        assert_eq!(
+            filter.filter(&ExtractorValue::Bytes(&[0x61]), &mut state_manager),
+            Ok(false)
+        );
+    }
+
     #[test]
     fn value_types_not_equal_test() {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0x0000_0000));
 
-        let mut filter = StateEqualToValueFilter::new(0);
+        let mut filter = StateEqualToValueFilter::new(0, CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::U8(0x00), &mut state_manager),
@@ -118,11 +167,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn widen_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U32(0x0000_0005));
+
+        let mut filter = StateEqualToValueFilter::new(0, CoercionPolicy::Widen);
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::U8(0x05), &mut state_manager),
+            Ok(true)
+        );
+        assert_eq!(
+            filter.filter(&ExtractorValue::Fixed(0x0005_0000), &mut state_manager),
+            Ok(true)
+        );
+    }
+
     #[test]
     fn wrong_state_type_test() {
         let mut state_manager = StateManager::new();
 
-        let mut filter = StateEqualToValueFilter::new(0);
+        let mut filter = StateEqualToValueFilter::new(0, CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -135,7 +201,7 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0x0000_0000));
 
-        let mut filter = StateEqualToValueFilter::new(0);
+        let mut filter = StateEqualToValueFilter::new(0, CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -145,9 +211,9 @@ mod tests {
 
     #[test]
     fn serialize_test() {
-        let filter = StateEqualToValueFilter::new(0xabab_abab);
+        let filter = StateEqualToValueFilter::new(0xabab_abab, CoercionPolicy::Strict);
 
-        let expected_data = vec![0xab, 0xab, 0xab, 0xab];
+        let expected_data = vec![0xab, 0xab, 0xab, 0xab, 0x00];
 
         assert_eq!(filter.serialize(), expected_data);
     }
@@ -156,7 +222,10 @@ mod tests {
     fn deserialize_test() {
         let data = vec![0xab, 0xab, 0xab, 0xab];
 
-        let filter = Box::new(StateEqualToValueFilter::new(0xabab_abab));
+        let filter = Box::new(StateEqualToValueFilter::new(
+            0xabab_abab,
+            CoercionPolicy::Strict,
+        ));
 
         assert_eq!(StateEqualToValueFilter::try_deserialize(&data), Ok(filter));
     }
@@ -3,25 +3,32 @@ extern crate alloc;
 use alloc::boxed::Box;
 use alloc::vec;
 use alloc::vec::Vec;
+use core::cmp::Ordering;
 use core::convert::TryInto;
 
-use crate::filter::{Filter, FilterError, STATE_MORE_THAN_CONST_FILTER_CODE};
+use crate::filter::{
+    try_deserialize_coercion_policy, CoercionPolicy, Filter, FilterError,
+    STATE_MORE_THAN_CONST_FILTER_CODE,
+};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::{ExtractorValue, Value};
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
 pub struct StateMoreThanConstFilter {
     state_index: u32,
     required_value: Value,
+    coercion_policy: CoercionPolicy,
 }
 
 impl StateMoreThanConstFilter {
-    pub fn new(state_index: u32, required_value: Value) -> Self {
+    pub fn new(state_index: u32, required_value: Value, coercion_policy: CoercionPolicy) -> Self {
         Self {
             state_index,
             required_value,
+            coercion_policy,
         }
     }
 }
@@ -32,20 +39,44 @@ impl Filter for StateMoreThanConstFilter {
         _value: &ExtractorValue,
         state_manager: &mut StateManager,
     ) -> Result<bool, FilterError> {
-        match (
-            state_manager.get_value(self.state_index),
-            &self.required_value,
-        ) {
-            (Some(Value::U8(value)), Value::U8(required_value)) => Ok(value > required_value),
-            (Some(Value::U16(value)), Value::U16(required_value)) => Ok(value > required_value),
-            (Some(Value::U32(value)), Value::U32(required_value)) => Ok(value > required_value),
-            _ => Err(FilterError::WrongStateType),
+        let current_state = match state_manager.get_value(self.state_index) {
+            Some(current_state) => current_state,
+            None => return Err(FilterError::WrongStateType),
+        };
+
+        match self
+            .coercion_policy
+            .compare_numbers(current_state, &self.required_value)
+        {
+            Some(ordering) => Ok(ordering == Ordering::Greater),
+            None => Err(FilterError::WrongStateType),
         }
     }
 
     fn get_code(&self) -> u16 {
         STATE_MORE_THAN_CONST_FILTER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        let accepted_types = match self.required_value {
+            Value::U8(_)
+            | Value::U16(_)
+            | Value::U32(_)
+            | Value::I8(_)
+            | Value::I16(_)
+            | Value::I32(_)
+            | Value::Fixed(_) => self
+                .coercion_policy
+                .get_accepted_value_types(self.required_value.get_type()),
+            _ => vec![],
+        };
+
+        vec![StateAccess::Read(self.state_index, accepted_types)]
+    }
 }
 
 impl Serialize for StateMoreThanConstFilter {
@@ -62,6 +93,7 @@ impl Serialize for StateMoreThanConstFilter {
         let mut required_value_bytes = self.required_value.serialize();
 
         data.append(&mut required_value_bytes);
+        data.append(&mut self.coercion_policy.serialize());
 
         return data;
     }
@@ -75,10 +107,13 @@ impl TryDeserialize for StateMoreThanConstFilter {
 
         let state_index = u32::from_be_bytes(data[0..=3].try_into().unwrap());
         let required_value = *Value::try_deserialize(&data[4..])?;
+        let coercion_policy =
+            try_deserialize_coercion_policy(data, 4 + required_value.serialize().len())?;
 
         Ok(Box::new(Self {
             state_index,
             required_value,
+            coercion_policy,
         }))
     }
 }
@@ -92,7 +127,8 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0xffff_ffff));
 
-        let mut filter = StateMoreThanConstFilter::new(0, Value::U32(0x0000_0000));
+        let mut filter =
+            StateMoreThanConstFilter::new(0, Value::U32(0x0000_0000), CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -105,7 +141,8 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0xffff_ffff));
 
-        let mut filter = StateMoreThanConstFilter::new(0, Value::U32(0xffff_ffff));
+        let mut filter =
+            StateMoreThanConstFilter::new(0, Value::U32(0xffff_ffff), CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -118,7 +155,8 @@ mod tests {
         let mut state_manager = StateManager::new();
         state_manager.set_value(0, Value::U32(0x0000_0000));
 
-        let mut filter = StateMoreThanConstFilter::new(0, Value::U32(0xffff_ffff));
+        let mut filter =
+            StateMoreThanConstFilter::new(0, Value::U32(0xffff_ffff), CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -126,11 +164,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn widen_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U8(0x10));
+
+        let mut filter = StateMoreThanConstFilter::new(0, Value::I16(-1), CoercionPolicy::Widen);
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+    }
+
     #[test]
     fn wrong_state_type_test() {
         let mut state_manager = StateManager::new();
 
-        let mut filter = StateMoreThanConstFilter::new(0, Value::U32(0x0000_0000));
+        let mut filter =
+            StateMoreThanConstFilter::new(0, Value::U32(0x0000_0000), CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::None, &mut state_manager),
@@ -140,9 +192,17 @@ mod tests {
 
     #[test]
     fn serialize_test() {
-        let filter = StateMoreThanConstFilter::new(0xabab_abab, Value::U32(0xffff_ffff));
+        let filter = StateMoreThanConstFilter::new(
+            0xabab_abab,
+            Value::U32(0xffff_ffff),
+            CoercionPolicy::Strict,
+        );
 
-        let expected_data = vec![0xab, 0xab, 0xab, 0xab, 0x02, 0xff, 0xff, 0xff, 0xff];
+        let expected_data = vec![
+            0xab, 0xab, 0xab, 0xab, // state index
+            0x02, 0xff, 0xff, 0xff, 0xff, // required value
+            0x00, // coercion policy
+        ];
 
         assert_eq!(filter.serialize(), expected_data);
     }
@@ -154,6 +214,7 @@ mod tests {
         let filter = Box::new(StateMoreThanConstFilter::new(
             0xabab_abab,
             Value::U32(0xffff_ffff),
+            CoercionPolicy::Strict,
         ));
 
         assert_eq!(StateMoreThanConstFilter::try_deserialize(&data), Ok(filter));
@@ -0,0 +1,274 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::filter::{
+    has_elapsed, serialize_timestamp, try_deserialize_timestamp, Filter, FilterError,
+    THROTTLE_FILTER_CODE,
+};
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::state_manager::StateManager;
+use crate::validator::StateAccess;
+use crate::{serialize_integer_to_vec, try_deserialize_integer_from_vec};
+use crate::{ExtractorValue, ExtractorValueType};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThrottleMode {
+    // The first event passes and starts the interval
+    Leading,
+    // The first event starts the interval and the first event after it passes. Filters only run
+    // on events, so nothing passes until one arrives after the interval.
+    Trailing,
+}
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub struct ThrottleFilter {
+    // Milliseconds between passing events
+    interval: u32,
+    mode: ThrottleMode,
+    // Milliseconds since the Unix epoch, the last pass in leading mode and the start of the
+    // interval in trailing mode
+    timestamp: Option<i64>,
+}
+
+impl ThrottleFilter {
+    pub fn new(interval: u32, mode: ThrottleMode) -> Self {
+        Self {
+            interval,
+            mode,
+            timestamp: None,
+        }
+    }
+}
+
+impl Filter for ThrottleFilter {
+    fn filter(
+        &mut self,
+        _value: &ExtractorValue,
+        state_manager: &mut StateManager,
+    ) -> Result<bool, FilterError> {
+        let now = state_manager.get_date_time().timestamp_millis();
+        let has_elapsed = has_elapsed(self.timestamp, now, self.interval);
+
+        match self.mode {
+            ThrottleMode::Leading if has_elapsed => {
+                self.timestamp = Some(now);
+
+                Ok(true)
+            }
+            ThrottleMode::Trailing if self.timestamp.is_none() => {
+                self.timestamp = Some(now);
+
+                Ok(false)
+            }
+            ThrottleMode::Trailing if has_elapsed => {
+                self.timestamp = None;
+
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn get_code(&self) -> u16 {
+        THROTTLE_FILTER_CODE
+    }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![]
+    }
+}
+
+impl Serialize for ThrottleMode {
+    fn serialize(&self) -> Vec<u8> {
+        match self {
+            ThrottleMode::Leading => vec![0x00],
+            ThrottleMode::Trailing => vec![0x01],
+        }
+    }
+}
+
+impl TryDeserialize for ThrottleMode {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.is_empty() {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        match data[0] {
+            0x00 => Ok(Box::new(ThrottleMode::Leading)),
+            0x01 => Ok(Box::new(ThrottleMode::Trailing)),
+            _ => Err(ConfigSerializerError::UnknownEnumVariant),
+        }
+    }
+}
+
+impl Serialize for ThrottleFilter {
+    fn serialize(&self) -> Vec<u8> {
+        let mut data = vec![];
+
+        serialize_integer_to_vec!(data, self.interval, u32);
+        data.append(&mut self.mode.serialize());
+        serialize_timestamp(&mut data, self.timestamp);
+
+        data
+    }
+}
+
+impl TryDeserialize for ThrottleFilter {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        let mut offset = 0;
+
+        let interval = try_deserialize_integer_from_vec!(data, offset, u32);
+        let mode = *ThrottleMode::try_deserialize(&data[offset..])?;
+        let timestamp = try_deserialize_timestamp(&data[offset + 1..])?;
+
+        Ok(Box::new(Self {
+            interval,
+            mode,
+            timestamp,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::DateTime;
+    use core::str::FromStr;
+
+    fn filter_at(
+        filter: &mut ThrottleFilter,
+        state_manager: &mut StateManager,
+        date_time: &str,
+    ) -> Result<bool, FilterError> {
+        state_manager.set_date_time(DateTime::from_str(date_time).unwrap());
+        filter.filter(&ExtractorValue::None, state_manager)
+    }
+
+    #[test]
+    fn leading_test() {
+        let mut state_manager = StateManager::new();
+
+        let mut filter = ThrottleFilter::new(1000, ThrottleMode::Leading);
+
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00Z"),
+            Ok(true)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00.4Z"),
+            Ok(false)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00.8Z"),
+            Ok(false)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:01Z"),
+            Ok(true)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:01.2Z"),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn trailing_test() {
+        let mut state_manager = StateManager::new();
+
+        let mut filter = ThrottleFilter::new(1000, ThrottleMode::Trailing);
+
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00Z"),
+            Ok(false)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00.5Z"),
+            Ok(false)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:01.1Z"),
+            Ok(true)
+        );
+        // Starts the next interval
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:01.2Z"),
+            Ok(false)
+        );
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:02.2Z"),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let mut state_manager = StateManager::new();
+
+        let mut filter = ThrottleFilter::new(1000, ThrottleMode::Leading);
+
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00Z"),
+            Ok(true)
+        );
+
+        let mut filter = ThrottleFilter::try_deserialize(&filter.serialize()).unwrap();
+
+        assert_eq!(
+            filter_at(&mut filter, &mut state_manager, "2022-01-03T06:30:00.5Z"),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn serialize_test() {
+        let mut filter = ThrottleFilter::new(0x0000_03e8, ThrottleMode::Trailing);
+        filter.timestamp = Some(0x0000_017e_2000_0000);
+
+        let expected_data = vec![
+            0x00, 0x00, 0x03, 0xe8, // interval
+            0x01, // mode
+            0x01, 0x00, 0x00, 0x01, 0x7e, 0x20, 0x00, 0x00, 0x00, // timestamp
+        ];
+
+        assert_eq!(filter.serialize(), expected_data);
+    }
+
+    #[test]
+    fn deserialize_test() {
+        let data = vec![0x00, 0x00, 0x03, 0xe8, 0x00, 0x00];
+
+        let filter = Box::new(ThrottleFilter::new(0x0000_03e8, ThrottleMode::Leading));
+
+        assert_eq!(ThrottleFilter::try_deserialize(&data), Ok(filter));
+    }
+
+    #[test]
+    fn deserialize_wrong_size_test() {
+        let data = vec![0x00, 0x00, 0x03, 0xe8, 0x00];
+
+        assert_eq!(
+            ThrottleFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::WrongSize)
+        );
+    }
+
+    #[test]
+    fn deserialize_unknown_mode_test() {
+        let data = vec![0x00, 0x00, 0x03, 0xe8, 0x02, 0x00];
+
+        assert_eq!(
+            ThrottleFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::UnknownEnumVariant)
+        );
+    }
+}
@@ -1,13 +1,15 @@
 extern crate alloc;
 
 use alloc::boxed::Box;
+use alloc::vec;
 use alloc::vec::Vec;
 
 use crate::cron::CronExpression;
 use crate::filter::{Filter, FilterError, TIME_MATCHES_CRON_EXPRESSION_FILTER_CODE};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::ExtractorValue;
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -27,12 +29,25 @@ impl Filter for TimeMatchesCronExpressionFilter {
         _value: &ExtractorValue,
         state_manager: &mut StateManager,
     ) -> Result<bool, FilterError> {
-        Ok(self.expression.do_match(state_manager.get_date_time()))
+        let date_time = state_manager.get_date_time();
+
+        match state_manager.get_time_zone() {
+            Some(time_zone) => Ok(self.expression.do_match_in_time_zone(date_time, time_zone)),
+            None => Ok(self.expression.do_match(date_time)),
+        }
     }
 
     fn get_code(&self) -> u16 {
         TIME_MATCHES_CRON_EXPRESSION_FILTER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![]
+    }
 }
 
 impl Serialize for TimeMatchesCronExpressionFilter {
@@ -45,6 +60,10 @@ impl TryDeserialize for TimeMatchesCronExpressionFilter {
     fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
         let expression = *CronExpression::try_deserialize(data)?;
 
+        if let Err(err) = expression.validate() {
+            return Err(ConfigSerializerError::InvalidCronExpression(err));
+        }
+
         Ok(Box::new(Self { expression }))
     }
 }
@@ -58,7 +77,29 @@ mod tests {
     use chrono::DateTime;
     use core::str::FromStr;
 
-    use crate::cron::CronField;
+    use crate::cron::{CronExpressionError, CronField};
+    use crate::time_zone::{DstRule, DstTransition, TimeZone};
+
+    fn central_european_time() -> TimeZone {
+        TimeZone {
+            offset: 3600,
+            dst: Some(DstRule {
+                offset: 7200,
+                start: DstTransition {
+                    month: 3,
+                    week: 5,
+                    weekday: 7,
+                    time: 7200,
+                },
+                end: DstTransition {
+                    month: 10,
+                    week: 5,
+                    weekday: 7,
+                    time: 10800,
+                },
+            }),
+        }
+    }
 
     #[test]
     fn time_matches_test() {
@@ -162,7 +203,7 @@ mod tests {
         let data = vec![
             4, 2, 1, 0, 5, // second
             5, 0, 3, 9, 15, 32, // minute
-            5, 1, 3, 9, 15, 32, // hour
+            5, 1, 3, 9, 15, 23, // hour
             1, 3, // day (month)
             1, 3, // month
             1, 3, // day (week)
@@ -175,7 +216,7 @@ mod tests {
         expected_included_values.insert(15);
 
         let mut expected_excluded_values = BTreeSet::new();
-        expected_excluded_values.insert(32);
+        expected_excluded_values.insert(23);
         expected_excluded_values.insert(9);
         expected_excluded_values.insert(15);
 
@@ -216,4 +257,88 @@ mod tests {
             Err(ConfigSerializerError::WrongSize),
         );
     }
+
+    #[test]
+    fn deserialize_invalid_expression_test() {
+        let data = vec![
+            4, 2, 1, 0, 5, // second
+            5, 0, 3, 9, 15, 75, // minute
+            1, 3, // hour
+            1, 3, // day (month)
+            1, 3, // month
+            1, 3, // day (week)
+            1, 3, // year
+        ];
+
+        assert_eq!(
+            TimeMatchesCronExpressionFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidCronExpression(
+                CronExpressionError::InvalidMinute
+            ))
+        );
+    }
+
+    #[test]
+    fn time_matches_in_time_zone_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_time_zone(Some(central_european_time()));
+
+        let mut filter = TimeMatchesCronExpressionFilter::new(
+            CronExpression::from_str("0 30 6 * * MON-FRI *").unwrap(),
+        );
+
+        state_manager.set_date_time(DateTime::from_str("2022-07-15T04:30:00Z").unwrap());
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+
+        state_manager.set_date_time(DateTime::from_str("2022-01-14T05:30:00Z").unwrap());
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn skipped_time_matches_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_time_zone(Some(central_european_time()));
+
+        let mut filter =
+            TimeMatchesCronExpressionFilter::new(CronExpression::from_str("0 30 2 * * *").unwrap());
+
+        // 02:30 does not exist on that day, so the expression matches at 03:30 instead
+        state_manager.set_date_time(DateTime::from_str("2022-03-27T01:30:00Z").unwrap());
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn repeated_time_matches_once_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_time_zone(Some(central_european_time()));
+
+        let mut filter =
+            TimeMatchesCronExpressionFilter::new(CronExpression::from_str("0 30 2 * * *").unwrap());
+
+        state_manager.set_date_time(DateTime::from_str("2022-10-30T00:30:00Z").unwrap());
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+
+        state_manager.set_date_time(DateTime::from_str("2022-10-30T01:30:00Z").unwrap());
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(false)
+        );
+    }
 }
@@ -0,0 +1,264 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use chrono::Duration;
+use core::convert::TryFrom;
+
+use crate::filter::{Filter, FilterError, TIME_MATCHES_SOLAR_EVENT_FILTER_CODE};
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::solar::SolarEvent;
+use crate::state_manager::StateManager;
+use crate::validator::StateAccess;
+use crate::{serialize_integer_to_vec, try_deserialize_integer_from_vec};
+use crate::{ExtractorValue, ExtractorValueType};
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub struct TimeMatchesSolarEventFilter {
+    latitude: f32,
+    longitude: f32,
+    event: SolarEvent,
+    // Seconds added to the event time, negative values match before the event
+    offset: i32,
+    // Seconds after the shifted event time during which the filter keeps matching
+    window: u32,
+}
+
+impl TimeMatchesSolarEventFilter {
+    pub fn new(latitude: f32, longitude: f32, event: SolarEvent, offset: i32, window: u32) -> Self {
+        Self {
+            latitude,
+            longitude,
+            event,
+            offset,
+            window,
+        }
+    }
+}
+
+impl Filter for TimeMatchesSolarEventFilter {
+    fn filter(
+        &mut self,
+        _value: &ExtractorValue,
+        state_manager: &mut StateManager,
+    ) -> Result<bool, FilterError> {
+        let date_time = *state_manager.get_date_time();
+        let date = date_time.naive_utc().date();
+
+        // Offsets and longitudes far from Greenwich can move the event onto a neighbouring day
+        for date in [date.pred_opt(), Some(date), date.succ_opt()]
+            .iter()
+            .flatten()
+        {
+            let event_date_time =
+                match self
+                    .event
+                    .get_date_time(self.latitude as f64, self.longitude as f64, date)
+                {
+                    Some(event_date_time) => event_date_time,
+                    None => continue,
+                };
+
+            let from = event_date_time + Duration::seconds(self.offset as i64);
+            let to = from + Duration::seconds(self.window as i64);
+
+            if date_time >= from && date_time < to {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn get_code(&self) -> u16 {
+        TIME_MATCHES_SOLAR_EVENT_FILTER_CODE
+    }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![]
+    }
+}
+
+impl Serialize for TimeMatchesSolarEventFilter {
+    fn serialize(&self) -> Vec<u8> {
+        let mut data = vec![];
+
+        serialize_integer_to_vec!(data, self.latitude.to_bits(), u32);
+        serialize_integer_to_vec!(data, self.longitude.to_bits(), u32);
+        data.push(self.event as u8);
+        serialize_integer_to_vec!(data, self.offset, i32);
+        serialize_integer_to_vec!(data, self.window, u32);
+
+        data
+    }
+}
+
+impl TryDeserialize for TimeMatchesSolarEventFilter {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.len() < 17 {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        let mut offset = 0;
+
+        let latitude = f32::from_bits(try_deserialize_integer_from_vec!(data, offset, u32));
+        let longitude = f32::from_bits(try_deserialize_integer_from_vec!(data, offset, u32));
+        let event = SolarEvent::try_from(try_deserialize_integer_from_vec!(data, offset, u8))?;
+        let event_offset = try_deserialize_integer_from_vec!(data, offset, i32);
+        #[allow(unused_assignments)]
+        let window = try_deserialize_integer_from_vec!(data, offset, u32);
+
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return Err(ConfigSerializerError::InvalidCoordinates);
+        }
+
+        Ok(Box::new(Self {
+            latitude,
+            longitude,
+            event,
+            offset: event_offset,
+            window,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::DateTime;
+    use core::str::FromStr;
+
+    #[test]
+    fn after_sunset_test() {
+        let mut state_manager = StateManager::new();
+
+        // Sunset in Vilnius is at 19:00 UTC on that day
+        let mut filter =
+            TimeMatchesSolarEventFilter::new(54.6872, 25.2797, SolarEvent::Sunset, 1800, 60);
+
+        state_manager.set_date_time(DateTime::from_str("2022-06-21T19:30:30Z").unwrap());
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+
+        state_manager.set_date_time(DateTime::from_str("2022-06-21T19:05:00Z").unwrap());
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(false)
+        );
+
+        state_manager.set_date_time(DateTime::from_str("2022-06-21T19:35:00Z").unwrap());
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn event_on_previous_utc_day_test() {
+        let mut state_manager = StateManager::new();
+
+        // Sunset in Los Angeles is around 03:08 UTC on the following day
+        let mut filter =
+            TimeMatchesSolarEventFilter::new(34.0522, -118.2437, SolarEvent::Sunset, 0, 600);
+
+        state_manager.set_date_time(DateTime::from_str("2022-06-22T03:12:00Z").unwrap());
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn polar_day_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_date_time(DateTime::from_str("2022-06-21T22:00:00Z").unwrap());
+
+        let mut filter =
+            TimeMatchesSolarEventFilter::new(78.2232, 15.6267, SolarEvent::Sunset, 0, 86400);
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::None, &mut state_manager),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn serialize_test() {
+        let filter =
+            TimeMatchesSolarEventFilter::new(54.5, -25.25, SolarEvent::CivilDusk, -900, 60);
+
+        let expected_data = vec![
+            0x42, 0x5a, 0x00, 0x00, // latitude
+            0xc1, 0xca, 0x00, 0x00, // longitude
+            0x03, // event
+            0xff, 0xff, 0xfc, 0x7c, // offset
+            0x00, 0x00, 0x00, 0x3c, // window
+        ];
+
+        assert_eq!(filter.serialize(), expected_data);
+    }
+
+    #[test]
+    fn deserialize_test() {
+        let data = vec![
+            0x42, 0x5a, 0x00, 0x00, // latitude
+            0xc1, 0xca, 0x00, 0x00, // longitude
+            0x03, // event
+            0xff, 0xff, 0xfc, 0x7c, // offset
+            0x00, 0x00, 0x00, 0x3c, // window
+        ];
+
+        let filter =
+            TimeMatchesSolarEventFilter::new(54.5, -25.25, SolarEvent::CivilDusk, -900, 60);
+
+        assert_eq!(
+            TimeMatchesSolarEventFilter::try_deserialize(&data),
+            Ok(Box::new(filter))
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_coordinates_test() {
+        let data = vec![
+            0x42, 0xc8, 0x00, 0x00, // latitude
+            0xc1, 0xca, 0x00, 0x00, // longitude
+            0x03, // event
+            0xff, 0xff, 0xfc, 0x7c, // offset
+            0x00, 0x00, 0x00, 0x3c, // window
+        ];
+
+        assert_eq!(
+            TimeMatchesSolarEventFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidCoordinates)
+        );
+    }
+
+    #[test]
+    fn deserialize_unknown_event_test() {
+        let data = vec![
+            0x42, 0x5a, 0x00, 0x00, // latitude
+            0xc1, 0xca, 0x00, 0x00, // longitude
+            0x04, // event
+            0xff, 0xff, 0xfc, 0x7c, // offset
+            0x00, 0x00, 0x00, 0x3c, // window
+        ];
+
+        assert_eq!(
+            TimeMatchesSolarEventFilter::try_deserialize(&data),
+            Err(ConfigSerializerError::UnknownEnumVariant)
+        );
+    }
+}
@@ -3,21 +3,34 @@ extern crate alloc;
 use alloc::boxed::Box;
 use alloc::vec;
 use alloc::vec::Vec;
+use core::convert::TryFrom;
 
-use crate::filter::{Filter, FilterError, VALUE_EQUAL_TO_CONST_FILTER_CODE};
+use crate::filter::{
+    try_deserialize_coercion_policy, CoercionPolicy, Filter, FilterError,
+    VALUE_EQUAL_TO_CONST_FILTER_CODE,
+};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::{ExtractorValue, Value};
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
 pub struct ValueEqualToConstFilter {
     required_value: Value,
+    coercion_policy: CoercionPolicy,
 }
 
 impl ValueEqualToConstFilter {
-    pub fn new(required_value: Value) -> Self {
-        Self { required_value }
+    pub fn new(required_value: Value, coercion_policy: CoercionPolicy) -> Self {
+        Self {
+            required_value,
+            coercion_policy,
+        }
+    }
+
+    pub fn get_required_value(&self) -> &Value {
+        &self.required_value
     }
 }
 
@@ -27,18 +40,25 @@ impl Filter for ValueEqualToConstFilter {
         value: &ExtractorValue,
         _state_manager: &mut StateManager,
     ) -> Result<bool, FilterError> {
-        match *value {
-            ExtractorValue::U8(value) => Ok(Value::U8(value) == self.required_value),
-            ExtractorValue::U16(value) => Ok(Value::U16(value) == self.required_value),
-            ExtractorValue::U32(value) => Ok(Value::U32(value) == self.required_value),
-            ExtractorValue::Bool(value) => Ok(Value::Bool(value) == self.required_value),
-            _ => Err(FilterError::WrongValueType),
+        match Value::try_from(value) {
+            Ok(value) => Ok(self.coercion_policy.equals(&value, &self.required_value)),
+            Err(_) => Err(FilterError::WrongValueType),
         }
     }
 
     fn get_code(&self) -> u16 {
         VALUE_EQUAL_TO_CONST_FILTER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        self.coercion_policy.get_accepted_extractor_value_types(
+            ExtractorValue::from(&self.required_value).get_type(),
+        )
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![]
+    }
 }
 
 impl Serialize for ValueEqualToConstFilter {
@@ -48,6 +68,7 @@ impl Serialize for ValueEqualToConstFilter {
         let mut required_value = self.required_value.serialize();
 
         data.append(&mut required_value);
+        data.append(&mut self.coercion_policy.serialize());
 
         return data;
     }
@@ -60,8 +81,13 @@ impl TryDeserialize for ValueEqualToConstFilter {
         }
 
         let required_value = *Value::try_deserialize(&data[0..])?;
+        let coercion_policy =
+            try_deserialize_coercion_policy(data, required_value.serialize().len())?;
 
-        Ok(Box::new(Self { required_value }))
+        Ok(Box::new(Self {
+            required_value,
+            coercion_policy,
+        }))
     }
 }
 
@@ -79,7 +105,8 @@ mod tests {
     fn values_equal_test() {
         let mut state_manager = StateManager::new();
 
-        let mut filter = ValueEqualToConstFilter::new(Value::U32(0x0000_0000));
+        let mut filter =
+            ValueEqualToConstFilter::new(Value::U32(0x0000_0000), CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::U32(0x0000_0000), &mut state_manager),
@@ -91,7 +118,8 @@ mod tests {
     fn values_not_equal_test() {
         let mut state_manager = StateManager::new();
 
-        let mut filter = ValueEqualToConstFilter::new(Value::U32(0xffff_ffff));
+        let mut filter =
+            ValueEqualToConstFilter::new(Value::U32(0xffff_ffff), CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::U32(0x0000_0000), &mut state_manager),
@@ -99,11 +127,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bytes_equal_test() {
+        let mut state_manager = StateManager::new();
+
+        let mut filter = ValueEqualToConstFilter::new(
+            Value::try_from_bytes(&[0x61, 0x62]).unwrap(),
+            CoercionPolicy::Strict,
+        );
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::Bytes(&[0x61, 0x62]), &mut state_manager),
+            Ok(true)
+        );
+    }
+
     #[test]
     fn value_types_not_equal_test() {
         let mut state_manager = StateManager::new();
 
-        let mut filter = ValueEqualToConstFilter::new(Value::U32(0x0000_0000));
+        let mut filter =
+            ValueEqualToConstFilter::new(Value::U32(0x0000_0000), CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::U8(0x00), &mut state_manager),
@@ -111,6 +155,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fixed_values_equal_test() {
+        let mut state_manager = StateManager::new();
+
+        let mut filter =
+            ValueEqualToConstFilter::new(Value::Fixed(-0x0001_8000), CoercionPolicy::Strict);
+
+        assert_eq!(
+            filter.filter(&ExtractorValue::Fixed(-0x0001_8000), &mut state_manager),
+            Ok(true)
+        );
+    }
+
     #[test]
     fn wrong_value_type_test() {
         let packet = Packet {
@@ -121,7 +178,8 @@ mod tests {
 
         let mut state_manager = StateManager::new();
 
-        let mut filter = ValueEqualToConstFilter::new(Value::U32(0x0000_0000));
+        let mut filter =
+            ValueEqualToConstFilter::new(Value::U32(0x0000_0000), CoercionPolicy::Strict);
 
         assert_eq!(
             filter.filter(&ExtractorValue::Packet(&packet), &mut state_manager),
@@ -131,9 +189,12 @@ mod tests {
 
     #[test]
     fn serialize_test() {
-        let filter = ValueEqualToConstFilter::new(Value::U32(0xffff_ffff));
+        let filter = ValueEqualToConstFilter::new(Value::U32(0xffff_ffff), CoercionPolicy::Strict);
 
-        let expected_data = vec![0x02, 0xff, 0xff, 0xff, 0xff];
+        let expected_data = vec![
+            0x02, 0xff, 0xff, 0xff, 0xff, // required value
+            0x00, // coercion policy
+        ];
 
         assert_eq!(filter.serialize(), expected_data);
     }
@@ -142,7 +203,10 @@ mod tests {
     fn deserialize_test() {
         let data = vec![0x02, 0xff, 0xff, 0xff, 0xff];
 
-        let filter = Box::new(ValueEqualToConstFilter::new(Value::U32(0xffff_ffff)));
+        let filter = Box::new(ValueEqualToConstFilter::new(
+            Value::U32(0xffff_ffff),
+            CoercionPolicy::Strict,
+        ));
 
         assert_eq!(ValueEqualToConstFilter::try_deserialize(&data), Ok(filter));
     }
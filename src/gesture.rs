@@ -0,0 +1,638 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use chrono::{DateTime, Utc};
+use core::convert::TryInto;
+
+use ross_protocol::convert_packet::{ConvertPacket, ConvertPacketError};
+use ross_protocol::event::button::{ButtonPressedEvent, ButtonReleasedEvent};
+use ross_protocol::event::EventError;
+use ross_protocol::packet::Packet;
+
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::{serialize_integer_to_vec, try_deserialize_integer_from_vec};
+
+// Synthetic event produced by the tracker on the device itself, never sent on the bus
+pub const BUTTON_GESTURE_EVENT_CODE: u16 = 0x8000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ButtonGesture {
+    // Released before the long press delay without a second press in the double click window
+    Click,
+    DoubleClick,
+    LongPressStart,
+    // Repeats every hold repeat interval while the button stays pressed after a long press
+    HoldTick,
+    LongPressRelease,
+}
+
+impl ButtonGesture {
+    pub fn get_code(&self) -> u8 {
+        match self {
+            ButtonGesture::Click => 0x00,
+            ButtonGesture::DoubleClick => 0x01,
+            ButtonGesture::LongPressStart => 0x02,
+            ButtonGesture::HoldTick => 0x03,
+            ButtonGesture::LongPressRelease => 0x04,
+        }
+    }
+
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0x00 => Some(ButtonGesture::Click),
+            0x01 => Some(ButtonGesture::DoubleClick),
+            0x02 => Some(ButtonGesture::LongPressStart),
+            0x03 => Some(ButtonGesture::HoldTick),
+            0x04 => Some(ButtonGesture::LongPressRelease),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ButtonGestureEvent {
+    pub receiver_address: u16,
+    pub button_address: u16,
+    pub index: u8,
+    pub gesture: ButtonGesture,
+}
+
+impl ConvertPacket<ButtonGestureEvent> for ButtonGestureEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 6 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != BUTTON_GESTURE_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let button_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let gesture = match ButtonGesture::from_code(packet.data[5]) {
+            Some(gesture) => gesture,
+            None => return Err(ConvertPacketError::UnknownEnumVariant),
+        };
+
+        Ok(ButtonGestureEvent {
+            receiver_address,
+            button_address,
+            index,
+            gesture,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(BUTTON_GESTURE_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.button_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+        data.push(self.gesture.get_code());
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GestureTimings {
+    // Milliseconds after a release in which a second press makes a double click, 0 disables
+    // double clicks so that clicks are reported on release without waiting
+    pub double_click_window: u32,
+    // Milliseconds a button has to be held for a long press
+    pub long_press_delay: u32,
+    // Milliseconds between hold ticks, 0 disables them
+    pub hold_repeat_interval: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ButtonState {
+    // Timestamps are milliseconds since the Unix epoch
+    Pressed { since: i64, is_second_press: bool },
+    LongPressed { next_tick: i64 },
+    // Released after a single press, waiting for a second one
+    Released { since: i64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ButtonTracking {
+    receiver_address: u16,
+    state: ButtonState,
+}
+
+// Turns button pressed and released events into gesture events. Every packet has to be passed to
+// `process`, including system ticks, which time out long presses and double click windows.
+#[derive(Debug, PartialEq)]
+pub struct ButtonGestureTracker {
+    timings: GestureTimings,
+    // Keyed by button address and index
+    buttons: BTreeMap<(u16, u8), ButtonTracking>,
+}
+
+impl ButtonGestureTracker {
+    pub fn new(timings: GestureTimings) -> Self {
+        Self {
+            timings,
+            buttons: BTreeMap::new(),
+        }
+    }
+
+    // Returns the gesture event packets to process after this packet
+    pub fn process(&mut self, packet: &Packet, date_time: &DateTime<Utc>) -> Vec<Packet> {
+        let now = date_time.timestamp_millis();
+
+        // Timeouts go first, so that a press after an expired double click window starts over
+        let mut events = self.process_timeouts(now);
+
+        if let Ok(event) = ButtonPressedEvent::try_from_packet(packet) {
+            self.process_pressed(&event, now);
+        } else if let Ok(event) = ButtonReleasedEvent::try_from_packet(packet) {
+            if let Some(gesture) = self.process_released(&event, now) {
+                events.push(ButtonGestureEvent {
+                    receiver_address: event.receiver_address,
+                    button_address: event.button_address,
+                    index: event.index,
+                    gesture,
+                });
+            }
+        }
+
+        events.iter().map(|event| event.to_packet()).collect()
+    }
+
+    fn process_timeouts(&mut self, now: i64) -> Vec<ButtonGestureEvent> {
+        let timings = &self.timings;
+        let mut events = vec![];
+
+        self.buttons.retain(|(button_address, index), tracking| {
+            let gesture = match tracking.state {
+                ButtonState::Pressed { since, .. }
+                    if now - since >= timings.long_press_delay as i64 =>
+                {
+                    tracking.state = ButtonState::LongPressed {
+                        next_tick: since
+                            + timings.long_press_delay as i64
+                            + timings.hold_repeat_interval as i64,
+                    };
+
+                    ButtonGesture::LongPressStart
+                }
+                ButtonState::LongPressed { next_tick }
+                    if timings.hold_repeat_interval != 0 && now >= next_tick =>
+                {
+                    // Ticks missed between two packets are merged into one
+                    let interval = timings.hold_repeat_interval as i64;
+                    let missed_ticks = (now - next_tick) / interval;
+
+                    tracking.state = ButtonState::LongPressed {
+                        next_tick: next_tick + (missed_ticks + 1) * interval,
+                    };
+
+                    ButtonGesture::HoldTick
+                }
+                ButtonState::Released { since }
+                    if now - since >= timings.double_click_window as i64 =>
+                {
+                    ButtonGesture::Click
+                }
+                _ => return true,
+            };
+
+            events.push(ButtonGestureEvent {
+                receiver_address: tracking.receiver_address,
+                button_address: *button_address,
+                index: *index,
+                gesture,
+            });
+
+            // Clicks end the gesture, the others keep the button pressed
+            gesture != ButtonGesture::Click
+        });
+
+        events
+    }
+
+    fn process_pressed(&mut self, event: &ButtonPressedEvent, now: i64) {
+        let key = (event.button_address, event.index);
+
+        let is_second_press = match self.buttons.get(&key) {
+            Some(ButtonTracking {
+                state: ButtonState::Released { .. },
+                ..
+            }) => true,
+            // Repeated pressed events keep the original press
+            Some(_) => return,
+            None => false,
+        };
+
+        self.buttons.insert(
+            key,
+            ButtonTracking {
+                receiver_address: event.receiver_address,
+                state: ButtonState::Pressed {
+                    since: now,
+                    is_second_press,
+                },
+            },
+        );
+    }
+
+    fn process_released(&mut self, event: &ButtonReleasedEvent, now: i64) -> Option<ButtonGesture> {
+        let key = (event.button_address, event.index);
+
+        let state = match self.buttons.get(&key) {
+            Some(tracking) => tracking.state,
+            None => return None,
+        };
+
+        match state {
+            ButtonState::Pressed {
+                is_second_press: false,
+                ..
+            } if self.timings.double_click_window != 0 => {
+                self.buttons.insert(
+                    key,
+                    ButtonTracking {
+                        receiver_address: event.receiver_address,
+                        state: ButtonState::Released { since: now },
+                    },
+                );
+
+                None
+            }
+            ButtonState::Pressed {
+                is_second_press, ..
+            } => {
+                self.buttons.remove(&key);
+
+                if is_second_press {
+                    Some(ButtonGesture::DoubleClick)
+                } else {
+                    Some(ButtonGesture::Click)
+                }
+            }
+            ButtonState::LongPressed { .. } => {
+                self.buttons.remove(&key);
+
+                Some(ButtonGesture::LongPressRelease)
+            }
+            ButtonState::Released { .. } => None,
+        }
+    }
+}
+
+impl Serialize for GestureTimings {
+    fn serialize(&self) -> Vec<u8> {
+        let mut data = vec![];
+
+        serialize_integer_to_vec!(data, self.double_click_window, u32);
+        serialize_integer_to_vec!(data, self.long_press_delay, u32);
+        serialize_integer_to_vec!(data, self.hold_repeat_interval, u32);
+
+        data
+    }
+}
+
+impl TryDeserialize for GestureTimings {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        let mut offset = 0;
+
+        let double_click_window = try_deserialize_integer_from_vec!(data, offset, u32);
+        let long_press_delay = try_deserialize_integer_from_vec!(data, offset, u32);
+        #[allow(unused_assignments)]
+        let hold_repeat_interval = try_deserialize_integer_from_vec!(data, offset, u32);
+
+        Ok(Box::new(Self {
+            double_click_window,
+            long_press_delay,
+            hold_repeat_interval,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::str::FromStr;
+
+    const TIMINGS: GestureTimings = GestureTimings {
+        double_click_window: 300,
+        long_press_delay: 800,
+        hold_repeat_interval: 200,
+    };
+
+    fn pressed() -> Packet {
+        ButtonPressedEvent {
+            receiver_address: 0xabab,
+            button_address: 0x0123,
+            index: 0x01,
+        }
+        .to_packet()
+    }
+
+    fn released() -> Packet {
+        ButtonReleasedEvent {
+            receiver_address: 0xabab,
+            button_address: 0x0123,
+            index: 0x01,
+        }
+        .to_packet()
+    }
+
+    fn tick() -> Packet {
+        Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: vec![0x00, 0x09],
+        }
+    }
+
+    fn process_at(
+        tracker: &mut ButtonGestureTracker,
+        packet: &Packet,
+        date_time: &str,
+    ) -> Vec<ButtonGesture> {
+        tracker
+            .process(packet, &DateTime::from_str(date_time).unwrap())
+            .iter()
+            .map(|packet| ButtonGestureEvent::try_from_packet(packet).unwrap().gesture)
+            .collect()
+    }
+
+    #[test]
+    fn click_test() {
+        let mut tracker = ButtonGestureTracker::new(TIMINGS);
+
+        assert_eq!(
+            process_at(&mut tracker, &pressed(), "2022-01-03T06:30:00Z"),
+            vec![]
+        );
+        assert_eq!(
+            process_at(&mut tracker, &released(), "2022-01-03T06:30:00.1Z"),
+            vec![]
+        );
+        // Still in the double click window
+        assert_eq!(
+            process_at(&mut tracker, &tick(), "2022-01-03T06:30:00.3Z"),
+            vec![]
+        );
+        assert_eq!(
+            process_at(&mut tracker, &tick(), "2022-01-03T06:30:00.4Z"),
+            vec![ButtonGesture::Click]
+        );
+        assert_eq!(
+            process_at(&mut tracker, &tick(), "2022-01-03T06:30:00.5Z"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn click_without_double_click_window_test() {
+        let mut tracker = ButtonGestureTracker::new(GestureTimings {
+            double_click_window: 0,
+            ..TIMINGS
+        });
+
+        assert_eq!(
+            process_at(&mut tracker, &pressed(), "2022-01-03T06:30:00Z"),
+            vec![]
+        );
+        assert_eq!(
+            process_at(&mut tracker, &released(), "2022-01-03T06:30:00.1Z"),
+            vec![ButtonGesture::Click]
+        );
+    }
+
+    #[test]
+    fn double_click_test() {
+        let mut tracker = ButtonGestureTracker::new(TIMINGS);
+
+        process_at(&mut tracker, &pressed(), "2022-01-03T06:30:00Z");
+        process_at(&mut tracker, &released(), "2022-01-03T06:30:00.1Z");
+
+        assert_eq!(
+            process_at(&mut tracker, &pressed(), "2022-01-03T06:30:00.3Z"),
+            vec![]
+        );
+        assert_eq!(
+            process_at(&mut tracker, &released(), "2022-01-03T06:30:00.4Z"),
+            vec![ButtonGesture::DoubleClick]
+        );
+        assert_eq!(
+            process_at(&mut tracker, &tick(), "2022-01-03T06:30:01Z"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn press_after_double_click_window_test() {
+        let mut tracker = ButtonGestureTracker::new(TIMINGS);
+
+        process_at(&mut tracker, &pressed(), "2022-01-03T06:30:00Z");
+        process_at(&mut tracker, &released(), "2022-01-03T06:30:00.1Z");
+
+        assert_eq!(
+            process_at(&mut tracker, &pressed(), "2022-01-03T06:30:00.5Z"),
+            vec![ButtonGesture::Click]
+        );
+        assert_eq!(
+            process_at(&mut tracker, &released(), "2022-01-03T06:30:00.6Z"),
+            vec![]
+        );
+        assert_eq!(
+            process_at(&mut tracker, &tick(), "2022-01-03T06:30:01Z"),
+            vec![ButtonGesture::Click]
+        );
+    }
+
+    #[test]
+    fn long_press_test() {
+        let mut tracker = ButtonGestureTracker::new(TIMINGS);
+
+        process_at(&mut tracker, &pressed(), "2022-01-03T06:30:00Z");
+
+        assert_eq!(
+            process_at(&mut tracker, &tick(), "2022-01-03T06:30:00.7Z"),
+            vec![]
+        );
+        assert_eq!(
+            process_at(&mut tracker, &tick(), "2022-01-03T06:30:00.8Z"),
+            vec![ButtonGesture::LongPressStart]
+        );
+        assert_eq!(
+            process_at(&mut tracker, &tick(), "2022-01-03T06:30:00.9Z"),
+            vec![]
+        );
+        assert_eq!(
+            process_at(&mut tracker, &tick(), "2022-01-03T06:30:01Z"),
+            vec![ButtonGesture::HoldTick]
+        );
+        // Missed ticks are merged
+        assert_eq!(
+            process_at(&mut tracker, &tick(), "2022-01-03T06:30:01.5Z"),
+            vec![ButtonGesture::HoldTick]
+        );
+        assert_eq!(
+            process_at(&mut tracker, &tick(), "2022-01-03T06:30:01.5Z"),
+            vec![]
+        );
+        assert_eq!(
+            process_at(&mut tracker, &tick(), "2022-01-03T06:30:01.6Z"),
+            vec![ButtonGesture::HoldTick]
+        );
+        assert_eq!(
+            process_at(&mut tracker, &released(), "2022-01-03T06:30:01.7Z"),
+            vec![ButtonGesture::LongPressRelease]
+        );
+        assert_eq!(
+            process_at(&mut tracker, &tick(), "2022-01-03T06:30:02Z"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn long_press_without_hold_ticks_test() {
+        let mut tracker = ButtonGestureTracker::new(GestureTimings {
+            hold_repeat_interval: 0,
+            ..TIMINGS
+        });
+
+        process_at(&mut tracker, &pressed(), "2022-01-03T06:30:00Z");
+
+        assert_eq!(
+            process_at(&mut tracker, &released(), "2022-01-03T06:30:01Z"),
+            vec![
+                ButtonGesture::LongPressStart,
+                ButtonGesture::LongPressRelease
+            ]
+        );
+    }
+
+    #[test]
+    fn buttons_tracked_separately_test() {
+        let mut tracker = ButtonGestureTracker::new(TIMINGS);
+
+        let other_pressed = ButtonPressedEvent {
+            receiver_address: 0xabab,
+            button_address: 0x0123,
+            index: 0x02,
+        }
+        .to_packet();
+
+        process_at(&mut tracker, &pressed(), "2022-01-03T06:30:00Z");
+        process_at(&mut tracker, &released(), "2022-01-03T06:30:00.1Z");
+
+        // A press of another button does not make a double click
+        assert_eq!(
+            process_at(&mut tracker, &other_pressed, "2022-01-03T06:30:00.2Z"),
+            vec![]
+        );
+
+        let packets = tracker.process(
+            &tick(),
+            &DateTime::from_str("2022-01-03T06:30:01Z").unwrap(),
+        );
+
+        assert_eq!(
+            packets
+                .iter()
+                .map(|packet| ButtonGestureEvent::try_from_packet(packet).unwrap())
+                .collect::<Vec<_>>(),
+            vec![
+                ButtonGestureEvent {
+                    receiver_address: 0xabab,
+                    button_address: 0x0123,
+                    index: 0x01,
+                    gesture: ButtonGesture::Click,
+                },
+                ButtonGestureEvent {
+                    receiver_address: 0xabab,
+                    button_address: 0x0123,
+                    index: 0x02,
+                    gesture: ButtonGesture::LongPressStart,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn event_to_packet_test() {
+        let event = ButtonGestureEvent {
+            receiver_address: 0xabab,
+            button_address: 0x0123,
+            index: 0x45,
+            gesture: ButtonGesture::HoldTick,
+        };
+
+        let expected_packet = Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: vec![
+                0x80, 0x00, // event code
+                0x01, 0x23, // button address
+                0x45, // index
+                0x03, // gesture
+            ],
+        };
+
+        assert_eq!(event.to_packet(), expected_packet);
+    }
+
+    #[test]
+    fn event_unknown_gesture_test() {
+        let packet = Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: vec![0x80, 0x00, 0x01, 0x23, 0x45, 0x05],
+        };
+
+        assert_eq!(
+            ButtonGestureEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::UnknownEnumVariant)
+        );
+    }
+
+    #[test]
+    fn timings_serialize_test() {
+        let expected_data = vec![
+            0x00, 0x00, 0x01, 0x2c, // double click window
+            0x00, 0x00, 0x03, 0x20, // long press delay
+            0x00, 0x00, 0x00, 0xc8, // hold repeat interval
+        ];
+
+        assert_eq!(TIMINGS.serialize(), expected_data);
+        assert_eq!(
+            GestureTimings::try_deserialize(&expected_data),
+            Ok(Box::new(TIMINGS))
+        );
+    }
+
+    #[test]
+    fn timings_deserialize_wrong_size_test() {
+        let data = vec![0x00, 0x00, 0x01, 0x2c, 0x00, 0x00, 0x03, 0x20, 0x00];
+
+        assert_eq!(
+            GestureTimings::try_deserialize(&data),
+            Err(ConfigSerializerError::WrongSize)
+        );
+    }
+}
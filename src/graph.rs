@@ -0,0 +1,333 @@
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::config::Config;
+use crate::producer::ProducerTarget;
+use crate::validator::{collect_state_accesses, ComponentLocation, StateAccess};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GraphNode {
+    Event(u16),
+    EventProcessor(usize),
+    State(u32),
+    Bcm(u16, u8),
+    Relay(u16, u8),
+    Message(u16, u16),
+}
+
+impl GraphNode {
+    fn get_id(&self) -> String {
+        match self {
+            GraphNode::Event(event_code) => format!("event_{:04x}", event_code),
+            GraphNode::EventProcessor(index) => format!("event_processor_{}", index),
+            GraphNode::State(state_index) => format!("state_{}", state_index),
+            GraphNode::Bcm(bcm_address, index) => format!("bcm_{:04x}_{}", bcm_address, index),
+            GraphNode::Relay(relay_address, index) => {
+                format!("relay_{:04x}_{}", relay_address, index)
+            }
+            GraphNode::Message(receiver_address, code) => {
+                format!("message_{:04x}_{:04x}", receiver_address, code)
+            }
+        }
+    }
+
+    fn get_label(&self) -> String {
+        match self {
+            GraphNode::Event(event_code) => format!("event 0x{:04x}", event_code),
+            GraphNode::EventProcessor(index) => format!("event processor {}", index),
+            GraphNode::State(state_index) => format!("state {}", state_index),
+            GraphNode::Bcm(bcm_address, index) => format!("bcm 0x{:04x}/{}", bcm_address, index),
+            GraphNode::Relay(relay_address, index) => {
+                format!("relay 0x{:04x}/{}", relay_address, index)
+            }
+            GraphNode::Message(receiver_address, code) => {
+                format!("message 0x{:04x}/0x{:04x}", receiver_address, code)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GraphEdge {
+    pub from: GraphNode,
+    pub to: GraphNode,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct StateGraph {
+    pub nodes: BTreeSet<GraphNode>,
+    pub edges: BTreeSet<GraphEdge>,
+}
+
+impl StateGraph {
+    pub fn from_config(config: &Config) -> Self {
+        let mut graph = Self {
+            nodes: BTreeSet::new(),
+            edges: BTreeSet::new(),
+        };
+
+        for (event_processor_index, event_processor) in config.event_processors.iter().enumerate() {
+            let event_processor_node = GraphNode::EventProcessor(event_processor_index);
+            graph.nodes.insert(event_processor_node.clone());
+
+            if let Some(event_codes) = event_processor.matcher.get_event_codes() {
+                for event_code in event_codes {
+                    graph.add_edge(GraphNode::Event(event_code), event_processor_node.clone());
+                }
+            }
+
+            for creator in event_processor.creators.iter() {
+                match creator.producer.get_target() {
+                    Some(ProducerTarget::Bcm {
+                        bcm_address, index, ..
+                    }) => graph.add_edge(
+                        event_processor_node.clone(),
+                        GraphNode::Bcm(bcm_address, index),
+                    ),
+                    Some(ProducerTarget::Relay {
+                        relay_address,
+                        index,
+                        ..
+                    }) => graph.add_edge(
+                        event_processor_node.clone(),
+                        GraphNode::Relay(relay_address, index),
+                    ),
+                    Some(ProducerTarget::Message {
+                        receiver_address,
+                        code,
+                        ..
+                    }) => graph.add_edge(
+                        event_processor_node.clone(),
+                        GraphNode::Message(receiver_address, code),
+                    ),
+                    None => {}
+                }
+            }
+        }
+
+        for (location, access) in collect_state_accesses(config) {
+            let event_processor_node = match location {
+                ComponentLocation::EventProcessor(index) | ComponentLocation::Creator(index, _) => {
+                    GraphNode::EventProcessor(index)
+                }
+            };
+
+            match access {
+                StateAccess::Read(state_index, _) => {
+                    graph.add_edge(GraphNode::State(state_index), event_processor_node)
+                }
+                StateAccess::Write(state_index, _) | StateAccess::Copy(state_index, _) => {
+                    graph.add_edge(event_processor_node, GraphNode::State(state_index))
+                }
+                // Both states are also read separately
+                StateAccess::Compare(..) => {}
+            }
+        }
+
+        graph
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut output = String::from("digraph config {\n");
+
+        for node in self.nodes.iter() {
+            let shape = match node {
+                GraphNode::Event(_) => "ellipse",
+                GraphNode::EventProcessor(_) => "box",
+                GraphNode::State(_) => "cylinder",
+                GraphNode::Bcm(_, _) | GraphNode::Relay(_, _) | GraphNode::Message(_, _) => "house",
+            };
+
+            writeln!(
+                output,
+                "    {} [label=\"{}\", shape={}];",
+                node.get_id(),
+                node.get_label(),
+                shape
+            )
+            .unwrap();
+        }
+
+        for edge in self.edges.iter() {
+            writeln!(
+                output,
+                "    {} -> {};",
+                edge.from.get_id(),
+                edge.to.get_id()
+            )
+            .unwrap();
+        }
+
+        output.push_str("}\n");
+
+        output
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        let mut output = String::from("flowchart LR\n");
+
+        for node in self.nodes.iter() {
+            let (open, close) = match node {
+                GraphNode::Event(_) => ("([", "])"),
+                GraphNode::EventProcessor(_) => ("[", "]"),
+                GraphNode::State(_) => ("[(", ")]"),
+                GraphNode::Bcm(_, _) | GraphNode::Relay(_, _) | GraphNode::Message(_, _) => {
+                    ("[/", "\\]")
+                }
+            };
+
+            writeln!(
+                output,
+                "    {}{}\"{}\"{}",
+                node.get_id(),
+                open,
+                node.get_label(),
+                close
+            )
+            .unwrap();
+        }
+
+        for edge in self.edges.iter() {
+            writeln!(
+                output,
+                "    {} --> {}",
+                edge.from.get_id(),
+                edge.to.get_id()
+            )
+            .unwrap();
+        }
+
+        output
+    }
+
+    fn add_edge(&mut self, from: GraphNode, to: GraphNode) {
+        self.nodes.insert(from.clone());
+        self.nodes.insert(to.clone());
+        self.edges.insert(GraphEdge { from, to });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::boxed::Box;
+    use alloc::collections::BTreeMap;
+    use alloc::vec;
+
+    use ross_protocol::event::event_code::BUTTON_PRESSED_EVENT_CODE;
+
+    use crate::creator::Creator;
+    use crate::event_processor::EventProcessor;
+    use crate::extractor::{EventCodeExtractor, NoneExtractor};
+    use crate::filter::{CoercionPolicy, FlipStateFilter, ValueEqualToConstFilter};
+    use crate::matcher::Matcher;
+    use crate::producer::BcmChangeBrightnessStateProducer;
+    use crate::Value;
+
+    fn config() -> Config {
+        let mut initial_state = BTreeMap::new();
+        initial_state.insert(0, Value::U8(0xff));
+
+        Config {
+            peripherals: BTreeMap::new(),
+            initial_state,
+            event_processors: vec![EventProcessor {
+                matcher: Matcher::And(
+                    Box::new(Matcher::Single {
+                        extractor: Box::new(EventCodeExtractor::new()),
+                        filter: Box::new(ValueEqualToConstFilter::new(
+                            Value::U16(BUTTON_PRESSED_EVENT_CODE),
+                            CoercionPolicy::Strict,
+                        )),
+                    }),
+                    Box::new(Matcher::Single {
+                        extractor: Box::new(NoneExtractor::new()),
+                        filter: Box::new(FlipStateFilter::new(1)),
+                    }),
+                ),
+                creators: vec![Creator {
+                    extractor: Box::new(NoneExtractor::new()),
+                    producer: Box::new(BcmChangeBrightnessStateProducer::new(0xabab, 0x01, 0)),
+                    matcher: None,
+                }],
+            }],
+            time_zone: None,
+            calendars: BTreeMap::new(),
+            gesture_timings: None,
+        }
+    }
+
+    #[test]
+    fn from_config_test() {
+        let graph = StateGraph::from_config(&config());
+
+        let mut expected_edges = BTreeSet::new();
+        expected_edges.insert(GraphEdge {
+            from: GraphNode::Event(BUTTON_PRESSED_EVENT_CODE),
+            to: GraphNode::EventProcessor(0),
+        });
+        expected_edges.insert(GraphEdge {
+            from: GraphNode::EventProcessor(0),
+            to: GraphNode::Bcm(0xabab, 0x01),
+        });
+        expected_edges.insert(GraphEdge {
+            from: GraphNode::State(1),
+            to: GraphNode::EventProcessor(0),
+        });
+        expected_edges.insert(GraphEdge {
+            from: GraphNode::EventProcessor(0),
+            to: GraphNode::State(1),
+        });
+        expected_edges.insert(GraphEdge {
+            from: GraphNode::State(0),
+            to: GraphNode::EventProcessor(0),
+        });
+
+        assert_eq!(graph.edges, expected_edges);
+        assert_eq!(graph.nodes.len(), 5);
+    }
+
+    #[test]
+    fn to_dot_test() {
+        let graph = StateGraph::from_config(&config());
+
+        let expected_output = "digraph config {\n\
+            \x20   event_0007 [label=\"event 0x0007\", shape=ellipse];\n\
+            \x20   event_processor_0 [label=\"event processor 0\", shape=box];\n\
+            \x20   state_0 [label=\"state 0\", shape=cylinder];\n\
+            \x20   state_1 [label=\"state 1\", shape=cylinder];\n\
+            \x20   bcm_abab_1 [label=\"bcm 0xabab/1\", shape=house];\n\
+            \x20   event_0007 -> event_processor_0;\n\
+            \x20   event_processor_0 -> state_1;\n\
+            \x20   event_processor_0 -> bcm_abab_1;\n\
+            \x20   state_0 -> event_processor_0;\n\
+            \x20   state_1 -> event_processor_0;\n\
+            }\n";
+
+        assert_eq!(graph.to_dot(), expected_output);
+    }
+
+    #[test]
+    fn to_mermaid_test() {
+        let graph = StateGraph::from_config(&config());
+
+        let expected_output = "flowchart LR\n\
+            \x20   event_0007([\"event 0x0007\"])\n\
+            \x20   event_processor_0[\"event processor 0\"]\n\
+            \x20   state_0[(\"state 0\")]\n\
+            \x20   state_1[(\"state 1\")]\n\
+            \x20   bcm_abab_1[/\"bcm 0xabab/1\"\\]\n\
+            \x20   event_0007 --> event_processor_0\n\
+            \x20   event_processor_0 --> state_1\n\
+            \x20   event_processor_0 --> bcm_abab_1\n\
+            \x20   state_0 --> event_processor_0\n\
+            \x20   state_1 --> event_processor_0\n";
+
+        assert_eq!(graph.to_mermaid(), expected_output);
+    }
+}
@@ -8,19 +8,31 @@ use core::convert::TryInto;
 
 use ross_protocol::packet::Packet;
 
+use crate::conversion::ConversionError;
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 
+pub mod calendar;
 pub mod config;
+pub mod conflict;
+pub mod conversion;
 pub mod creator;
 pub mod cron;
 pub mod event_processor;
+pub mod expression;
 pub mod extractor;
 pub mod filter;
+pub mod gesture;
+pub mod graph;
 pub mod matcher;
 pub mod peripheral;
 pub mod producer;
 pub mod serializer;
+pub mod solar;
 pub mod state_manager;
+pub mod time_zone;
+pub mod validator;
+
+pub const MAX_BYTES_VALUE_LEN: usize = 64;
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub enum Value {
@@ -32,6 +44,55 @@ pub enum Value {
     RgbB(u8, u8, u8, u8),
     Rgbw(u8, u8, u8, u8),
     RgbwB(u8, u8, u8, u8, u8),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    // Signed Q16.16 fixed-point number, 21.5 is stored as 0x0015_8000
+    Fixed(i32),
+    // Text or opaque data
+    Bytes(BytesValue),
+}
+
+// Up to MAX_BYTES_VALUE_LEN bytes, only built through `BytesValue::new`
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct BytesValue(Vec<u8>);
+
+impl BytesValue {
+    pub fn new(bytes: &[u8]) -> Result<Self, ConversionError> {
+        if bytes.len() > MAX_BYTES_VALUE_LEN {
+            return Err(ConversionError::TooLong);
+        }
+
+        Ok(Self(bytes.to_vec()))
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Value {
+    pub fn get_type(&self) -> ValueType {
+        match self {
+            Value::U8(_) => ValueType::U8,
+            Value::U16(_) => ValueType::U16,
+            Value::U32(_) => ValueType::U32,
+            Value::Bool(_) => ValueType::Bool,
+            Value::Rgb(_, _, _) => ValueType::Rgb,
+            Value::RgbB(_, _, _, _) => ValueType::RgbB,
+            Value::Rgbw(_, _, _, _) => ValueType::Rgbw,
+            Value::RgbwB(_, _, _, _, _) => ValueType::RgbwB,
+            Value::I8(_) => ValueType::I8,
+            Value::I16(_) => ValueType::I16,
+            Value::I32(_) => ValueType::I32,
+            Value::Fixed(_) => ValueType::Fixed,
+            Value::Bytes(_) => ValueType::Bytes,
+        }
+    }
+
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, ConversionError> {
+        Ok(Value::Bytes(BytesValue::new(bytes)?))
+    }
 }
 
 impl Serialize for Value {
@@ -57,6 +118,28 @@ impl Serialize for Value {
             Value::RgbB(r, g, b, brightness) => vec![0x05, r, g, b, brightness],
             Value::Rgbw(r, g, b, w) => vec![0x06, r, g, b, w],
             Value::RgbwB(r, g, b, w, brightness) => vec![0x07, r, g, b, w, brightness],
+            Value::I8(value) => vec![0x08, value as u8],
+            Value::I16(value) => {
+                let bytes = value.to_be_bytes();
+
+                vec![0x09, bytes[0], bytes[1]]
+            }
+            Value::I32(value) => {
+                let bytes = value.to_be_bytes();
+
+                vec![0x0a, bytes[0], bytes[1], bytes[2], bytes[3]]
+            }
+            Value::Fixed(value) => {
+                let bytes = value.to_be_bytes();
+
+                vec![0x0b, bytes[0], bytes[1], bytes[2], bytes[3]]
+            }
+            Value::Bytes(ref value) => {
+                let mut data = vec![0x0c, value.as_slice().len() as u8];
+                data.extend_from_slice(value.as_slice());
+
+                data
+            }
         }
     }
 }
@@ -120,11 +203,88 @@ impl TryDeserialize for Value {
                     data[1], data[2], data[3], data[4], data[5],
                 )))
             }
+            0x08 => Ok(Box::new(Value::I8(data[1] as i8))),
+            0x09 => {
+                if data.len() < 3 {
+                    return Err(ConfigSerializerError::WrongSize);
+                }
+
+                let value = i16::from_be_bytes(data[1..=2].try_into().unwrap());
+
+                Ok(Box::new(Value::I16(value)))
+            }
+            0x0a => {
+                if data.len() < 5 {
+                    return Err(ConfigSerializerError::WrongSize);
+                }
+
+                let value = i32::from_be_bytes(data[1..=4].try_into().unwrap());
+
+                Ok(Box::new(Value::I32(value)))
+            }
+            0x0b => {
+                if data.len() < 5 {
+                    return Err(ConfigSerializerError::WrongSize);
+                }
+
+                let value = i32::from_be_bytes(data[1..=4].try_into().unwrap());
+
+                Ok(Box::new(Value::Fixed(value)))
+            }
+            0x0c => {
+                let len = data[1] as usize;
+
+                if data.len() < len + 2 {
+                    return Err(ConfigSerializerError::WrongSize);
+                }
+
+                match Value::try_from_bytes(&data[2..len + 2]) {
+                    Ok(value) => Ok(Box::new(value)),
+                    Err(_) => Err(ConfigSerializerError::WrongSize),
+                }
+            }
             _ => Err(ConfigSerializerError::UnknownEnumVariant),
         }
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ValueType {
+    U8,
+    U16,
+    U32,
+    Bool,
+    Rgb,
+    RgbB,
+    Rgbw,
+    RgbwB,
+    I8,
+    I16,
+    I32,
+    Fixed,
+    Bytes,
+}
+
+impl ValueType {
+    pub fn all() -> Vec<ValueType> {
+        vec![
+            ValueType::U8,
+            ValueType::U16,
+            ValueType::U32,
+            ValueType::Bool,
+            ValueType::Rgb,
+            ValueType::RgbB,
+            ValueType::Rgbw,
+            ValueType::RgbwB,
+            ValueType::I8,
+            ValueType::I16,
+            ValueType::I32,
+            ValueType::Fixed,
+            ValueType::Bytes,
+        ]
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum ExtractorValue<'a> {
     None,
@@ -133,6 +293,78 @@ pub enum ExtractorValue<'a> {
     U32(u32),
     Bool(bool),
     Packet(&'a Packet),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    Fixed(i32),
+    Bytes(&'a [u8]),
+    Rgb(u8, u8, u8),
+    RgbB(u8, u8, u8, u8),
+    Rgbw(u8, u8, u8, u8),
+    RgbwB(u8, u8, u8, u8, u8),
+}
+
+impl<'a> ExtractorValue<'a> {
+    pub fn get_type(&self) -> ExtractorValueType {
+        match self {
+            ExtractorValue::None => ExtractorValueType::None,
+            ExtractorValue::U8(_) => ExtractorValueType::U8,
+            ExtractorValue::U16(_) => ExtractorValueType::U16,
+            ExtractorValue::U32(_) => ExtractorValueType::U32,
+            ExtractorValue::Bool(_) => ExtractorValueType::Bool,
+            ExtractorValue::Packet(_) => ExtractorValueType::Packet,
+            ExtractorValue::I8(_) => ExtractorValueType::I8,
+            ExtractorValue::I16(_) => ExtractorValueType::I16,
+            ExtractorValue::I32(_) => ExtractorValueType::I32,
+            ExtractorValue::Fixed(_) => ExtractorValueType::Fixed,
+            ExtractorValue::Bytes(_) => ExtractorValueType::Bytes,
+            ExtractorValue::Rgb(_, _, _) => ExtractorValueType::Rgb,
+            ExtractorValue::RgbB(_, _, _, _) => ExtractorValueType::RgbB,
+            ExtractorValue::Rgbw(_, _, _, _) => ExtractorValueType::Rgbw,
+            ExtractorValue::RgbwB(_, _, _, _, _) => ExtractorValueType::RgbwB,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ExtractorValueType {
+    None,
+    U8,
+    U16,
+    U32,
+    Bool,
+    Packet,
+    I8,
+    I16,
+    I32,
+    Fixed,
+    Bytes,
+    Rgb,
+    RgbB,
+    Rgbw,
+    RgbwB,
+}
+
+impl ExtractorValueType {
+    pub fn all() -> Vec<ExtractorValueType> {
+        vec![
+            ExtractorValueType::None,
+            ExtractorValueType::U8,
+            ExtractorValueType::U16,
+            ExtractorValueType::U32,
+            ExtractorValueType::Bool,
+            ExtractorValueType::Packet,
+            ExtractorValueType::I8,
+            ExtractorValueType::I16,
+            ExtractorValueType::I32,
+            ExtractorValueType::Fixed,
+            ExtractorValueType::Bytes,
+            ExtractorValueType::Rgb,
+            ExtractorValueType::RgbB,
+            ExtractorValueType::Rgbw,
+            ExtractorValueType::RgbwB,
+        ]
+    }
 }
 
 #[cfg(test)]
@@ -283,6 +515,116 @@ mod tests {
         assert_eq!(Value::try_deserialize(&data), Ok(expected_value));
     }
 
+    #[test]
+    fn value_i8_serialize_test() {
+        let value = Value::I8(-2);
+
+        let expected_data = vec![0x08, 0xfe];
+
+        assert_eq!(value.serialize(), expected_data);
+    }
+
+    #[test]
+    fn value_i8_deserialize_test() {
+        let data = vec![0x08, 0xfe];
+
+        let expected_value = Box::new(Value::I8(-2));
+
+        assert_eq!(Value::try_deserialize(&data), Ok(expected_value));
+    }
+
+    #[test]
+    fn value_i16_serialize_test() {
+        let value = Value::I16(-2);
+
+        let expected_data = vec![0x09, 0xff, 0xfe];
+
+        assert_eq!(value.serialize(), expected_data);
+    }
+
+    #[test]
+    fn value_i16_deserialize_test() {
+        let data = vec![0x09, 0xff, 0xfe];
+
+        let expected_value = Box::new(Value::I16(-2));
+
+        assert_eq!(Value::try_deserialize(&data), Ok(expected_value));
+    }
+
+    #[test]
+    fn value_i32_serialize_test() {
+        let value = Value::I32(-2);
+
+        let expected_data = vec![0x0a, 0xff, 0xff, 0xff, 0xfe];
+
+        assert_eq!(value.serialize(), expected_data);
+    }
+
+    #[test]
+    fn value_i32_deserialize_test() {
+        let data = vec![0x0a, 0xff, 0xff, 0xff, 0xfe];
+
+        let expected_value = Box::new(Value::I32(-2));
+
+        assert_eq!(Value::try_deserialize(&data), Ok(expected_value));
+    }
+
+    #[test]
+    fn value_fixed_serialize_test() {
+        let value = Value::Fixed(-0x0015_8000);
+
+        let expected_data = vec![0x0b, 0xff, 0xea, 0x80, 0x00];
+
+        assert_eq!(value.serialize(), expected_data);
+    }
+
+    #[test]
+    fn value_fixed_deserialize_test() {
+        let data = vec![0x0b, 0x00, 0x15, 0x80, 0x00];
+
+        let expected_value = Box::new(Value::Fixed(0x0015_8000));
+
+        assert_eq!(Value::try_deserialize(&data), Ok(expected_value));
+    }
+
+    #[test]
+    fn value_bytes_serialize_test() {
+        let value = Value::try_from_bytes(&[0x61, 0x62, 0x63]).unwrap();
+
+        let expected_data = vec![0x0c, 0x03, 0x61, 0x62, 0x63];
+
+        assert_eq!(value.serialize(), expected_data);
+    }
+
+    #[test]
+    fn value_bytes_deserialize_test() {
+        let data = vec![0x0c, 0x03, 0x61, 0x62, 0x63];
+
+        let expected_value = Box::new(Value::try_from_bytes(&[0x61, 0x62, 0x63]).unwrap());
+
+        assert_eq!(Value::try_deserialize(&data), Ok(expected_value));
+    }
+
+    #[test]
+    fn value_bytes_too_long_test() {
+        let mut data = vec![0x0c, MAX_BYTES_VALUE_LEN as u8 + 1];
+        data.resize(MAX_BYTES_VALUE_LEN + 3, 0x00);
+
+        assert_eq!(
+            Value::try_deserialize(&data),
+            Err(ConfigSerializerError::WrongSize)
+        );
+        assert_eq!(
+            Value::try_from_bytes(&data[2..]),
+            Err(ConversionError::TooLong)
+        );
+        assert_eq!(
+            Value::try_from_bytes(&data[3..]),
+            Ok(Value::Bytes(BytesValue(vec![0x00; MAX_BYTES_VALUE_LEN])))
+        );
+        assert_eq!(BytesValue::new(&data[2..]), Err(ConversionError::TooLong));
+    }
+
     #[test]
     fn value_wrong_size_test() {
         let data = vec![0x02, 0xab, 0xab, 0xab];
@@ -295,7 +637,7 @@ mod tests {
 
     #[test]
     fn value_unknown_enum_variant_test() {
-        let data = vec![0x08, 0x01];
+        let data = vec![0x0d, 0x01];
 
         assert_eq!(
             Value::try_deserialize(&data),
@@ -1,16 +1,24 @@
 extern crate alloc;
 
 use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
 use alloc::vec;
 use alloc::vec::Vec;
 
+use ross_protocol::event::event_code::{
+    BUTTON_PRESSED_EVENT_CODE, BUTTON_RELEASED_EVENT_CODE, MESSAGE_EVENT_CODE,
+};
 use ross_protocol::packet::Packet;
 
-use crate::extractor::{Extractor, ExtractorError};
-use crate::filter::{Filter, FilterError};
+use crate::extractor::{
+    ButtonGestureExtractor, ButtonGestureIndexExtractor, ButtonIndexExtractor, EventCodeExtractor,
+    Extractor, ExtractorError, MessageCodeExtractor, MessageValueExtractor,
+};
+use crate::filter::{Filter, FilterError, ValueEqualToConstFilter};
+use crate::gesture::BUTTON_GESTURE_EVENT_CODE;
 use crate::serializer::{ConfigSerializer, ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::{serialize_integer_to_vec, try_deserialize_integer_from_vec};
+use crate::{serialize_integer_to_vec, try_deserialize_integer_from_vec, Value};
 
 #[derive(Debug)]
 pub enum MatcherError {
@@ -53,6 +61,69 @@ impl Matcher {
                 && matcher2.do_match(packet, state_manager)?),
         }
     }
+
+    // Returns the event codes this matcher can match, `None` if it is not restricted to any
+    pub fn get_event_codes(&self) -> Option<BTreeSet<u16>> {
+        match self {
+            Matcher::Single { extractor, filter } => {
+                if extractor.is::<EventCodeExtractor>() {
+                    if let Some(filter) = filter.downcast_ref::<ValueEqualToConstFilter>() {
+                        if let Value::U16(event_code) = filter.get_required_value() {
+                            let mut event_codes = BTreeSet::new();
+                            event_codes.insert(*event_code);
+
+                            return Some(event_codes);
+                        }
+                    }
+                }
+
+                let mut event_codes = BTreeSet::new();
+
+                if extractor.is::<ButtonIndexExtractor>() {
+                    event_codes.insert(BUTTON_PRESSED_EVENT_CODE);
+                    event_codes.insert(BUTTON_RELEASED_EVENT_CODE);
+                } else if extractor.is::<ButtonGestureExtractor>()
+                    || extractor.is::<ButtonGestureIndexExtractor>()
+                {
+                    event_codes.insert(BUTTON_GESTURE_EVENT_CODE);
+                } else if extractor.is::<MessageCodeExtractor>()
+                    || extractor.is::<MessageValueExtractor>()
+                {
+                    event_codes.insert(MESSAGE_EVENT_CODE);
+                } else {
+                    // Including time filters, which match any packet arriving while the time matches
+                    return None;
+                }
+
+                Some(event_codes)
+            }
+            Matcher::Not(_) => None,
+            Matcher::Or(matcher1, matcher2) => {
+                match (matcher1.get_event_codes(), matcher2.get_event_codes()) {
+                    (Some(event_codes1), Some(event_codes2)) => {
+                        Some(event_codes1.union(&event_codes2).copied().collect())
+                    }
+                    _ => None,
+                }
+            }
+            Matcher::And(matcher1, matcher2) => {
+                intersect_event_codes(matcher1.get_event_codes(), matcher2.get_event_codes())
+            }
+        }
+    }
+}
+
+pub fn intersect_event_codes(
+    event_codes1: Option<BTreeSet<u16>>,
+    event_codes2: Option<BTreeSet<u16>>,
+) -> Option<BTreeSet<u16>> {
+    match (event_codes1, event_codes2) {
+        (Some(event_codes1), Some(event_codes2)) => {
+            Some(event_codes1.intersection(&event_codes2).copied().collect())
+        }
+        (Some(event_codes), None) | (None, Some(event_codes)) => Some(event_codes),
+        (None, None) => None,
+    }
 }
 
 impl Serialize for Matcher {
@@ -3,7 +3,7 @@ extern crate alloc;
 use alloc::boxed::Box;
 use alloc::vec;
 use alloc::vec::Vec;
-use core::convert::TryInto;
+use core::convert::{TryFrom, TryInto};
 
 use ross_protocol::convert_packet::ConvertPacket;
 use ross_protocol::event::bcm::BcmValue;
@@ -11,13 +11,14 @@ use ross_protocol::event::bcm::{BcmAnimateBrightnessEvent, BcmChangeBrightnessEv
 use ross_protocol::packet::Packet;
 
 use crate::producer::{
-    Producer, ProducerError, BCM_ANIMATE_BRIGHTNESS_PRODUCER_CODE,
+    Producer, ProducerError, ProducerTarget, TargetValue, BCM_ANIMATE_BRIGHTNESS_PRODUCER_CODE,
     BCM_ANIMATE_BRIGHTNESS_STATE_PRODUCER_CODE, BCM_CHANGE_BRIGHTNESS_PRODUCER_CODE,
     BCM_CHANGE_BRIGHTNESS_STATE_PRODUCER_CODE,
 };
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::{ExtractorValue, Value};
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value, ValueType};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -57,6 +58,22 @@ impl Producer for BcmChangeBrightnessProducer {
     fn get_code(&self) -> u16 {
         BCM_CHANGE_BRIGHTNESS_PRODUCER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![]
+    }
+
+    fn get_target(&self) -> Option<ProducerTarget> {
+        Some(ProducerTarget::Bcm {
+            bcm_address: self.bcm_address,
+            index: self.index,
+            value: TargetValue::Const(self.value),
+        })
+    }
 }
 
 impl Serialize for BcmChangeBrightnessProducer {
@@ -117,14 +134,13 @@ impl Producer for BcmChangeBrightnessStateProducer {
         device_address: u16,
     ) -> Result<Option<Packet>, ProducerError> {
         let current_value = match state_manager.get_value(self.state_index) {
-            Some(Value::U8(value)) => BcmValue::Single(*value),
-            Some(Value::Rgb(r, g, b)) => BcmValue::Rgb(*r, *g, *b),
-            Some(Value::RgbB(r, g, b, brightness)) => BcmValue::RgbB(*r, *g, *b, *brightness),
-            Some(Value::Rgbw(r, g, b, w)) => BcmValue::Rgbw(*r, *g, *b, *w),
-            Some(Value::RgbwB(r, g, b, w, brightness)) => {
-                BcmValue::RgbwB(*r, *g, *b, *w, *brightness)
-            }
-            _ => return Err(ProducerError::WrongStateType),
+            // Bool states are not sent as binary values
+            Some(Value::Bool(_)) => return Err(ProducerError::WrongStateType),
+            Some(value) => match BcmValue::try_from(value) {
+                Ok(current_value) => current_value,
+                Err(_) => return Err(ProducerError::WrongStateType),
+            },
+            None => return Err(ProducerError::WrongStateType),
         };
 
         let event = BcmChangeBrightnessEvent {
@@ -140,6 +156,31 @@ impl Producer for BcmChangeBrightnessStateProducer {
     fn get_code(&self) -> u16 {
         BCM_CHANGE_BRIGHTNESS_STATE_PRODUCER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![StateAccess::Read(
+            self.state_index,
+            vec![
+                ValueType::U8,
+                ValueType::Rgb,
+                ValueType::RgbB,
+                ValueType::Rgbw,
+                ValueType::RgbwB,
+            ],
+        )]
+    }
+
+    fn get_target(&self) -> Option<ProducerTarget> {
+        Some(ProducerTarget::Bcm {
+            bcm_address: self.bcm_address,
+            index: self.index,
+            value: TargetValue::State(self.state_index),
+        })
+    }
 }
 
 impl Serialize for BcmChangeBrightnessStateProducer {
@@ -218,6 +259,22 @@ impl Producer for BcmAnimateBrightnessProducer {
     fn get_code(&self) -> u16 {
         BCM_ANIMATE_BRIGHTNESS_PRODUCER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![]
+    }
+
+    fn get_target(&self) -> Option<ProducerTarget> {
+        Some(ProducerTarget::Bcm {
+            bcm_address: self.bcm_address,
+            index: self.index,
+            value: TargetValue::Const(self.target_value),
+        })
+    }
 }
 
 impl Serialize for BcmAnimateBrightnessProducer {
@@ -291,14 +348,13 @@ impl Producer for BcmAnimateBrightnessStateProducer {
         device_address: u16,
     ) -> Result<Option<Packet>, ProducerError> {
         let current_value = match state_manager.get_value(self.state_index) {
-            Some(Value::U8(value)) => BcmValue::Single(*value),
-            Some(Value::Rgb(r, g, b)) => BcmValue::Rgb(*r, *g, *b),
-            Some(Value::RgbB(r, g, b, brightness)) => BcmValue::RgbB(*r, *g, *b, *brightness),
-            Some(Value::Rgbw(r, g, b, w)) => BcmValue::Rgbw(*r, *g, *b, *w),
-            Some(Value::RgbwB(r, g, b, w, brightness)) => {
-                BcmValue::RgbwB(*r, *g, *b, *w, *brightness)
-            }
-            _ => return Err(ProducerError::WrongStateType),
+            // Bool states are not sent as binary values
+            Some(Value::Bool(_)) => return Err(ProducerError::WrongStateType),
+            Some(value) => match BcmValue::try_from(value) {
+                Ok(current_value) => current_value,
+                Err(_) => return Err(ProducerError::WrongStateType),
+            },
+            None => return Err(ProducerError::WrongStateType),
         };
 
         let event = BcmAnimateBrightnessEvent {
@@ -315,6 +371,31 @@ impl Producer for BcmAnimateBrightnessStateProducer {
     fn get_code(&self) -> u16 {
         BCM_ANIMATE_BRIGHTNESS_STATE_PRODUCER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![StateAccess::Read(
+            self.state_index,
+            vec![
+                ValueType::U8,
+                ValueType::Rgb,
+                ValueType::RgbB,
+                ValueType::Rgbw,
+                ValueType::RgbwB,
+            ],
+        )]
+    }
+
+    fn get_target(&self) -> Option<ProducerTarget> {
+        Some(ProducerTarget::Bcm {
+            bcm_address: self.bcm_address,
+            index: self.index,
+            value: TargetValue::State(self.state_index),
+        })
+    }
 }
 
 impl Serialize for BcmAnimateBrightnessStateProducer {
@@ -531,6 +612,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn change_brightness_state_bool_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::Bool(true));
+
+        let producer = BcmChangeBrightnessStateProducer::new(0xabab, 0x01, 0);
+
+        assert_eq!(
+            producer.produce(ExtractorValue::None, &state_manager, 0x0000),
+            Err(ProducerError::WrongStateType)
+        );
+    }
+
     #[test]
     fn change_brightness_state_serialize_test() {
         let producer = BcmChangeBrightnessStateProducer::new(0xabab, 0x01, 0xffff_ffff);
@@ -675,6 +769,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn animate_brightness_state_bool_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::Bool(true));
+
+        let producer = BcmAnimateBrightnessStateProducer::new(0xabab, 0x01, 0xabab_abab, 0);
+
+        assert_eq!(
+            producer.produce(ExtractorValue::None, &state_manager, 0x0000),
+            Err(ProducerError::WrongStateType)
+        );
+    }
+
     #[test]
     fn animate_brightness_state_serialize_test() {
         let producer =
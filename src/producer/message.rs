@@ -9,10 +9,11 @@ use ross_protocol::convert_packet::ConvertPacket;
 use ross_protocol::event::message::{MessageEvent, MessageValue};
 use ross_protocol::packet::Packet;
 
-use crate::producer::{Producer, ProducerError, MESSAGE_PRODUCER_CODE};
+use crate::producer::{Producer, ProducerError, ProducerTarget, MESSAGE_PRODUCER_CODE};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::ExtractorValue;
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -52,6 +53,22 @@ impl Producer for MessageProducer {
     fn get_code(&self) -> u16 {
         MESSAGE_PRODUCER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![]
+    }
+
+    fn get_target(&self) -> Option<ProducerTarget> {
+        Some(ProducerTarget::Message {
+            receiver_address: self.receiver_address,
+            code: self.code,
+            value: self.value,
+        })
+    }
 }
 
 impl Serialize for MessageProducer {
@@ -1,11 +1,18 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
 use core::fmt::Debug;
 use downcast_rs::{impl_downcast, Downcast};
 
+use ross_protocol::event::bcm::BcmValue;
+use ross_protocol::event::message::MessageValue;
+use ross_protocol::event::relay::RelayValue;
 use ross_protocol::packet::Packet;
 
 use crate::serializer::Serialize;
 use crate::state_manager::StateManager;
-use crate::ExtractorValue;
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType};
 
 mod none;
 pub use none::*;
@@ -30,6 +37,8 @@ pub const BCM_CHANGE_BRIGHTNESS_STATE_PRODUCER_CODE: u16 = 0x0004;
 pub const BCM_ANIMATE_BRIGHTNESS_PRODUCER_CODE: u16 = 0x0005;
 pub const BCM_ANIMATE_BRIGHTNESS_STATE_PRODUCER_CODE: u16 = 0x0006;
 pub const RELAY_SET_VALUE_PRODUCER_CODE: u16 = 0x0007;
+pub const PACKET_BYTES_PRODUCER_CODE: u16 = 0x0008;
+pub const PACKET_BYTES_STATE_PRODUCER_CODE: u16 = 0x0009;
 
 #[derive(Debug, PartialEq)]
 pub enum ProducerError {
@@ -37,6 +46,31 @@ pub enum ProducerError {
     WrongStateType,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProducerTarget {
+    Bcm {
+        bcm_address: u16,
+        index: u8,
+        value: TargetValue<BcmValue>,
+    },
+    Relay {
+        relay_address: u16,
+        index: u8,
+        value: RelayValue,
+    },
+    Message {
+        receiver_address: u16,
+        code: u16,
+        value: MessageValue,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TargetValue<T> {
+    Const(T),
+    State(u32),
+}
+
 pub trait Producer: Downcast + Debug + Serialize {
     fn produce(
         &self,
@@ -45,6 +79,9 @@ pub trait Producer: Downcast + Debug + Serialize {
         device_address: u16,
     ) -> Result<Option<Packet>, ProducerError>;
     fn get_code(&self) -> u16;
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType>;
+    fn get_state_accesses(&self) -> Vec<StateAccess>;
+    fn get_target(&self) -> Option<ProducerTarget>;
 }
 
 impl_downcast!(Producer);
@@ -6,10 +6,11 @@ use alloc::vec::Vec;
 
 use ross_protocol::packet::Packet;
 
-use crate::producer::{Producer, ProducerError, NONE_PRODUCER_CODE};
+use crate::producer::{Producer, ProducerError, ProducerTarget, NONE_PRODUCER_CODE};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::ExtractorValue;
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -34,6 +35,18 @@ impl Producer for NoneProducer {
     fn get_code(&self) -> u16 {
         NONE_PRODUCER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![]
+    }
+
+    fn get_target(&self) -> Option<ProducerTarget> {
+        None
+    }
 }
 
 impl Serialize for NoneProducer {
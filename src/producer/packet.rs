@@ -7,10 +7,14 @@ use core::convert::TryInto;
 
 use ross_protocol::packet::Packet;
 
-use crate::producer::{Producer, ProducerError, PACKET_PRODUCER_CODE};
+use crate::producer::{
+    Producer, ProducerError, ProducerTarget, PACKET_BYTES_PRODUCER_CODE,
+    PACKET_BYTES_STATE_PRODUCER_CODE, PACKET_PRODUCER_CODE,
+};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::ExtractorValue;
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType, Value, ValueType};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -45,6 +49,18 @@ impl Producer for PacketProducer {
     fn get_code(&self) -> u16 {
         PACKET_PRODUCER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        vec![ExtractorValueType::Packet]
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![]
+    }
+
+    fn get_target(&self) -> Option<ProducerTarget> {
+        None
+    }
 }
 
 impl Serialize for PacketProducer {
@@ -67,6 +83,158 @@ impl TryDeserialize for PacketProducer {
     }
 }
 
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub struct PacketBytesProducer {
+    receiver_address: u16,
+}
+
+impl PacketBytesProducer {
+    pub fn new(receiver_address: u16) -> Self {
+        Self { receiver_address }
+    }
+}
+
+impl Producer for PacketBytesProducer {
+    fn produce(
+        &self,
+        value: ExtractorValue,
+        _state_manager: &StateManager,
+        _device_address: u16,
+    ) -> Result<Option<Packet>, ProducerError> {
+        let data = match value {
+            ExtractorValue::Bytes(data) => data.to_vec(),
+            _ => return Err(ProducerError::WrongValueType),
+        };
+
+        Ok(Some(Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }))
+    }
+
+    fn get_code(&self) -> u16 {
+        PACKET_BYTES_PRODUCER_CODE
+    }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        vec![ExtractorValueType::Bytes]
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![]
+    }
+
+    fn get_target(&self) -> Option<ProducerTarget> {
+        None
+    }
+}
+
+impl Serialize for PacketBytesProducer {
+    fn serialize(&self) -> Vec<u8> {
+        let receiver_address = self.receiver_address.to_be_bytes();
+
+        vec![receiver_address[0], receiver_address[1]]
+    }
+}
+
+impl TryDeserialize for PacketBytesProducer {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.len() < 2 {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        let receiver_address = u16::from_be_bytes(data[0..=1].try_into().unwrap());
+
+        Ok(Box::new(Self { receiver_address }))
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub struct PacketBytesStateProducer {
+    receiver_address: u16,
+    state_index: u32,
+}
+
+impl PacketBytesStateProducer {
+    pub fn new(receiver_address: u16, state_index: u32) -> Self {
+        Self {
+            receiver_address,
+            state_index,
+        }
+    }
+}
+
+impl Producer for PacketBytesStateProducer {
+    fn produce(
+        &self,
+        _value: ExtractorValue,
+        state_manager: &StateManager,
+        _device_address: u16,
+    ) -> Result<Option<Packet>, ProducerError> {
+        let data = match state_manager.get_value(self.state_index) {
+            Some(Value::Bytes(data)) => data.as_slice().to_vec(),
+            _ => return Err(ProducerError::WrongStateType),
+        };
+
+        Ok(Some(Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }))
+    }
+
+    fn get_code(&self) -> u16 {
+        PACKET_BYTES_STATE_PRODUCER_CODE
+    }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![StateAccess::Read(self.state_index, vec![ValueType::Bytes])]
+    }
+
+    fn get_target(&self) -> Option<ProducerTarget> {
+        None
+    }
+}
+
+impl Serialize for PacketBytesStateProducer {
+    fn serialize(&self) -> Vec<u8> {
+        let receiver_address = self.receiver_address.to_be_bytes();
+        let state_index = self.state_index.to_be_bytes();
+
+        vec![
+            receiver_address[0],
+            receiver_address[1],
+            state_index[0],
+            state_index[1],
+            state_index[2],
+            state_index[3],
+        ]
+    }
+}
+
+impl TryDeserialize for PacketBytesStateProducer {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.len() < 6 {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        let receiver_address = u16::from_be_bytes(data[0..=1].try_into().unwrap());
+        let state_index = u32::from_be_bytes(data[2..=5].try_into().unwrap());
+
+        Ok(Box::new(Self {
+            receiver_address,
+            state_index,
+        }))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     extern crate alloc;
@@ -130,4 +298,115 @@ mod tests {
             Err(ConfigSerializerError::WrongSize)
         );
     }
+
+    #[test]
+    fn bytes_test() {
+        let mut expected_packet = PACKET;
+        expected_packet.device_address = 0xffff;
+        expected_packet.data = vec![0x61, 0x62, 0x63];
+
+        let state_manager = StateManager::new();
+
+        let producer = PacketBytesProducer::new(0xffff);
+
+        assert_eq!(
+            producer.produce(
+                ExtractorValue::Bytes(&[0x61, 0x62, 0x63]),
+                &state_manager,
+                0x0123
+            ),
+            Ok(Some(expected_packet))
+        );
+    }
+
+    #[test]
+    fn bytes_wrong_value_type_test() {
+        let state_manager = StateManager::new();
+
+        let producer = PacketBytesProducer::new(0xffff);
+
+        assert_eq!(
+            producer.produce(ExtractorValue::U8(0x00), &state_manager, 0x0123),
+            Err(ProducerError::WrongValueType)
+        );
+    }
+
+    #[test]
+    fn bytes_serialize_test() {
+        let producer = PacketBytesProducer::new(0xabab);
+
+        let expected_data = vec![0xab, 0xab];
+
+        assert_eq!(producer.serialize(), expected_data);
+    }
+
+    #[test]
+    fn bytes_deserialize_test() {
+        let data = vec![0xab, 0xab];
+
+        let producer = Box::new(PacketBytesProducer::new(0xabab));
+
+        assert_eq!(PacketBytesProducer::try_deserialize(&data), Ok(producer));
+    }
+
+    #[test]
+    fn bytes_state_test() {
+        let mut expected_packet = PACKET;
+        expected_packet.device_address = 0xffff;
+        expected_packet.data = vec![0x61, 0x62, 0x63];
+
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::try_from_bytes(&[0x61, 0x62, 0x63]).unwrap());
+
+        let producer = PacketBytesStateProducer::new(0xffff, 0);
+
+        assert_eq!(
+            producer.produce(ExtractorValue::None, &state_manager, 0x0123),
+            Ok(Some(expected_packet))
+        );
+    }
+
+    #[test]
+    fn bytes_state_wrong_state_type_test() {
+        let mut state_manager = StateManager::new();
+        state_manager.set_value(0, Value::U8(0x00));
+
+        let producer = PacketBytesStateProducer::new(0xffff, 0);
+
+        assert_eq!(
+            producer.produce(ExtractorValue::None, &state_manager, 0x0123),
+            Err(ProducerError::WrongStateType)
+        );
+    }
+
+    #[test]
+    fn bytes_state_serialize_test() {
+        let producer = PacketBytesStateProducer::new(0xabab, 0x0123_4567);
+
+        let expected_data = vec![0xab, 0xab, 0x01, 0x23, 0x45, 0x67];
+
+        assert_eq!(producer.serialize(), expected_data);
+    }
+
+    #[test]
+    fn bytes_state_deserialize_test() {
+        let data = vec![0xab, 0xab, 0x01, 0x23, 0x45, 0x67];
+
+        let producer = Box::new(PacketBytesStateProducer::new(0xabab, 0x0123_4567));
+
+        assert_eq!(
+            PacketBytesStateProducer::try_deserialize(&data),
+            Ok(producer)
+        );
+    }
+
+    #[test]
+    fn bytes_state_deserialize_wrong_size_test() {
+        let data = vec![0xab, 0xab, 0x01, 0x23, 0x45];
+
+        assert_eq!(
+            PacketBytesStateProducer::try_deserialize(&data),
+            Err(ConfigSerializerError::WrongSize)
+        );
+    }
 }
@@ -9,10 +9,11 @@ use ross_protocol::convert_packet::ConvertPacket;
 use ross_protocol::event::relay::{RelayDoubleExclusiveValue, RelaySetValueEvent, RelayValue};
 use ross_protocol::packet::Packet;
 
-use crate::producer::{Producer, ProducerError, RELAY_SET_VALUE_PRODUCER_CODE};
+use crate::producer::{Producer, ProducerError, ProducerTarget, RELAY_SET_VALUE_PRODUCER_CODE};
 use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
 use crate::state_manager::StateManager;
-use crate::ExtractorValue;
+use crate::validator::StateAccess;
+use crate::{ExtractorValue, ExtractorValueType};
 
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -52,6 +53,22 @@ impl Producer for RelaySetValueProducer {
     fn get_code(&self) -> u16 {
         RELAY_SET_VALUE_PRODUCER_CODE
     }
+
+    fn get_accepted_value_types(&self) -> Vec<ExtractorValueType> {
+        ExtractorValueType::all()
+    }
+
+    fn get_state_accesses(&self) -> Vec<StateAccess> {
+        vec![]
+    }
+
+    fn get_target(&self) -> Option<ProducerTarget> {
+        Some(ProducerTarget::Relay {
+            relay_address: self.relay_address,
+            index: self.index,
+            value: self.value,
+        })
+    }
 }
 
 impl Serialize for RelaySetValueProducer {
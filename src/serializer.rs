@@ -5,14 +5,19 @@ use alloc::collections::BTreeMap;
 use alloc::vec;
 use alloc::vec::Vec;
 
+use crate::calendar::Calendar;
 use crate::config::Config;
 use crate::creator::Creator;
+use crate::cron::CronExpressionError;
 use crate::event_processor::EventProcessor;
+use crate::expression::ExpressionError;
 use crate::extractor::*;
 use crate::filter::*;
+use crate::gesture::GestureTimings;
 use crate::matcher::Matcher;
 use crate::peripheral::Peripheral;
 use crate::producer::*;
+use crate::time_zone::TimeZone;
 use crate::Value;
 
 #[macro_export]
@@ -53,6 +58,10 @@ pub trait TryDeserialize {
     fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError>;
 }
 
+const TIME_ZONE_SECTION_CODE: u8 = 0x00;
+const CALENDAR_SECTION_CODE: u8 = 0x01;
+const GESTURE_TIMINGS_SECTION_CODE: u8 = 0x02;
+
 #[derive(Debug, PartialEq)]
 pub enum ConfigSerializerError {
     WrongSize,
@@ -60,6 +69,16 @@ pub enum ConfigSerializerError {
     UnknownExtractor,
     UnknownFilter,
     UnknownProducer,
+    InvalidCronExpression(CronExpressionError),
+    InvalidTimeZone,
+    InvalidCoordinates,
+    InvalidCalendar,
+    InvalidOverflowMode,
+    InvalidExpression(ExpressionError),
+    InvalidCycleValues,
+    InvalidHysteresisThresholds,
+    FilterTooLong,
+    InvalidComparisonSet,
 }
 
 pub struct ConfigSerializer {}
@@ -117,6 +136,29 @@ impl ConfigSerializer {
             }
         }
 
+        // Optional sections are appended last, so configs without them stay compatible
+        if let Some(time_zone) = &config.time_zone {
+            let mut time_zone = time_zone.serialize();
+            serialize_integer_to_vec!(data, TIME_ZONE_SECTION_CODE, u8);
+            serialize_integer_to_vec!(data, time_zone.len(), u32);
+            data.append(&mut time_zone);
+        }
+
+        for (calendar_index, calendar) in config.calendars.iter() {
+            let mut calendar = calendar.serialize();
+            serialize_integer_to_vec!(data, CALENDAR_SECTION_CODE, u8);
+            serialize_integer_to_vec!(data, calendar.len() + 4, u32);
+            serialize_integer_to_vec!(data, *calendar_index, u32);
+            data.append(&mut calendar);
+        }
+
+        if let Some(gesture_timings) = &config.gesture_timings {
+            let mut gesture_timings = gesture_timings.serialize();
+            serialize_integer_to_vec!(data, GESTURE_TIMINGS_SECTION_CODE, u8);
+            serialize_integer_to_vec!(data, gesture_timings.len(), u32);
+            data.append(&mut gesture_timings);
+        }
+
         Ok(data)
     }
 
@@ -203,10 +245,46 @@ impl ConfigSerializer {
             event_processors.push(EventProcessor { matcher, creators });
         }
 
+        let mut time_zone = None;
+        let mut calendars = BTreeMap::new();
+        let mut gesture_timings = None;
+
+        while offset < data.len() {
+            let section_code = try_deserialize_integer_from_vec!(data, offset, u8);
+            let section_len = try_deserialize_integer_from_vec!(data, offset, u32) as usize;
+
+            if data.len() < offset + section_len {
+                return Err(ConfigSerializerError::WrongSize);
+            }
+
+            let section = &data[offset..offset + section_len];
+            offset += section_len;
+
+            match section_code {
+                TIME_ZONE_SECTION_CODE => time_zone = Some(*TimeZone::try_deserialize(section)?),
+                CALENDAR_SECTION_CODE => {
+                    let mut section_offset = 0;
+
+                    let calendar_index =
+                        try_deserialize_integer_from_vec!(section, section_offset, u32);
+                    let calendar = *Calendar::try_deserialize(&section[section_offset..])?;
+
+                    calendars.insert(calendar_index, calendar);
+                }
+                GESTURE_TIMINGS_SECTION_CODE => {
+                    gesture_timings = Some(*GestureTimings::try_deserialize(section)?)
+                }
+                _ => return Err(ConfigSerializerError::UnknownEnumVariant),
+            }
+        }
+
         Ok(Config {
             peripherals,
             initial_state,
             event_processors,
+            time_zone,
+            calendars,
+            gesture_timings,
         })
     }
 
@@ -224,6 +302,11 @@ impl ConfigSerializer {
             MESSAGE_CODE_EXTRACTOR_CODE => Ok(MessageCodeExtractor::try_deserialize(data)?),
             MESSAGE_VALUE_EXTRACTOR_CODE => Ok(MessageValueExtractor::try_deserialize(data)?),
             BUTTON_INDEX_EXTRACTOR_CODE => Ok(ButtonIndexExtractor::try_deserialize(data)?),
+            PACKET_BYTES_EXTRACTOR_CODE => Ok(PacketBytesExtractor::try_deserialize(data)?),
+            BUTTON_GESTURE_EXTRACTOR_CODE => Ok(ButtonGestureExtractor::try_deserialize(data)?),
+            BUTTON_GESTURE_INDEX_EXTRACTOR_CODE => {
+                Ok(ButtonGestureIndexExtractor::try_deserialize(data)?)
+            }
             _ => Err(ConfigSerializerError::UnknownExtractor),
         }
     }
@@ -262,6 +345,18 @@ impl ConfigSerializer {
             }
             SET_STATE_TO_STATE_FILTER_CODE => Ok(SetStateToStateFilter::try_deserialize(data)?),
             STATE_EQUAL_TO_STATE_FILTER_CODE => Ok(StateEqualToStateFilter::try_deserialize(data)?),
+            TIME_MATCHES_SOLAR_EVENT_FILTER_CODE => {
+                Ok(TimeMatchesSolarEventFilter::try_deserialize(data)?)
+            }
+            CRON_SCHEDULE_FILTER_CODE => Ok(CronScheduleFilter::try_deserialize(data)?),
+            DATE_IN_CALENDAR_FILTER_CODE => Ok(DateInCalendarFilter::try_deserialize(data)?),
+            COMPARISON_FILTER_CODE => Ok(ComparisonFilter::try_deserialize(data)?),
+            EXPRESSION_FILTER_CODE => Ok(ExpressionFilter::try_deserialize(data)?),
+            CYCLE_STATE_FILTER_CODE => Ok(CycleStateFilter::try_deserialize(data)?),
+            DEBOUNCE_FILTER_CODE => Ok(DebounceFilter::try_deserialize(data)?),
+            THROTTLE_FILTER_CODE => Ok(ThrottleFilter::try_deserialize(data)?),
+            HYSTERESIS_FILTER_CODE => Ok(HysteresisFilter::try_deserialize(data)?),
+            STATE_CHANGED_FILTER_CODE => Ok(StateChangedFilter::try_deserialize(data)?),
             _ => Err(ConfigSerializerError::UnknownExtractor),
         }
     }
@@ -287,6 +382,10 @@ impl ConfigSerializer {
                 Ok(BcmAnimateBrightnessStateProducer::try_deserialize(data)?)
             }
             RELAY_SET_VALUE_PRODUCER_CODE => Ok(RelaySetValueProducer::try_deserialize(data)?),
+            PACKET_BYTES_PRODUCER_CODE => Ok(PacketBytesProducer::try_deserialize(data)?),
+            PACKET_BYTES_STATE_PRODUCER_CODE => {
+                Ok(PacketBytesStateProducer::try_deserialize(data)?)
+            }
             _ => Err(ConfigSerializerError::UnknownProducer),
         }
     }
@@ -296,7 +395,9 @@ impl ConfigSerializer {
 mod tests {
     use super::*;
 
+    use crate::calendar::CalendarEntry;
     use crate::peripheral::BcmPeripheral;
+    use crate::time_zone::{DstRule, DstTransition};
 
     #[test]
     fn serialize_test() {
@@ -316,7 +417,10 @@ mod tests {
         event_processors.push(EventProcessor {
             matcher: Matcher::Single {
                 extractor: Box::new(EventCodeExtractor::new()),
-                filter: Box::new(ValueEqualToConstFilter::new(Value::U8(0xff))),
+                filter: Box::new(ValueEqualToConstFilter::new(
+                    Value::U8(0xff),
+                    CoercionPolicy::Strict,
+                )),
             },
             creators: vec![Creator {
                 extractor: Box::new(NoneExtractor::new()),
@@ -329,6 +433,9 @@ mod tests {
             peripherals,
             initial_state,
             event_processors,
+            time_zone: None,
+            calendars: BTreeMap::new(),
+            gesture_timings: None,
         };
 
         let data = ConfigSerializer::serialize(&config).unwrap();
@@ -344,13 +451,14 @@ mod tests {
             0x02, // state value len
             0x00, 0xff, // state value
             0x00, 0x00, 0x00, 0x01, // event processor count
-            0x00, 0x00, 0x00, 0x09, // matcher len
+            0x00, 0x00, 0x00, 0x0a, // matcher len
             0x00, // matcher enum code
             0x00, 0x02, // EVENT_CODE_EXTRACTOR_CODE
             0x00, // extractor len
             0x00, 0x00, // VALUE_EQUAL_TO_CONST_FILTER_CODE
-            0x02, // filter len
+            0x03, // filter len
             0x00, 0xff, // value
+            0x00, // coercion policy
             0x00, 0x00, 0x00, 0x01, // creator count
             0x00, 0x00, // NONE_EXTRACTOR_CODE
             0x00, // extractor len
@@ -389,6 +497,180 @@ mod tests {
         assert_eq!(config.event_processors.len(), 0);
     }
 
+    #[test]
+    fn time_zone_serialize_test() {
+        let config = Config {
+            peripherals: BTreeMap::new(),
+            initial_state: BTreeMap::new(),
+            event_processors: vec![],
+            time_zone: Some(TimeZone {
+                offset: -18000,
+                dst: None,
+            }),
+            calendars: BTreeMap::new(),
+            gesture_timings: None,
+        };
+
+        let expected_data = vec![
+            0x00, 0x00, 0x00, 0x00, // peripheral count
+            0x00, 0x00, 0x00, 0x00, // initial state count
+            0x00, 0x00, 0x00, 0x00, // event processor count
+            0x00, // TIME_ZONE_SECTION_CODE
+            0x00, 0x00, 0x00, 0x05, // section len
+            0xff, 0xff, 0xb9, 0xb0, // offset
+            0x00, // dst exists
+        ];
+
+        assert_eq!(ConfigSerializer::serialize(&config), Ok(expected_data));
+    }
+
+    #[test]
+    fn time_zone_deserialize_test() {
+        let data = vec![
+            0x00, 0x00, 0x00, 0x00, // peripheral count
+            0x00, 0x00, 0x00, 0x00, // initial state count
+            0x00, 0x00, 0x00, 0x00, // event processor count
+            0x00, // TIME_ZONE_SECTION_CODE
+            0x00, 0x00, 0x00, 0x17, // section len
+            0xff, 0xff, 0xb9, 0xb0, // offset
+            0x01, // dst exists
+            0xff, 0xff, 0xc7, 0xc0, // dst offset
+            0x03, 0x02, 0x07, 0x00, 0x00, 0x1c, 0x20, // start
+            0x0b, 0x01, 0x07, 0x00, 0x00, 0x1c, 0x20, // end
+        ];
+
+        let config = ConfigSerializer::deserialize(&data).unwrap();
+
+        assert_eq!(
+            config.time_zone,
+            Some(TimeZone {
+                offset: -18000,
+                dst: Some(DstRule {
+                    offset: -14400,
+                    start: DstTransition {
+                        month: 3,
+                        week: 2,
+                        weekday: 7,
+                        time: 7200,
+                    },
+                    end: DstTransition {
+                        month: 11,
+                        week: 1,
+                        weekday: 7,
+                        time: 7200,
+                    },
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn gesture_timings_round_trip_test() {
+        let data = vec![
+            0x00, 0x00, 0x00, 0x00, // peripheral count
+            0x00, 0x00, 0x00, 0x00, // initial state count
+            0x00, 0x00, 0x00, 0x00, // event processor count
+            0x02, // GESTURE_TIMINGS_SECTION_CODE
+            0x00, 0x00, 0x00, 0x0c, // section len
+            0x00, 0x00, 0x01, 0x2c, // double click window
+            0x00, 0x00, 0x03, 0x20, // long press delay
+            0x00, 0x00, 0x00, 0xc8, // hold repeat interval
+        ];
+
+        let config = ConfigSerializer::deserialize(&data).unwrap();
+
+        assert_eq!(
+            config.gesture_timings,
+            Some(GestureTimings {
+                double_click_window: 300,
+                long_press_delay: 800,
+                hold_repeat_interval: 200,
+            })
+        );
+        assert_eq!(ConfigSerializer::serialize(&config), Ok(data));
+    }
+
+    #[test]
+    fn calendar_serialize_test() {
+        let mut calendars = BTreeMap::new();
+        calendars.insert(
+            0xabab_abab,
+            Calendar {
+                entries: vec![CalendarEntry::YearlyRange((12, 24), (12, 26))],
+            },
+        );
+
+        let config = Config {
+            peripherals: BTreeMap::new(),
+            initial_state: BTreeMap::new(),
+            event_processors: vec![],
+            time_zone: None,
+            calendars,
+            gesture_timings: None,
+        };
+
+        let expected_data = vec![
+            0x00, 0x00, 0x00, 0x00, // peripheral count
+            0x00, 0x00, 0x00, 0x00, // initial state count
+            0x00, 0x00, 0x00, 0x00, // event processor count
+            0x01, // CALENDAR_SECTION_CODE
+            0x00, 0x00, 0x00, 0x0e, // section len
+            0xab, 0xab, 0xab, 0xab, // calendar index
+            0x00, 0x00, 0x00, 0x01, // entry count
+            0x05, // entry len
+            0x01, 0x0c, 0x18, 0x0c, 0x1a, // yearly range
+        ];
+
+        assert_eq!(ConfigSerializer::serialize(&config), Ok(expected_data));
+    }
+
+    #[test]
+    fn calendar_deserialize_test() {
+        let data = vec![
+            0x00, 0x00, 0x00, 0x00, // peripheral count
+            0x00, 0x00, 0x00, 0x00, // initial state count
+            0x00, 0x00, 0x00, 0x00, // event processor count
+            0x01, // CALENDAR_SECTION_CODE
+            0x00, 0x00, 0x00, 0x0e, // section len
+            0xab, 0xab, 0xab, 0xab, // calendar index
+            0x00, 0x00, 0x00, 0x01, // entry count
+            0x05, // entry len
+            0x01, 0x0c, 0x18, 0x0c, 0x1a, // yearly range
+            0x01, // CALENDAR_SECTION_CODE
+            0x00, 0x00, 0x00, 0x08, // section len
+            0x00, 0x00, 0x00, 0x01, // calendar index
+            0x00, 0x00, 0x00, 0x00, // entry count
+        ];
+
+        let config = ConfigSerializer::deserialize(&data).unwrap();
+
+        let mut expected_calendars = BTreeMap::new();
+        expected_calendars.insert(
+            0xabab_abab,
+            Calendar {
+                entries: vec![CalendarEntry::YearlyRange((12, 24), (12, 26))],
+            },
+        );
+        expected_calendars.insert(0x0000_0001, Calendar { entries: vec![] });
+
+        assert_eq!(config.calendars, expected_calendars);
+    }
+
+    #[test]
+    fn unknown_section_deserialize_test() {
+        let data = vec![
+            0x00, 0x00, 0x00, 0x00, // peripheral count
+            0x00, 0x00, 0x00, 0x00, // initial state count
+            0x00, 0x00, 0x00, 0x00, // event processor count
+            0xff, // section code
+            0x00, 0x00, 0x00, 0x00, // section len
+        ];
+
+        let err = ConfigSerializer::deserialize(&data).unwrap_err();
+
+        assert_eq!(ConfigSerializerError::UnknownEnumVariant, err);
+    }
+
     #[test]
     fn deserialize_test() {
         let data = vec![
@@ -453,14 +735,20 @@ mod tests {
         event_processors.push(EventProcessor {
             matcher: Matcher::Single {
                 extractor: Box::new(EventCodeExtractor::new()),
-                filter: Box::new(ValueEqualToConstFilter::new(Value::U8(0xff))),
+                filter: Box::new(ValueEqualToConstFilter::new(
+                    Value::U8(0xff),
+                    CoercionPolicy::Strict,
+                )),
             },
             creators: vec![Creator {
                 extractor: Box::new(NoneExtractor::new()),
                 producer: Box::new(BcmChangeBrightnessStateProducer::new(0xabab, 0xff, 0)),
                 matcher: Some(Matcher::Single {
                     extractor: Box::new(EventCodeExtractor::new()),
-                    filter: Box::new(ValueEqualToConstFilter::new(Value::U8(0xff))),
+                    filter: Box::new(ValueEqualToConstFilter::new(
+                        Value::U8(0xff),
+                        CoercionPolicy::Strict,
+                    )),
                 }),
             }],
         });
@@ -469,6 +757,9 @@ mod tests {
             peripherals,
             initial_state,
             event_processors,
+            time_zone: None,
+            calendars: BTreeMap::new(),
+            gesture_timings: None,
         };
 
         let data = ConfigSerializer::serialize(&config).unwrap();
@@ -484,13 +775,14 @@ mod tests {
             0x02, // state_value len
             0x00, 0xff, // state_value
             0x00, 0x00, 0x00, 0x01, // event processor count
-            0x00, 0x00, 0x00, 0x09, // matcher len
+            0x00, 0x00, 0x00, 0x0a, // matcher len
             0x00, // matcher enum code
             0x00, 0x02, // EVENT_CODE_EXTRACTOR_CODE
             0x00, // extractor len
             0x00, 0x00, // VALUE_EQUAL_TO_CONST_FILTER_CODE
-            0x02, // filter len
+            0x03, // filter len
             0x00, 0xff, // value
+            0x00, // coercion policy
             0x00, 0x00, 0x00, 0x01, // creator count
             0x00, 0x00, // NONE_EXTRACTOR_CODE
             0x00, // extractor len
@@ -500,13 +792,14 @@ mod tests {
             0xff, // channel
             0x00, 0x00, 0x00, 0x00, // state_index
             0x01, // matcher exists
-            0x00, 0x00, 0x00, 0x09, // matcher len
+            0x00, 0x00, 0x00, 0x0a, // matcher len
             0x00, // matcher enum code
             0x00, 0x02, // EVENT_CODE_EXTRACTOR_CODE
             0x00, // extractor len
             0x00, 0x00, // VALUE_EQUAL_TO_CONST_FILTER_CODE
-            0x02, // filter len
+            0x03, // filter len
             0x00, 0xff, // value
+            0x00, // coercion policy
         ];
 
         assert_eq!(data, expected_data);
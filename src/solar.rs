@@ -0,0 +1,157 @@
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Utc};
+use core::convert::TryFrom;
+use libm::{acos, asin, cos, floor, fmod, round, sin};
+
+use crate::serializer::ConfigSerializerError;
+
+const JULIAN_DAY_J2000: f64 = 2451545.0;
+const JULIAN_DAY_UNIX_EPOCH: f64 = 2440587.5;
+const EARTH_AXIAL_TILT: f64 = 23.4397;
+
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SolarEvent {
+    Sunrise = 0x00,
+    Sunset = 0x01,
+    CivilDawn = 0x02,
+    CivilDusk = 0x03,
+}
+
+impl SolarEvent {
+    // Elevation of the center of the sun in degrees, accounting for refraction and the solar disc
+    fn get_elevation(&self) -> f64 {
+        match self {
+            SolarEvent::Sunrise | SolarEvent::Sunset => -0.833,
+            SolarEvent::CivilDawn | SolarEvent::CivilDusk => -6.0,
+        }
+    }
+
+    fn is_morning(&self) -> bool {
+        matches!(self, SolarEvent::Sunrise | SolarEvent::CivilDawn)
+    }
+
+    // Returns the time of the event on the given UTC date, `None` if the sun never reaches the
+    // event elevation on that day
+    pub fn get_date_time(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        date: &NaiveDate,
+    ) -> Option<DateTime<Utc>> {
+        let julian_day = date.num_days_from_ce() as f64 + 1721424.5;
+        let day = floor(julian_day - JULIAN_DAY_J2000 + 0.5 + 0.0008);
+
+        let mean_solar_time = day - longitude / 360.0;
+        let mean_anomaly = normalize_degrees(357.5291 + 0.98560028 * mean_solar_time);
+        let center = 1.9148 * sin(mean_anomaly.to_radians())
+            + 0.02 * sin((2.0 * mean_anomaly).to_radians())
+            + 0.0003 * sin((3.0 * mean_anomaly).to_radians());
+        let ecliptic_longitude = normalize_degrees(mean_anomaly + center + 180.0 + 102.9372);
+        let solar_transit =
+            JULIAN_DAY_J2000 + mean_solar_time + 0.0053 * sin(mean_anomaly.to_radians())
+                - 0.0069 * sin((2.0 * ecliptic_longitude).to_radians());
+
+        let declination =
+            asin(sin(ecliptic_longitude.to_radians()) * sin(EARTH_AXIAL_TILT.to_radians()));
+        let latitude = latitude.to_radians();
+
+        let cos_hour_angle = (sin(self.get_elevation().to_radians())
+            - sin(latitude) * sin(declination))
+            / (cos(latitude) * cos(declination));
+
+        if !(-1.0..=1.0).contains(&cos_hour_angle) {
+            return None;
+        }
+
+        let hour_angle = acos(cos_hour_angle).to_degrees();
+
+        let event_julian_day = if self.is_morning() {
+            solar_transit - hour_angle / 360.0
+        } else {
+            solar_transit + hour_angle / 360.0
+        };
+
+        let timestamp = round((event_julian_day - JULIAN_DAY_UNIX_EPOCH) * 86400.0) as i64;
+
+        NaiveDateTime::from_timestamp_opt(timestamp, 0)
+            .map(|date_time| DateTime::from_utc(date_time, Utc))
+    }
+}
+
+fn normalize_degrees(degrees: f64) -> f64 {
+    let degrees = fmod(degrees, 360.0);
+
+    if degrees < 0.0 {
+        degrees + 360.0
+    } else {
+        degrees
+    }
+}
+
+impl TryFrom<u8> for SolarEvent {
+    type Error = ConfigSerializerError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x00 => Ok(SolarEvent::Sunrise),
+            0x01 => Ok(SolarEvent::Sunset),
+            0x02 => Ok(SolarEvent::CivilDawn),
+            0x03 => Ok(SolarEvent::CivilDusk),
+            _ => Err(ConfigSerializerError::UnknownEnumVariant),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::str::FromStr;
+
+    fn assert_close(date_time: Option<DateTime<Utc>>, expected: &str) {
+        let expected: DateTime<Utc> = DateTime::from_str(expected).unwrap();
+        let difference = (date_time.unwrap() - expected).num_seconds().abs();
+
+        assert!(difference < 120, "{:?} != {:?}", date_time, expected);
+    }
+
+    #[test]
+    fn sunrise_sunset_test() {
+        // Vilnius on the summer solstice
+        let date = NaiveDate::from_ymd(2022, 6, 21);
+
+        assert_close(
+            SolarEvent::Sunrise.get_date_time(54.6872, 25.2797, &date),
+            "2022-06-21T01:42:00Z",
+        );
+        assert_close(
+            SolarEvent::Sunset.get_date_time(54.6872, 25.2797, &date),
+            "2022-06-21T19:00:00Z",
+        );
+    }
+
+    #[test]
+    fn civil_twilight_test() {
+        // New York on the winter solstice
+        let date = NaiveDate::from_ymd(2022, 12, 21);
+
+        assert_close(
+            SolarEvent::CivilDawn.get_date_time(40.7128, -74.006, &date),
+            "2022-12-21T11:47:00Z",
+        );
+        assert_close(
+            SolarEvent::CivilDusk.get_date_time(40.7128, -74.006, &date),
+            "2022-12-21T22:02:00Z",
+        );
+    }
+
+    #[test]
+    fn polar_day_test() {
+        let date = NaiveDate::from_ymd(2022, 6, 21);
+
+        assert_eq!(
+            SolarEvent::Sunset.get_date_time(78.2232, 15.6267, &date),
+            None
+        );
+    }
+}
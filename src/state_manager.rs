@@ -3,11 +3,15 @@ extern crate alloc;
 use alloc::collections::BTreeMap;
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 
+use crate::calendar::Calendar;
+use crate::time_zone::TimeZone;
 use crate::Value;
 
 pub struct StateManager {
     state: BTreeMap<u32, Value>,
     date_time: DateTime<Utc>,
+    time_zone: Option<TimeZone>,
+    calendars: BTreeMap<u32, Calendar>,
 }
 
 impl StateManager {
@@ -21,6 +25,8 @@ impl StateManager {
                 ),
                 Utc,
             ),
+            time_zone: None,
+            calendars: BTreeMap::new(),
         }
     }
 
@@ -39,4 +45,32 @@ impl StateManager {
     pub fn set_date_time(&mut self, date_time: DateTime<Utc>) {
         self.date_time = date_time;
     }
+
+    pub fn get_time_zone(&self) -> Option<&TimeZone> {
+        self.time_zone.as_ref()
+    }
+
+    pub fn set_time_zone(&mut self, time_zone: Option<TimeZone>) {
+        self.time_zone = time_zone;
+    }
+
+    pub fn get_calendar(&self, index: u32) -> Option<&Calendar> {
+        self.calendars.get(&index)
+    }
+
+    pub fn set_calendar(&mut self, index: u32, calendar: Calendar) {
+        self.calendars.insert(index, calendar);
+    }
+
+    pub fn remove_calendar(&mut self, index: u32) {
+        self.calendars.remove(&index);
+    }
+
+    // Local date and time if a time zone is set, UTC otherwise
+    pub fn get_local_date_time(&self) -> NaiveDateTime {
+        match &self.time_zone {
+            Some(time_zone) => time_zone.to_local(&self.date_time).get_date_time(),
+            None => self.date_time.naive_utc(),
+        }
+    }
 }
@@ -0,0 +1,400 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Utc};
+
+use crate::serializer::{ConfigSerializerError, Serialize, TryDeserialize};
+use crate::{serialize_integer_to_vec, try_deserialize_integer_from_vec};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeZone {
+    // Standard offset from UTC in seconds, positive east of Greenwich
+    pub offset: i32,
+    pub dst: Option<DstRule>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DstRule {
+    // Offset from UTC in seconds while daylight saving time is in effect
+    pub offset: i32,
+    pub start: DstTransition,
+    pub end: DstTransition,
+}
+
+// Same semantics as the POSIX TZ `Mm.w.d/time` rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct DstTransition {
+    pub month: u8,
+    // 1 to 4 for the n-th occurrence of the weekday in the month, 5 for the last one
+    pub week: u8,
+    // Starting from Monday as 1
+    pub weekday: u8,
+    // Seconds since local midnight, in the local time in effect before the transition
+    pub time: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LocalDateTime {
+    Regular(NaiveDateTime),
+    // Falls within one DST offset after clocks jump forward, along with the skipped local time
+    AfterGap(NaiveDateTime, NaiveDateTime),
+    // Second occurrence of a local time after clocks fall back
+    Repeated(NaiveDateTime),
+}
+
+impl LocalDateTime {
+    pub fn get_date_time(&self) -> NaiveDateTime {
+        match self {
+            LocalDateTime::Regular(date_time)
+            | LocalDateTime::AfterGap(date_time, _)
+            | LocalDateTime::Repeated(date_time) => *date_time,
+        }
+    }
+}
+
+impl DstTransition {
+    fn is_valid(&self) -> bool {
+        (1..=12).contains(&self.month)
+            && (1..=5).contains(&self.week)
+            && (1..=7).contains(&self.weekday)
+    }
+
+    fn get_local_date_time(&self, year: i32) -> Option<NaiveDateTime> {
+        // Transitions built directly are not checked when deserializing
+        if !self.is_valid() {
+            return None;
+        }
+
+        let first_day = NaiveDate::from_ymd_opt(year, self.month as u32, 1)?;
+        let first_weekday = first_day.weekday().number_from_monday();
+
+        let mut day =
+            1 + (7 + self.weekday as u32 - first_weekday) % 7 + (self.week as u32 - 1) * 7;
+
+        while NaiveDate::from_ymd_opt(year, self.month as u32, day).is_none() {
+            day -= 7;
+        }
+
+        let date_time =
+            NaiveDate::from_ymd_opt(year, self.month as u32, day)?.and_hms_opt(0, 0, 0)?;
+
+        Some(date_time + Duration::seconds(self.time as i64))
+    }
+}
+
+impl TimeZone {
+    pub fn get_offset(&self, date_time: &DateTime<Utc>) -> i32 {
+        match &self.dst {
+            Some(dst) if self.is_dst(dst, &date_time.naive_utc()) => dst.offset,
+            _ => self.offset,
+        }
+    }
+
+    pub fn to_local(&self, date_time: &DateTime<Utc>) -> LocalDateTime {
+        let date_time = date_time.naive_utc();
+
+        let dst = match &self.dst {
+            Some(dst) => dst,
+            None => {
+                return LocalDateTime::Regular(date_time + Duration::seconds(self.offset as i64))
+            }
+        };
+
+        let (start, end) = match self.get_transitions(dst, &date_time) {
+            Some(transitions) => transitions,
+            None => {
+                return LocalDateTime::Regular(date_time + Duration::seconds(self.offset as i64))
+            }
+        };
+
+        let delta = Duration::seconds(dst.offset as i64 - self.offset as i64);
+
+        if self.is_dst(dst, &date_time) {
+            let local_date_time = date_time + Duration::seconds(dst.offset as i64);
+
+            if date_time >= start && date_time < start + delta {
+                LocalDateTime::AfterGap(local_date_time, local_date_time - delta)
+            } else {
+                LocalDateTime::Regular(local_date_time)
+            }
+        } else {
+            let local_date_time = date_time + Duration::seconds(self.offset as i64);
+
+            if date_time >= end && date_time < end + delta {
+                LocalDateTime::Repeated(local_date_time)
+            } else {
+                LocalDateTime::Regular(local_date_time)
+            }
+        }
+    }
+
+    fn is_dst(&self, dst: &DstRule, date_time: &NaiveDateTime) -> bool {
+        match self.get_transitions(dst, date_time) {
+            // Southern hemisphere rules start daylight saving time later in the year than they end it
+            Some((start, end)) if start > end => *date_time >= start || *date_time < end,
+            Some((start, end)) => *date_time >= start && *date_time < end,
+            None => false,
+        }
+    }
+
+    // Returns the UTC instants daylight saving time starts and ends at in the local year
+    fn get_transitions(
+        &self,
+        dst: &DstRule,
+        date_time: &NaiveDateTime,
+    ) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        let year = (*date_time + Duration::seconds(self.offset as i64)).year();
+
+        let start = dst.start.get_local_date_time(year)? - Duration::seconds(self.offset as i64);
+        let end = dst.end.get_local_date_time(year)? - Duration::seconds(dst.offset as i64);
+
+        Some((start, end))
+    }
+}
+
+impl Serialize for DstTransition {
+    fn serialize(&self) -> Vec<u8> {
+        let mut data = vec![self.month, self.week, self.weekday];
+
+        serialize_integer_to_vec!(data, self.time, i32);
+
+        data
+    }
+}
+
+impl TryDeserialize for DstTransition {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        if data.len() < 7 {
+            return Err(ConfigSerializerError::WrongSize);
+        }
+
+        let mut offset = 3;
+
+        #[allow(unused_assignments)]
+        let time = try_deserialize_integer_from_vec!(data, offset, i32);
+
+        let transition = Self {
+            month: data[0],
+            week: data[1],
+            weekday: data[2],
+            time,
+        };
+
+        if !transition.is_valid() {
+            return Err(ConfigSerializerError::InvalidTimeZone);
+        }
+
+        Ok(Box::new(transition))
+    }
+}
+
+impl Serialize for TimeZone {
+    fn serialize(&self) -> Vec<u8> {
+        let mut data = vec![];
+
+        serialize_integer_to_vec!(data, self.offset, i32);
+
+        match &self.dst {
+            Some(dst) => {
+                data.push(0x01);
+                serialize_integer_to_vec!(data, dst.offset, i32);
+                data.append(&mut dst.start.serialize());
+                data.append(&mut dst.end.serialize());
+            }
+            None => data.push(0x00),
+        }
+
+        data
+    }
+}
+
+impl TryDeserialize for TimeZone {
+    fn try_deserialize(data: &[u8]) -> Result<Box<Self>, ConfigSerializerError> {
+        let mut offset = 0;
+
+        let time_zone_offset = try_deserialize_integer_from_vec!(data, offset, i32);
+        let dst_exists = try_deserialize_integer_from_vec!(data, offset, u8) != 0;
+
+        let dst = if dst_exists {
+            let dst_offset = try_deserialize_integer_from_vec!(data, offset, i32);
+
+            if data.len() < offset + 14 {
+                return Err(ConfigSerializerError::WrongSize);
+            }
+
+            let start = *DstTransition::try_deserialize(&data[offset..offset + 7])?;
+            let end = *DstTransition::try_deserialize(&data[offset + 7..offset + 14])?;
+
+            Some(DstRule {
+                offset: dst_offset,
+                start,
+                end,
+            })
+        } else {
+            None
+        };
+
+        Ok(Box::new(Self {
+            offset: time_zone_offset,
+            dst,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::str::FromStr;
+
+    fn central_european_time() -> TimeZone {
+        TimeZone {
+            offset: 3600,
+            dst: Some(DstRule {
+                offset: 7200,
+                start: DstTransition {
+                    month: 3,
+                    week: 5,
+                    weekday: 7,
+                    time: 7200,
+                },
+                end: DstTransition {
+                    month: 10,
+                    week: 5,
+                    weekday: 7,
+                    time: 10800,
+                },
+            }),
+        }
+    }
+
+    fn local(date_time: &str) -> NaiveDateTime {
+        NaiveDateTime::from_str(date_time).unwrap()
+    }
+
+    fn utc(date_time: &str) -> DateTime<Utc> {
+        DateTime::from_str(date_time).unwrap()
+    }
+
+    #[test]
+    fn get_offset_test() {
+        let time_zone = central_european_time();
+
+        assert_eq!(time_zone.get_offset(&utc("2022-01-15T12:00:00Z")), 3600);
+        assert_eq!(time_zone.get_offset(&utc("2022-07-15T12:00:00Z")), 7200);
+        assert_eq!(time_zone.get_offset(&utc("2022-03-27T00:59:59Z")), 3600);
+        assert_eq!(time_zone.get_offset(&utc("2022-03-27T01:00:00Z")), 7200);
+        assert_eq!(time_zone.get_offset(&utc("2022-10-30T00:59:59Z")), 7200);
+        assert_eq!(time_zone.get_offset(&utc("2022-10-30T01:00:00Z")), 3600);
+    }
+
+    #[test]
+    fn get_offset_southern_hemisphere_test() {
+        // Australian Eastern Time, from the first Sunday in October to the first Sunday in April
+        let time_zone = TimeZone {
+            offset: 36000,
+            dst: Some(DstRule {
+                offset: 39600,
+                start: DstTransition {
+                    month: 10,
+                    week: 1,
+                    weekday: 7,
+                    time: 7200,
+                },
+                end: DstTransition {
+                    month: 4,
+                    week: 1,
+                    weekday: 7,
+                    time: 10800,
+                },
+            }),
+        };
+
+        assert_eq!(time_zone.get_offset(&utc("2022-01-15T12:00:00Z")), 39600);
+        assert_eq!(time_zone.get_offset(&utc("2022-07-15T12:00:00Z")), 36000);
+    }
+
+    #[test]
+    fn get_offset_invalid_transition_test() {
+        let mut time_zone = central_european_time();
+
+        if let Some(dst) = time_zone.dst.as_mut() {
+            dst.start.week = 0;
+        }
+
+        assert_eq!(time_zone.get_offset(&utc("2022-07-15T12:00:00Z")), 3600);
+    }
+
+    #[test]
+    fn to_local_test() {
+        let time_zone = central_european_time();
+
+        assert_eq!(
+            time_zone.to_local(&utc("2022-07-15T12:00:00Z")),
+            LocalDateTime::Regular(local("2022-07-15T14:00:00"))
+        );
+        assert_eq!(
+            time_zone.to_local(&utc("2022-03-27T01:30:00Z")),
+            LocalDateTime::AfterGap(local("2022-03-27T03:30:00"), local("2022-03-27T02:30:00"))
+        );
+        assert_eq!(
+            time_zone.to_local(&utc("2022-03-27T02:00:00Z")),
+            LocalDateTime::Regular(local("2022-03-27T04:00:00"))
+        );
+        assert_eq!(
+            time_zone.to_local(&utc("2022-10-30T00:30:00Z")),
+            LocalDateTime::Regular(local("2022-10-30T02:30:00"))
+        );
+        assert_eq!(
+            time_zone.to_local(&utc("2022-10-30T01:30:00Z")),
+            LocalDateTime::Repeated(local("2022-10-30T02:30:00"))
+        );
+    }
+
+    #[test]
+    fn serialize_test() {
+        let expected_data = vec![
+            0x00, 0x00, 0x0e, 0x10, // offset
+            0x01, // dst exists
+            0x00, 0x00, 0x1c, 0x20, // dst offset
+            0x03, 0x05, 0x07, 0x00, 0x00, 0x1c, 0x20, // start
+            0x0a, 0x05, 0x07, 0x00, 0x00, 0x2a, 0x30, // end
+        ];
+
+        assert_eq!(central_european_time().serialize(), expected_data);
+    }
+
+    #[test]
+    fn deserialize_test() {
+        let data = vec![
+            0x00, 0x00, 0x0e, 0x10, // offset
+            0x01, // dst exists
+            0x00, 0x00, 0x1c, 0x20, // dst offset
+            0x03, 0x05, 0x07, 0x00, 0x00, 0x1c, 0x20, // start
+            0x0a, 0x05, 0x07, 0x00, 0x00, 0x2a, 0x30, // end
+        ];
+
+        assert_eq!(
+            TimeZone::try_deserialize(&data),
+            Ok(Box::new(central_european_time()))
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_transition_test() {
+        let data = vec![
+            0x00, 0x00, 0x0e, 0x10, // offset
+            0x01, // dst exists
+            0x00, 0x00, 0x1c, 0x20, // dst offset
+            0x0d, 0x05, 0x07, 0x00, 0x00, 0x1c, 0x20, // start
+            0x0a, 0x05, 0x07, 0x00, 0x00, 0x2a, 0x30, // end
+        ];
+
+        assert_eq!(
+            TimeZone::try_deserialize(&data),
+            Err(ConfigSerializerError::InvalidTimeZone)
+        );
+    }
+}
@@ -0,0 +1,923 @@
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use ross_protocol::event::bcm::BcmValue;
+use ross_protocol::event::relay::RelayValue;
+
+use crate::config::Config;
+use crate::filter::{CoercionPolicy, DateInCalendarFilter};
+use crate::matcher::Matcher;
+use crate::peripheral::{BcmPeripheral, Peripheral, RelayPeripheral};
+use crate::producer::{ProducerTarget, TargetValue};
+use crate::{ExtractorValueType, ValueType};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StateAccess {
+    // Reads the state, which has to be of one of the listed types
+    Read(u32, Vec<ValueType>),
+    // Writes the state, the type is `None` if it is only known at runtime
+    Write(u32, Option<ValueType>),
+    // Copies the second state into the first one
+    Copy(u32, u32),
+    // Compares the first state with the second one, which has to be of a comparable type
+    Compare(u32, u32, CoercionPolicy),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ComponentLocation {
+    EventProcessor(usize),
+    Creator(usize, usize),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ValidationError {
+    StateTypeMismatch {
+        location: ComponentLocation,
+        state_index: u32,
+        state_type: ValueType,
+        accepted_types: Vec<ValueType>,
+    },
+    ConflictingStateWrite {
+        location: ComponentLocation,
+        state_index: u32,
+        state_type: ValueType,
+        written_type: ValueType,
+    },
+    UninitializedState {
+        location: ComponentLocation,
+        state_index: u32,
+    },
+    IncompatibleValueType {
+        location: ComponentLocation,
+        value_types: Vec<ExtractorValueType>,
+        accepted_types: Vec<ExtractorValueType>,
+    },
+    UnknownPeripheral {
+        location: ComponentLocation,
+        peripheral_index: u32,
+    },
+    PeripheralTypeMismatch {
+        location: ComponentLocation,
+        peripheral_index: u32,
+    },
+    UnknownGatewayAddress {
+        location: ComponentLocation,
+        peripheral_index: u32,
+        address: u16,
+    },
+    IncompatiblePeripheralValue {
+        location: ComponentLocation,
+        peripheral_index: u32,
+    },
+    UnknownCalendar {
+        location: ComponentLocation,
+        calendar_index: u32,
+    },
+}
+
+pub fn infer_state_types(config: &Config) -> BTreeMap<u32, ValueType> {
+    infer_state_types_from_accesses(config, &collect_state_accesses(config))
+}
+
+pub fn validate_state_types(config: &Config) -> Vec<ValidationError> {
+    let accesses = collect_state_accesses(config);
+    let state_types = infer_state_types_from_accesses(config, &accesses);
+
+    let mut errors = vec![];
+
+    for (location, access) in accesses.into_iter() {
+        match access {
+            StateAccess::Read(state_index, accepted_types) => {
+                if !config.initial_state.contains_key(&state_index) {
+                    errors.push(ValidationError::UninitializedState {
+                        location,
+                        state_index,
+                    });
+                }
+
+                if let Some(state_type) = state_types.get(&state_index) {
+                    if !accepted_types.contains(state_type) {
+                        errors.push(ValidationError::StateTypeMismatch {
+                            location,
+                            state_index,
+                            state_type: *state_type,
+                            accepted_types,
+                        });
+                    }
+                }
+            }
+            StateAccess::Write(state_index, Some(written_type)) => {
+                if let Some(state_type) = state_types.get(&state_index) {
+                    if *state_type != written_type {
+                        errors.push(ValidationError::ConflictingStateWrite {
+                            location,
+                            state_index,
+                            state_type: *state_type,
+                            written_type,
+                        });
+                    }
+                }
+            }
+            StateAccess::Write(_, None) => {}
+            StateAccess::Copy(state_index, source_state_index) => {
+                if let (Some(state_type), Some(written_type)) = (
+                    state_types.get(&state_index),
+                    state_types.get(&source_state_index),
+                ) {
+                    if state_type != written_type {
+                        errors.push(ValidationError::ConflictingStateWrite {
+                            location,
+                            state_index,
+                            state_type: *state_type,
+                            written_type: *written_type,
+                        });
+                    }
+                }
+            }
+            StateAccess::Compare(state_index, target_state_index, coercion_policy) => {
+                if let (Some(state_type), Some(target_type)) = (
+                    state_types.get(&state_index),
+                    state_types.get(&target_state_index),
+                ) {
+                    let accepted_types = coercion_policy.get_accepted_value_types(*target_type);
+
+                    if !accepted_types.contains(state_type) {
+                        errors.push(ValidationError::StateTypeMismatch {
+                            location,
+                            state_index,
+                            state_type: *state_type,
+                            accepted_types,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+pub fn collect_state_accesses(config: &Config) -> Vec<(ComponentLocation, StateAccess)> {
+    let mut accesses = vec![];
+
+    for (event_processor_index, event_processor) in config.event_processors.iter().enumerate() {
+        let location = ComponentLocation::EventProcessor(event_processor_index);
+
+        for access in collect_matcher_state_accesses(&event_processor.matcher) {
+            accesses.push((location, access));
+        }
+
+        for (creator_index, creator) in event_processor.creators.iter().enumerate() {
+            let location = ComponentLocation::Creator(event_processor_index, creator_index);
+
+            if let Some(matcher) = &creator.matcher {
+                for access in collect_matcher_state_accesses(matcher) {
+                    accesses.push((location, access));
+                }
+            }
+
+            for access in creator.producer.get_state_accesses() {
+                accesses.push((location, access));
+            }
+        }
+    }
+
+    accesses
+}
+
+pub fn validate_value_types(config: &Config) -> Vec<ValidationError> {
+    let mut errors = vec![];
+
+    for (event_processor_index, event_processor) in config.event_processors.iter().enumerate() {
+        let location = ComponentLocation::EventProcessor(event_processor_index);
+
+        validate_matcher_value_types(&event_processor.matcher, location, &mut errors);
+
+        for (creator_index, creator) in event_processor.creators.iter().enumerate() {
+            let location = ComponentLocation::Creator(event_processor_index, creator_index);
+
+            if let Some(matcher) = &creator.matcher {
+                validate_matcher_value_types(matcher, location, &mut errors);
+            }
+
+            validate_value_type_pair(
+                creator.extractor.get_value_types(),
+                creator.producer.get_accepted_value_types(),
+                location,
+                &mut errors,
+            );
+        }
+    }
+
+    errors
+}
+
+pub fn validate_peripheral_references(config: &Config) -> Vec<ValidationError> {
+    let state_types = infer_state_types(config);
+
+    let mut errors = vec![];
+
+    for (event_processor_index, event_processor) in config.event_processors.iter().enumerate() {
+        for (creator_index, creator) in event_processor.creators.iter().enumerate() {
+            let location = ComponentLocation::Creator(event_processor_index, creator_index);
+
+            let target = match creator.producer.get_target() {
+                Some(target) => target,
+                None => continue,
+            };
+
+            let peripheral_index = match target {
+                ProducerTarget::Bcm { index, .. } | ProducerTarget::Relay { index, .. } => {
+                    index as u32
+                }
+                ProducerTarget::Message { .. } => continue,
+            };
+
+            let peripheral = match config.peripherals.get(&peripheral_index) {
+                Some(peripheral) => peripheral,
+                None => {
+                    errors.push(ValidationError::UnknownPeripheral {
+                        location,
+                        peripheral_index,
+                    });
+                    continue;
+                }
+            };
+
+            let (address, gateway_addresses, is_compatible) = match (&target, peripheral) {
+                (
+                    ProducerTarget::Bcm {
+                        bcm_address, value, ..
+                    },
+                    Peripheral::Bcm(peripheral, gateway_addresses),
+                ) => (
+                    *bcm_address,
+                    gateway_addresses,
+                    is_bcm_value_compatible(peripheral, value, &state_types),
+                ),
+                (
+                    ProducerTarget::Relay {
+                        relay_address,
+                        value,
+                        ..
+                    },
+                    Peripheral::Relay(peripheral, gateway_addresses),
+                ) => (
+                    *relay_address,
+                    gateway_addresses,
+                    is_relay_value_compatible(peripheral, value),
+                ),
+                _ => {
+                    errors.push(ValidationError::PeripheralTypeMismatch {
+                        location,
+                        peripheral_index,
+                    });
+                    continue;
+                }
+            };
+
+            if !gateway_addresses.contains(&address) {
+                errors.push(ValidationError::UnknownGatewayAddress {
+                    location,
+                    peripheral_index,
+                    address,
+                });
+            }
+
+            if !is_compatible {
+                errors.push(ValidationError::IncompatiblePeripheralValue {
+                    location,
+                    peripheral_index,
+                });
+            }
+        }
+    }
+
+    errors
+}
+
+pub fn validate_calendar_references(config: &Config) -> Vec<ValidationError> {
+    let mut errors = vec![];
+
+    for (event_processor_index, event_processor) in config.event_processors.iter().enumerate() {
+        let location = ComponentLocation::EventProcessor(event_processor_index);
+
+        validate_matcher_calendar_references(
+            config,
+            &event_processor.matcher,
+            location,
+            &mut errors,
+        );
+
+        for (creator_index, creator) in event_processor.creators.iter().enumerate() {
+            let location = ComponentLocation::Creator(event_processor_index, creator_index);
+
+            if let Some(matcher) = &creator.matcher {
+                validate_matcher_calendar_references(config, matcher, location, &mut errors);
+            }
+        }
+    }
+
+    errors
+}
+
+fn collect_matcher_state_accesses(matcher: &Matcher) -> Vec<StateAccess> {
+    match matcher {
+        Matcher::Single { filter, .. } => filter.get_state_accesses(),
+        Matcher::Not(matcher) => collect_matcher_state_accesses(matcher),
+        Matcher::Or(matcher1, matcher2) | Matcher::And(matcher1, matcher2) => {
+            let mut accesses = collect_matcher_state_accesses(matcher1);
+            accesses.append(&mut collect_matcher_state_accesses(matcher2));
+
+            accesses
+        }
+    }
+}
+
+fn validate_matcher_value_types(
+    matcher: &Matcher,
+    location: ComponentLocation,
+    errors: &mut Vec<ValidationError>,
+) {
+    match matcher {
+        Matcher::Single { extractor, filter } => validate_value_type_pair(
+            extractor.get_value_types(),
+            filter.get_accepted_value_types(),
+            location,
+            errors,
+        ),
+        Matcher::Not(matcher) => validate_matcher_value_types(matcher, location, errors),
+        Matcher::Or(matcher1, matcher2) | Matcher::And(matcher1, matcher2) => {
+            validate_matcher_value_types(matcher1, location, errors);
+            validate_matcher_value_types(matcher2, location, errors);
+        }
+    }
+}
+
+fn validate_matcher_calendar_references(
+    config: &Config,
+    matcher: &Matcher,
+    location: ComponentLocation,
+    errors: &mut Vec<ValidationError>,
+) {
+    match matcher {
+        Matcher::Single { filter, .. } => {
+            if let Some(filter) = filter.downcast_ref::<DateInCalendarFilter>() {
+                let calendar_index = filter.get_calendar_index();
+
+                if !config.calendars.contains_key(&calendar_index) {
+                    errors.push(ValidationError::UnknownCalendar {
+                        location,
+                        calendar_index,
+                    });
+                }
+            }
+        }
+        Matcher::Not(matcher) => {
+            validate_matcher_calendar_references(config, matcher, location, errors)
+        }
+        Matcher::Or(matcher1, matcher2) | Matcher::And(matcher1, matcher2) => {
+            validate_matcher_calendar_references(config, matcher1, location, errors);
+            validate_matcher_calendar_references(config, matcher2, location, errors);
+        }
+    }
+}
+
+fn validate_value_type_pair(
+    value_types: Vec<ExtractorValueType>,
+    accepted_types: Vec<ExtractorValueType>,
+    location: ComponentLocation,
+    errors: &mut Vec<ValidationError>,
+) {
+    if !value_types
+        .iter()
+        .any(|value_type| accepted_types.contains(value_type))
+    {
+        errors.push(ValidationError::IncompatibleValueType {
+            location,
+            value_types,
+            accepted_types,
+        });
+    }
+}
+
+fn is_bcm_value_compatible(
+    peripheral: &BcmPeripheral,
+    value: &TargetValue<BcmValue>,
+    state_types: &BTreeMap<u32, ValueType>,
+) -> bool {
+    match (peripheral, value) {
+        (BcmPeripheral::Single(_), TargetValue::Const(value)) => {
+            matches!(value, BcmValue::Binary(_) | BcmValue::Single(_))
+        }
+        (BcmPeripheral::Rgb(_, _, _), TargetValue::Const(value)) => {
+            matches!(value, BcmValue::Rgb(_, _, _) | BcmValue::RgbB(_, _, _, _))
+        }
+        (BcmPeripheral::Rgbw(_, _, _, _), TargetValue::Const(value)) => matches!(
+            value,
+            BcmValue::Rgbw(_, _, _, _) | BcmValue::RgbwB(_, _, _, _, _)
+        ),
+        (_, TargetValue::State(state_index)) => match state_types.get(state_index) {
+            Some(ValueType::U8) => matches!(peripheral, BcmPeripheral::Single(_)),
+            Some(ValueType::Rgb) | Some(ValueType::RgbB) => {
+                matches!(peripheral, BcmPeripheral::Rgb(_, _, _))
+            }
+            Some(ValueType::Rgbw) | Some(ValueType::RgbwB) => {
+                matches!(peripheral, BcmPeripheral::Rgbw(_, _, _, _))
+            }
+            // Wrong or unknown state types are reported by the state type validation
+            _ => true,
+        },
+    }
+}
+
+fn is_relay_value_compatible(peripheral: &RelayPeripheral, value: &RelayValue) -> bool {
+    matches!(
+        (peripheral, value),
+        (RelayPeripheral::Single(_), RelayValue::Single(_))
+            | (
+                RelayPeripheral::DoubleExclusive(_, _),
+                RelayValue::DoubleExclusive(_)
+            )
+    )
+}
+
+fn infer_state_types_from_accesses(
+    config: &Config,
+    accesses: &[(ComponentLocation, StateAccess)],
+) -> BTreeMap<u32, ValueType> {
+    let mut state_types = BTreeMap::new();
+
+    for (state_index, value) in config.initial_state.iter() {
+        state_types.insert(*state_index, value.get_type());
+    }
+
+    for (_, access) in accesses.iter() {
+        if let StateAccess::Write(state_index, Some(written_type)) = access {
+            state_types.entry(*state_index).or_insert(*written_type);
+        }
+    }
+
+    loop {
+        let mut changed = false;
+
+        for (_, access) in accesses.iter() {
+            if let StateAccess::Copy(state_index, source_state_index) = access {
+                if state_types.contains_key(state_index) {
+                    continue;
+                }
+
+                if let Some(source_type) = state_types.get(source_state_index).copied() {
+                    state_types.insert(*state_index, source_type);
+                    changed = true;
+                }
+            }
+        }
+
+        if !changed {
+            break state_types;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::boxed::Box;
+
+    use ross_protocol::event::relay::RelayDoubleExclusiveValue;
+
+    use crate::calendar::Calendar;
+    use crate::creator::Creator;
+    use crate::event_processor::EventProcessor;
+    use crate::extractor::{
+        ButtonIndexExtractor, EventCodeExtractor, NoneExtractor, PacketExtractor,
+    };
+    use crate::filter::{
+        CoercionPolicy, FlipStateFilter, IncrementStateByConstFilter, OverflowMode,
+        SetStateToConstFilter, SetStateToStateFilter, StateEqualToStateFilter,
+        ValueEqualToConstFilter,
+    };
+    use crate::producer::{
+        BcmChangeBrightnessProducer, BcmChangeBrightnessStateProducer, NoneProducer,
+        PacketProducer, Producer, RelaySetValueProducer,
+    };
+    use crate::Value;
+
+    fn config_with_processor(
+        initial_state: BTreeMap<u32, Value>,
+        event_processor: EventProcessor,
+    ) -> Config {
+        Config {
+            peripherals: BTreeMap::new(),
+            initial_state,
+            event_processors: vec![event_processor],
+            time_zone: None,
+            calendars: BTreeMap::new(),
+            gesture_timings: None,
+        }
+    }
+
+    #[test]
+    fn valid_test() {
+        let mut initial_state = BTreeMap::new();
+        initial_state.insert(0, Value::Bool(false));
+        initial_state.insert(1, Value::U8(0xff));
+
+        let mut config = config_with_processor(
+            initial_state,
+            EventProcessor {
+                matcher: Matcher::And(
+                    Box::new(Matcher::Single {
+                        extractor: Box::new(EventCodeExtractor::new()),
+                        filter: Box::new(ValueEqualToConstFilter::new(
+                            Value::U16(0x0000),
+                            CoercionPolicy::Strict,
+                        )),
+                    }),
+                    Box::new(Matcher::Single {
+                        extractor: Box::new(NoneExtractor::new()),
+                        filter: Box::new(FlipStateFilter::new(0)),
+                    }),
+                ),
+                creators: vec![Creator {
+                    extractor: Box::new(NoneExtractor::new()),
+                    producer: Box::new(BcmChangeBrightnessStateProducer::new(0xabab, 0x00, 1)),
+                    matcher: None,
+                }],
+            },
+        );
+
+        config.peripherals.insert(
+            0,
+            Peripheral::Bcm(BcmPeripheral::Single(0x00), vec![0xabab]),
+        );
+
+        assert_eq!(validate_state_types(&config), vec![]);
+        assert_eq!(validate_value_types(&config), vec![]);
+        assert_eq!(validate_peripheral_references(&config), vec![]);
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn state_type_mismatch_test() {
+        let mut initial_state = BTreeMap::new();
+        initial_state.insert(0, Value::U8(0x00));
+
+        let config = config_with_processor(
+            initial_state,
+            EventProcessor {
+                matcher: Matcher::Single {
+                    extractor: Box::new(NoneExtractor::new()),
+                    filter: Box::new(FlipStateFilter::new(0)),
+                },
+                creators: vec![Creator {
+                    extractor: Box::new(NoneExtractor::new()),
+                    producer: Box::new(NoneProducer::new()),
+                    matcher: Some(Matcher::Single {
+                        extractor: Box::new(NoneExtractor::new()),
+                        filter: Box::new(IncrementStateByConstFilter::new(
+                            0,
+                            Value::U16(0x0001),
+                            OverflowMode::Wrap,
+                            CoercionPolicy::Strict,
+                        )),
+                    }),
+                }],
+            },
+        );
+
+        assert_eq!(
+            validate_state_types(&config),
+            vec![
+                ValidationError::StateTypeMismatch {
+                    location: ComponentLocation::EventProcessor(0),
+                    state_index: 0,
+                    state_type: ValueType::U8,
+                    accepted_types: vec![ValueType::Bool],
+                },
+                ValidationError::StateTypeMismatch {
+                    location: ComponentLocation::Creator(0, 0),
+                    state_index: 0,
+                    state_type: ValueType::U8,
+                    accepted_types: vec![ValueType::U16],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn conflicting_state_write_test() {
+        let mut initial_state = BTreeMap::new();
+        initial_state.insert(0, Value::U8(0x00));
+
+        let config = config_with_processor(
+            initial_state,
+            EventProcessor {
+                matcher: Matcher::And(
+                    Box::new(Matcher::Single {
+                        extractor: Box::new(NoneExtractor::new()),
+                        filter: Box::new(SetStateToConstFilter::new(0, Value::Bool(true))),
+                    }),
+                    Box::new(Matcher::Single {
+                        extractor: Box::new(NoneExtractor::new()),
+                        filter: Box::new(SetStateToConstFilter::new(0, Value::U8(0xff))),
+                    }),
+                ),
+                creators: vec![],
+            },
+        );
+
+        assert_eq!(
+            validate_state_types(&config),
+            vec![ValidationError::ConflictingStateWrite {
+                location: ComponentLocation::EventProcessor(0),
+                state_index: 0,
+                state_type: ValueType::U8,
+                written_type: ValueType::Bool,
+            }]
+        );
+    }
+
+    #[test]
+    fn compared_state_type_mismatch_test() {
+        let mut initial_state = BTreeMap::new();
+        initial_state.insert(0, Value::U8(0x00));
+        initial_state.insert(1, Value::U16(0x0000));
+
+        let config = config_with_processor(
+            initial_state,
+            EventProcessor {
+                matcher: Matcher::Single {
+                    extractor: Box::new(NoneExtractor::new()),
+                    filter: Box::new(StateEqualToStateFilter::new(0, 1, CoercionPolicy::Strict)),
+                },
+                creators: vec![],
+            },
+        );
+
+        assert_eq!(
+            validate_state_types(&config),
+            vec![ValidationError::StateTypeMismatch {
+                location: ComponentLocation::EventProcessor(0),
+                state_index: 0,
+                state_type: ValueType::U8,
+                accepted_types: vec![ValueType::U16],
+            }]
+        );
+
+        let mut initial_state = BTreeMap::new();
+        initial_state.insert(0, Value::U8(0x00));
+        initial_state.insert(1, Value::U16(0x0000));
+
+        let config = config_with_processor(
+            initial_state,
+            EventProcessor {
+                matcher: Matcher::Single {
+                    extractor: Box::new(NoneExtractor::new()),
+                    filter: Box::new(StateEqualToStateFilter::new(0, 1, CoercionPolicy::Widen)),
+                },
+                creators: vec![],
+            },
+        );
+
+        assert_eq!(validate_state_types(&config), vec![]);
+    }
+
+    #[test]
+    fn uninitialized_state_test() {
+        let config = config_with_processor(
+            BTreeMap::new(),
+            EventProcessor {
+                matcher: Matcher::Single {
+                    extractor: Box::new(NoneExtractor::new()),
+                    filter: Box::new(SetStateToConstFilter::new(0, Value::Rgb(0x01, 0x23, 0x45))),
+                },
+                creators: vec![Creator {
+                    extractor: Box::new(NoneExtractor::new()),
+                    producer: Box::new(BcmChangeBrightnessStateProducer::new(0xabab, 0x00, 0)),
+                    matcher: None,
+                }],
+            },
+        );
+
+        assert_eq!(
+            validate_state_types(&config),
+            vec![ValidationError::UninitializedState {
+                location: ComponentLocation::Creator(0, 0),
+                state_index: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn infer_state_types_test() {
+        let mut initial_state = BTreeMap::new();
+        initial_state.insert(0, Value::U32(0x0000_0000));
+
+        let config = config_with_processor(
+            initial_state,
+            EventProcessor {
+                matcher: Matcher::And(
+                    Box::new(Matcher::Single {
+                        extractor: Box::new(NoneExtractor::new()),
+                        filter: Box::new(SetStateToStateFilter::new(2, 1)),
+                    }),
+                    Box::new(Matcher::Single {
+                        extractor: Box::new(NoneExtractor::new()),
+                        filter: Box::new(SetStateToStateFilter::new(1, 0)),
+                    }),
+                ),
+                creators: vec![],
+            },
+        );
+
+        let mut expected_state_types = BTreeMap::new();
+        expected_state_types.insert(0, ValueType::U32);
+        expected_state_types.insert(1, ValueType::U32);
+        expected_state_types.insert(2, ValueType::U32);
+
+        assert_eq!(infer_state_types(&config), expected_state_types);
+    }
+
+    #[test]
+    fn incompatible_value_type_test() {
+        let config = config_with_processor(
+            BTreeMap::new(),
+            EventProcessor {
+                matcher: Matcher::Single {
+                    extractor: Box::new(ButtonIndexExtractor::new()),
+                    filter: Box::new(ValueEqualToConstFilter::new(
+                        Value::U16(0x0001),
+                        CoercionPolicy::Strict,
+                    )),
+                },
+                creators: vec![
+                    Creator {
+                        extractor: Box::new(PacketExtractor::new()),
+                        producer: Box::new(PacketProducer::new(0xabab)),
+                        matcher: None,
+                    },
+                    Creator {
+                        extractor: Box::new(NoneExtractor::new()),
+                        producer: Box::new(PacketProducer::new(0xabab)),
+                        matcher: None,
+                    },
+                ],
+            },
+        );
+
+        assert_eq!(
+            config.validate(),
+            Err(vec![
+                ValidationError::IncompatibleValueType {
+                    location: ComponentLocation::EventProcessor(0),
+                    value_types: vec![ExtractorValueType::U8],
+                    accepted_types: vec![ExtractorValueType::U16],
+                },
+                ValidationError::IncompatibleValueType {
+                    location: ComponentLocation::Creator(0, 1),
+                    value_types: vec![ExtractorValueType::None],
+                    accepted_types: vec![ExtractorValueType::Packet],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn peripheral_reference_test() {
+        let creator = |producer: Box<dyn Producer>| Creator {
+            extractor: Box::new(NoneExtractor::new()),
+            producer,
+            matcher: None,
+        };
+
+        let mut config = config_with_processor(
+            BTreeMap::new(),
+            EventProcessor {
+                matcher: Matcher::Single {
+                    extractor: Box::new(NoneExtractor::new()),
+                    filter: Box::new(ValueEqualToConstFilter::new(
+                        Value::Bool(true),
+                        CoercionPolicy::Strict,
+                    )),
+                },
+                creators: vec![
+                    creator(Box::new(BcmChangeBrightnessProducer::new(
+                        0xabab,
+                        0x00,
+                        BcmValue::Rgb(0x01, 0x23, 0x45),
+                    ))),
+                    creator(Box::new(RelaySetValueProducer::new(
+                        0xcdcd,
+                        0x01,
+                        RelayValue::DoubleExclusive(RelayDoubleExclusiveValue::FirstChannelOn),
+                    ))),
+                    creator(Box::new(BcmChangeBrightnessProducer::new(
+                        0xabab,
+                        0x02,
+                        BcmValue::Single(0xff),
+                    ))),
+                    creator(Box::new(BcmChangeBrightnessProducer::new(
+                        0xabab,
+                        0x01,
+                        BcmValue::Single(0xff),
+                    ))),
+                    creator(Box::new(BcmChangeBrightnessProducer::new(
+                        0x0000,
+                        0x00,
+                        BcmValue::Single(0xff),
+                    ))),
+                    creator(Box::new(RelaySetValueProducer::new(
+                        0xcdcd,
+                        0x01,
+                        RelayValue::Single(true),
+                    ))),
+                ],
+            },
+        );
+
+        config.peripherals.insert(
+            0,
+            Peripheral::Bcm(BcmPeripheral::Single(0x00), vec![0xabab]),
+        );
+        config.peripherals.insert(
+            1,
+            Peripheral::Relay(RelayPeripheral::Single(0x00), vec![0xcdcd]),
+        );
+
+        assert_eq!(
+            validate_peripheral_references(&config),
+            vec![
+                ValidationError::IncompatiblePeripheralValue {
+                    location: ComponentLocation::Creator(0, 0),
+                    peripheral_index: 0,
+                },
+                ValidationError::IncompatiblePeripheralValue {
+                    location: ComponentLocation::Creator(0, 1),
+                    peripheral_index: 1,
+                },
+                ValidationError::UnknownPeripheral {
+                    location: ComponentLocation::Creator(0, 2),
+                    peripheral_index: 2,
+                },
+                ValidationError::PeripheralTypeMismatch {
+                    location: ComponentLocation::Creator(0, 3),
+                    peripheral_index: 1,
+                },
+                ValidationError::UnknownGatewayAddress {
+                    location: ComponentLocation::Creator(0, 4),
+                    peripheral_index: 0,
+                    address: 0x0000,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn calendar_reference_test() {
+        let mut config = config_with_processor(
+            BTreeMap::new(),
+            EventProcessor {
+                matcher: Matcher::And(
+                    Box::new(Matcher::Single {
+                        extractor: Box::new(NoneExtractor::new()),
+                        filter: Box::new(DateInCalendarFilter::new(0, false)),
+                    }),
+                    Box::new(Matcher::Not(Box::new(Matcher::Single {
+                        extractor: Box::new(NoneExtractor::new()),
+                        filter: Box::new(DateInCalendarFilter::new(1, true)),
+                    }))),
+                ),
+                creators: vec![Creator {
+                    extractor: Box::new(NoneExtractor::new()),
+                    producer: Box::new(NoneProducer::new()),
+                    matcher: Some(Matcher::Single {
+                        extractor: Box::new(NoneExtractor::new()),
+                        filter: Box::new(DateInCalendarFilter::new(2, true)),
+                    }),
+                }],
+            },
+        );
+
+        config.calendars.insert(1, Calendar { entries: vec![] });
+
+        assert_eq!(
+            validate_calendar_references(&config),
+            vec![
+                ValidationError::UnknownCalendar {
+                    location: ComponentLocation::EventProcessor(0),
+                    calendar_index: 0,
+                },
+                ValidationError::UnknownCalendar {
+                    location: ComponentLocation::Creator(0, 0),
+                    calendar_index: 2,
+                },
+            ]
+        );
+    }
+}